@@ -0,0 +1,8 @@
+CREATE TABLE `task_tags` (
+	`task_id` text NOT NULL,
+	`tag` text NOT NULL,
+	PRIMARY KEY(`task_id`, `tag`),
+	FOREIGN KEY (`task_id`) REFERENCES `tasks`(`id`) ON UPDATE no action ON DELETE cascade
+);
+--> statement-breakpoint
+CREATE INDEX `idx_task_tags_tag` ON `task_tags` (`tag`);
@@ -0,0 +1,5 @@
+ALTER TABLE `messages` ADD COLUMN `provider_id` text;
+--> statement-breakpoint
+ALTER TABLE `messages` ADD COLUMN `model` text;
+--> statement-breakpoint
+CREATE INDEX `idx_messages_provider_model` ON `messages` (`provider_id`,`model`);
@@ -1,5 +1,8 @@
+use base64::{engine::general_purpose::STANDARD, Engine as _};
 use serde::Deserialize;
 use serde_json::{json, Value};
+use crate::runtime::run_blocking;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use tauri::webview::{PageLoadEvent, WebviewBuilder};
 use tauri::{AppHandle, Emitter, Manager, Webview, WebviewUrl, Window};
@@ -88,6 +91,135 @@ fn get_webview(app: &AppHandle) -> Option<Webview> {
   app.get_webview(BROWSER_VIEW_LABEL)
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CaptureFormat {
+  Png,
+  Jpeg,
+}
+
+impl CaptureFormat {
+  fn parse(value: Option<&str>) -> Option<Self> {
+    match value.map(|v| v.trim().to_lowercase()).as_deref() {
+      None | Some("") | Some("png") => Some(CaptureFormat::Png),
+      Some("jpeg") | Some("jpg") => Some(CaptureFormat::Jpeg),
+      _ => None,
+    }
+  }
+
+  fn mime(self) -> &'static str {
+    match self {
+      CaptureFormat::Png => "image/png",
+      CaptureFormat::Jpeg => "image/jpeg",
+    }
+  }
+
+  fn extension(self) -> &'static str {
+    match self {
+      CaptureFormat::Png => "png",
+      CaptureFormat::Jpeg => "jpg",
+    }
+  }
+}
+
+const CAPTURE_FILE_PREFIX: &str = "emdash-browser-capture-";
+
+// Temp file for a capture; removed on drop unless handed off with `keep`.
+struct CaptureFile {
+  path: PathBuf,
+  keep: bool,
+}
+
+impl CaptureFile {
+  fn new(format: CaptureFormat) -> Self {
+    let path = std::env::temp_dir().join(format!(
+      "{CAPTURE_FILE_PREFIX}{}.{}",
+      uuid::Uuid::new_v4(),
+      format.extension()
+    ));
+    CaptureFile { path, keep: false }
+  }
+
+  fn keep(mut self) -> PathBuf {
+    self.keep = true;
+    std::mem::take(&mut self.path)
+  }
+}
+
+impl Drop for CaptureFile {
+  fn drop(&mut self) {
+    if !self.keep {
+      let _ = std::fs::remove_file(&self.path);
+    }
+  }
+}
+
+/// Whether `path` is a temp file written by browser_view_capture.
+pub(crate) fn is_capture_file(path: &Path) -> bool {
+  path.parent() == Some(std::env::temp_dir().as_path())
+    && path
+      .file_name()
+      .and_then(|name| name.to_str())
+      .is_some_and(|name| name.starts_with(CAPTURE_FILE_PREFIX))
+}
+
+// WebKitGTK exposes an async snapshot API; the callback fires on the GTK main
+// thread, so the encoded bytes are handed back over a channel.
+#[cfg(target_os = "linux")]
+fn capture_webview(webview: &Webview, format: CaptureFormat, quality: u8) -> Result<Vec<u8>, String> {
+  use std::sync::mpsc;
+  use std::time::Duration;
+  use webkit2gtk::{SnapshotOptions, SnapshotRegion, WebViewExt};
+
+  let (tx, rx) = mpsc::channel::<Result<Vec<u8>, String>>();
+  webview
+    .with_webview(move |platform| {
+      platform.inner().snapshot(
+        SnapshotRegion::Visible,
+        SnapshotOptions::NONE,
+        None::<&webkit2gtk::gio::Cancellable>,
+        move |result| {
+          let encoded = result
+            .map_err(|err| err.to_string())
+            .and_then(|surface| encode_surface(&surface, format, quality));
+          let _ = tx.send(encoded);
+        },
+      );
+    })
+    .map_err(|err| err.to_string())?;
+
+  rx.recv_timeout(Duration::from_secs(10))
+    .map_err(|_| "Timed out capturing browser view".to_string())?
+}
+
+#[cfg(target_os = "linux")]
+fn encode_surface(
+  surface: &gtk::cairo::Surface,
+  format: CaptureFormat,
+  quality: u8,
+) -> Result<Vec<u8>, String> {
+  let image = gtk::cairo::ImageSurface::try_from(surface.clone())
+    .map_err(|_| "Unexpected snapshot surface type".to_string())?;
+  let pixbuf = gtk::gdk::pixbuf_get_from_surface(surface, 0, 0, image.width(), image.height())
+    .ok_or_else(|| "Failed to read snapshot pixels".to_string())?;
+  let result = match format {
+    CaptureFormat::Png => pixbuf.save_to_bufferv("png", &[]),
+    CaptureFormat::Jpeg => {
+      let quality = quality.to_string();
+      pixbuf.save_to_bufferv("jpeg", &[("quality", quality.as_str())])
+    }
+  };
+  result.map_err(|err| err.to_string())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn capture_webview(
+  _webview: &Webview,
+  _format: CaptureFormat,
+  _quality: u8,
+) -> Result<Vec<u8>, String> {
+  Err("Capturing the browser view is not supported on this platform".to_string())
+}
+
 #[tauri::command]
 pub fn browser_view_show(
   window: Window,
@@ -191,6 +323,15 @@ pub fn browser_view_reload(app: AppHandle) -> Value {
   json!({ "ok": true })
 }
 
+#[tauri::command]
+pub fn browser_view_current_url(app: AppHandle) -> Value {
+  let url = get_webview(&app)
+    .and_then(|webview| webview.url().ok())
+    .map(|u| u.to_string())
+    .filter(|u| !u.is_empty() && u != "about:blank");
+  json!({ "ok": true, "url": url })
+}
+
 #[tauri::command]
 pub fn browser_view_open_devtools(_app: AppHandle) -> Value {
   #[cfg(debug_assertions)]
@@ -200,6 +341,112 @@ pub fn browser_view_open_devtools(_app: AppHandle) -> Value {
   json!({ "ok": true })
 }
 
+#[tauri::command]
+pub async fn browser_view_capture(
+  app: AppHandle,
+  format: Option<String>,
+  quality: Option<u8>,
+) -> Value {
+  let format = match CaptureFormat::parse(format.as_deref()) {
+    Some(format) => format,
+    None => return json!({ "ok": false, "error": "Unsupported capture format" }),
+  };
+  let quality = quality.unwrap_or(85).clamp(1, 100);
+  let webview = match get_webview(&app) {
+    Some(webview) => webview,
+    None => return json!({ "ok": false, "error": "Browser view is not open" }),
+  };
+
+  run_blocking(json!({ "ok": false, "error": "Task cancelled" }), move || {
+    let bytes = match capture_webview(&webview, format, quality) {
+      Ok(bytes) => bytes,
+      Err(err) => return json!({ "ok": false, "error": err }),
+    };
+    let data_url = format!("data:{};base64,{}", format.mime(), STANDARD.encode(&bytes));
+    // Persist a temp copy so the renderer can hand it to fs_save_attachment, which
+    // removes it once copied.
+    let capture = CaptureFile::new(format);
+    let path_value = match std::fs::write(&capture.path, &bytes) {
+      Ok(_) => Value::String(capture.keep().to_string_lossy().to_string()),
+      Err(_) => Value::Null,
+    };
+    json!({
+      "ok": true,
+      "mime": format.mime(),
+      "dataUrl": data_url,
+      "path": path_value
+    })
+  })
+  .await
+}
+
+fn clear_cookies(webview: &Webview) -> Result<usize, String> {
+  let cookies = webview.cookies().map_err(|err| err.to_string())?;
+  let mut removed = 0usize;
+  for cookie in cookies {
+    if webview.delete_cookie(cookie).is_ok() {
+      removed += 1;
+    }
+  }
+  Ok(removed)
+}
+
+#[cfg(target_os = "linux")]
+fn clear_cache(webview: &Webview) -> Result<bool, String> {
+  use webkit2gtk::{WebContextExt, WebViewExt};
+  webview
+    .with_webview(|platform| {
+      if let Some(context) = platform.inner().context() {
+        context.clear_cache();
+      }
+    })
+    .map_err(|err| err.to_string())?;
+  Ok(true)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn clear_cache(_webview: &Webview) -> Result<bool, String> {
+  Ok(false)
+}
+
+/// Clears webview data for the preview.
+///
+/// Supported scopes per platform:
+/// - `cookies`: all desktop platforms (via the webview cookie store).
+/// - `cache`: Linux only (WebKitGTK); a no-op reported as `supported: false` elsewhere.
+/// - `all`: all desktop platforms (clears every kind of browsing data).
+#[tauri::command]
+pub async fn browser_view_clear_storage(app: AppHandle, scope: Option<String>) -> Value {
+  let scope = scope
+    .map(|s| s.trim().to_lowercase())
+    .filter(|s| !s.is_empty())
+    .unwrap_or_else(|| "all".to_string());
+  if !matches!(scope.as_str(), "cookies" | "cache" | "all") {
+    return json!({ "ok": false, "error": "Unsupported scope" });
+  }
+  let webview = match get_webview(&app) {
+    Some(webview) => webview,
+    None => return json!({ "ok": true, "scope": scope, "supported": true }),
+  };
+
+  run_blocking(json!({ "ok": false, "error": "Task cancelled" }), move || {
+    let result = match scope.as_str() {
+      "cookies" => clear_cookies(&webview).map(|_| true),
+      "cache" => clear_cache(&webview),
+      _ => webview
+        .clear_all_browsing_data()
+        .map(|_| true)
+        .map_err(|err| err.to_string()),
+    };
+    match result {
+      Ok(true) => json!({ "ok": true, "scope": scope, "supported": true }),
+      Ok(false) => json!({ "ok": true, "scope": scope, "supported": false }),
+      Err(err) => json!({ "ok": false, "scope": scope, "error": err }),
+    }
+  })
+  .await
+}
+
 #[tauri::command]
 pub fn browser_view_clear(app: AppHandle) -> Value {
   if let Some(webview) = get_webview(&app) {
@@ -1,14 +1,17 @@
 use base64::{engine::general_purpose::STANDARD, Engine as _};
+use crate::http::http_agent_no_redirects;
 use crate::runtime::run_blocking;
+use crate::worktree;
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
 use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::io::Read;
-use std::net::TcpListener;
+use std::io::{BufRead, BufReader, Read};
+use std::net::{TcpListener, UdpSocket};
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Command, Stdio};
 use tauri::{AppHandle, Emitter, Manager};
 
 const CONFIG_RELATIVE_PATH: &str = ".emdash/config.json";
@@ -17,6 +20,18 @@ const DEFAULT_START_COMMAND: &str = "npm run dev";
 const DEFAULT_BUN_START_COMMAND: &str = "bun run dev";
 const DEFAULT_WORKDIR: &str = ".";
 const DEFAULT_PREVIEW_SERVICE: &str = "app";
+const CONFIG_HASH_LABEL: &str = "emdash.configHash";
+const BUILD_HASH_LABEL: &str = "emdash.buildHash";
+const COMPOSE_SANITIZED_PATH: &str = ".emdash/compose.sanitized.json";
+const COMPOSE_OVERRIDE_PATH: &str = ".emdash/compose.override.yml";
+const LOCKFILES: [&str; 6] = [
+  "package-lock.json",
+  "npm-shrinkwrap.json",
+  "pnpm-lock.yaml",
+  "yarn.lock",
+  "bun.lockb",
+  "bun.lock",
+];
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -27,6 +42,13 @@ pub struct ResolvedContainerPortConfig {
   pub preview: bool,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolvedContainerResources {
+  pub memory: Option<String>,
+  pub cpus: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ResolvedContainerConfig {
@@ -36,6 +58,18 @@ pub struct ResolvedContainerConfig {
   pub env_file: Option<String>,
   pub workdir: String,
   pub ports: Vec<ResolvedContainerPortConfig>,
+  #[serde(default)]
+  pub compose_files: Vec<String>,
+  #[serde(default)]
+  pub compose_profiles: Vec<String>,
+  #[serde(default)]
+  pub resources: Option<ResolvedContainerResources>,
+  #[serde(default)]
+  pub allow_shell_start: bool,
+  #[serde(default)]
+  pub image: Option<String>,
+  #[serde(default)]
+  pub dockerfile: Option<String>,
 }
 
 #[derive(Debug)]
@@ -73,12 +107,15 @@ pub struct ContainerStartArgs {
   task_path: String,
   run_id: Option<String>,
   mode: Option<String>,
+  reuse: Option<bool>,
+  dry_run: Option<bool>,
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ContainerStopArgs {
   task_id: String,
+  task_path: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -87,6 +124,12 @@ pub struct ContainerInspectArgs {
   task_id: String,
 }
 
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerPruneArgs {
+  all: Option<bool>,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ResolveIconArgs {
@@ -95,6 +138,14 @@ pub struct ResolveIconArgs {
   task_path: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolveIconsArgs {
+  services: Vec<String>,
+  allow_network: Option<bool>,
+  task_path: Option<String>,
+}
+
 #[derive(Default)]
 pub struct ContainerState {}
 
@@ -177,7 +228,24 @@ fn resolve_package_manager(
   Ok(normalized)
 }
 
-fn resolve_start_command(raw: Option<&Value>, package_manager: &str) -> Result<String, ContainerConfigError> {
+const SHELL_START_OPERATORS: &[&str] = &[";", "&&", "`", "$(", "|", "\n"];
+
+fn resolve_allow_shell_start(raw: Option<&Value>) -> Result<bool, ContainerConfigError> {
+  match raw {
+    None | Some(Value::Null) => Ok(false),
+    Some(Value::Bool(value)) => Ok(*value),
+    _ => Err(ContainerConfigError {
+      message: "`allowShellStart` must be a boolean".to_string(),
+      path: Some("allowShellStart".to_string()),
+    }),
+  }
+}
+
+fn resolve_start_command(
+  raw: Option<&Value>,
+  package_manager: &str,
+  allow_shell: bool,
+) -> Result<String, ContainerConfigError> {
   if raw.is_none() || matches!(raw, Some(Value::Null)) {
     return Ok(if package_manager == "bun" {
       DEFAULT_BUN_START_COMMAND.to_string()
@@ -192,6 +260,12 @@ fn resolve_start_command(raw: Option<&Value>, package_manager: &str) -> Result<S
       path: Some("start".to_string()),
     });
   }
+  if !allow_shell && SHELL_START_OPERATORS.iter().any(|op| value.contains(op)) {
+    return Err(ContainerConfigError {
+      message: "`start` contains shell operators (;, &&, |, backticks, or $( ). The start command runs through a shell inside the container, so chained or substituted commands are rejected by default to avoid running unintended code from a checked-in config. Set \"allowShellStart\": true in .emdash/config.json if you trust this command.".to_string(),
+      path: Some("start".to_string()),
+    });
+  }
   Ok(value.to_string())
 }
 
@@ -209,6 +283,36 @@ fn resolve_env_file(raw: Option<&Value>) -> Result<Option<String>, ContainerConf
   Ok(Some(value.to_string()))
 }
 
+// A custom image must ship `bash`, since the install + start script runs via `bash -lc`.
+fn resolve_image(raw: Option<&Value>) -> Result<Option<String>, ContainerConfigError> {
+  if raw.is_none() || matches!(raw, Some(Value::Null)) {
+    return Ok(None);
+  }
+  let value = raw.and_then(|v| v.as_str()).unwrap_or("").trim();
+  if value.is_empty() {
+    return Err(ContainerConfigError {
+      message: "`image` must be a non-empty string".to_string(),
+      path: Some("image".to_string()),
+    });
+  }
+  Ok(Some(value.to_string()))
+}
+
+// Path to a Dockerfile relative to the task root; takes precedence over `image`.
+fn resolve_dockerfile(raw: Option<&Value>) -> Result<Option<String>, ContainerConfigError> {
+  if raw.is_none() || matches!(raw, Some(Value::Null)) {
+    return Ok(None);
+  }
+  let value = raw.and_then(|v| v.as_str()).unwrap_or("").trim();
+  if value.is_empty() {
+    return Err(ContainerConfigError {
+      message: "`dockerfile` must be a non-empty string".to_string(),
+      path: Some("dockerfile".to_string()),
+    });
+  }
+  Ok(Some(value.to_string()))
+}
+
 fn resolve_workdir(raw: Option<&Value>) -> Result<String, ContainerConfigError> {
   if raw.is_none() || matches!(raw, Some(Value::Null)) {
     return Ok(DEFAULT_WORKDIR.to_string());
@@ -223,6 +327,82 @@ fn resolve_workdir(raw: Option<&Value>) -> Result<String, ContainerConfigError>
   Ok(value.to_string())
 }
 
+fn resolve_string_list(raw: Option<&Value>, key: &str) -> Result<Vec<String>, ContainerConfigError> {
+  if raw.is_none() || matches!(raw, Some(Value::Null)) {
+    return Ok(Vec::new());
+  }
+  let list = raw.and_then(|v| v.as_array()).ok_or_else(|| ContainerConfigError {
+    message: format!("`{}` must be an array of strings", key),
+    path: Some(key.to_string()),
+  })?;
+  let mut result = Vec::new();
+  for (idx, entry) in list.iter().enumerate() {
+    let value = entry.as_str().unwrap_or("").trim();
+    if value.is_empty() {
+      return Err(ContainerConfigError {
+        message: format!("`{}` entries must be non-empty strings", key),
+        path: Some(format!("{}[{}]", key, idx)),
+      });
+    }
+    result.push(value.to_string());
+  }
+  Ok(result)
+}
+
+fn resolve_memory_limit(raw: Option<&Value>) -> Result<Option<String>, ContainerConfigError> {
+  if raw.is_none() || matches!(raw, Some(Value::Null)) {
+    return Ok(None);
+  }
+  let invalid = || ContainerConfigError {
+    message: "`resources.memory` must be a number with a unit (b, k, m, or g), e.g. \"2g\"".to_string(),
+    path: Some("resources.memory".to_string()),
+  };
+  let value = raw.and_then(|v| v.as_str()).ok_or_else(invalid)?.trim().to_lowercase();
+  let (amount, unit) = value.split_at(value.len().saturating_sub(1));
+  if !matches!(unit, "b" | "k" | "m" | "g")
+    || amount.is_empty()
+    || !amount.chars().all(|c| c.is_ascii_digit())
+    || amount.parse::<u64>().map_or(true, |n| n == 0)
+  {
+    return Err(invalid());
+  }
+  Ok(Some(value))
+}
+
+fn resolve_cpu_limit(raw: Option<&Value>) -> Result<Option<String>, ContainerConfigError> {
+  if raw.is_none() || matches!(raw, Some(Value::Null)) {
+    return Ok(None);
+  }
+  let text = match raw {
+    Some(Value::String(s)) => s.trim().to_string(),
+    Some(Value::Number(n)) => n.to_string(),
+    _ => String::new(),
+  };
+  match text.parse::<f64>() {
+    Ok(cpus) if cpus.is_finite() && cpus > 0.0 => Ok(Some(text)),
+    _ => Err(ContainerConfigError {
+      message: "`resources.cpus` must be a positive number, e.g. \"1.5\"".to_string(),
+      path: Some("resources.cpus".to_string()),
+    }),
+  }
+}
+
+fn resolve_resources(raw: Option<&Value>) -> Result<Option<ResolvedContainerResources>, ContainerConfigError> {
+  if raw.is_none() || matches!(raw, Some(Value::Null)) {
+    return Ok(None);
+  }
+  let obj = raw.and_then(|v| v.as_object()).ok_or_else(|| ContainerConfigError {
+    message: "`resources` must be an object".to_string(),
+    path: Some("resources".to_string()),
+  })?;
+  let memory = resolve_memory_limit(obj.get("memory"))?;
+  let cpus = resolve_cpu_limit(obj.get("cpus"))?;
+  if memory.is_none() && cpus.is_none() {
+    return Ok(None);
+  }
+  Ok(Some(ResolvedContainerResources { memory, cpus }))
+}
+
 fn resolve_ports(raw: Option<&Value>) -> Result<Vec<ResolvedContainerPortConfig>, ContainerConfigError> {
   if raw.is_none() || matches!(raw, Some(Value::Null)) {
     return Ok(vec![default_port()]);
@@ -257,14 +437,19 @@ fn resolve_ports(raw: Option<&Value>) -> Result<Vec<ResolvedContainerPortConfig>
         path: Some(format!("{}.container", path)),
       });
     }
-    if let Some(protocol) = obj.get("protocol") {
-      if protocol.as_str().unwrap_or("").to_lowercase() != "tcp" && !protocol.is_null() {
-        return Err(ContainerConfigError {
-          message: "Only TCP protocol is supported in M1".to_string(),
-          path: Some(format!("{}.protocol", path)),
-        });
+    let protocol = match obj.get("protocol") {
+      None | Some(Value::Null) => "tcp".to_string(),
+      Some(value) => {
+        let normalized = value.as_str().unwrap_or("").trim().to_lowercase();
+        if normalized != "tcp" && normalized != "udp" {
+          return Err(ContainerConfigError {
+            message: "`protocol` must be \"tcp\" or \"udp\"".to_string(),
+            path: Some(format!("{}.protocol", path)),
+          });
+        }
+        normalized
       }
-    }
+    };
     if let Some(preview) = obj.get("preview") {
       if !preview.is_boolean() {
         return Err(ContainerConfigError {
@@ -276,7 +461,7 @@ fn resolve_ports(raw: Option<&Value>) -> Result<Vec<ResolvedContainerPortConfig>
     result.push(ResolvedContainerPortConfig {
       service: service.to_string(),
       container: container as u16,
-      protocol: "tcp".to_string(),
+      protocol,
       preview: obj.get("preview").and_then(|v| v.as_bool()).unwrap_or(false),
     });
   }
@@ -287,6 +472,12 @@ fn resolve_ports(raw: Option<&Value>) -> Result<Vec<ResolvedContainerPortConfig>
 }
 
 fn ensure_preview_port(ports: &mut Vec<ResolvedContainerPortConfig>) {
+  // Previews are HTTP, so only TCP ports can be the preview target.
+  for p in ports.iter_mut() {
+    if p.protocol != "tcp" {
+      p.preview = false;
+    }
+  }
   if ports.iter().any(|p| p.preview) {
     let mut seen_preview = false;
     for p in ports.iter_mut() {
@@ -300,7 +491,7 @@ fn ensure_preview_port(ports: &mut Vec<ResolvedContainerPortConfig>) {
     }
     return;
   }
-  if let Some(first) = ports.first_mut() {
+  if let Some(first) = ports.iter_mut().find(|p| p.protocol == "tcp") {
     first.preview = true;
   }
 }
@@ -325,10 +516,16 @@ fn resolve_container_config(
   let obj = input.as_object().cloned().unwrap_or_default();
   let version = resolve_version(obj.get("version"))?;
   let package_manager = resolve_package_manager(obj.get("packageManager"), inferred)?;
-  let start = resolve_start_command(obj.get("start"), &package_manager)?;
+  let allow_shell_start = resolve_allow_shell_start(obj.get("allowShellStart"))?;
+  let start = resolve_start_command(obj.get("start"), &package_manager, allow_shell_start)?;
   let env_file = resolve_env_file(obj.get("envFile"))?;
+  let image = resolve_image(obj.get("image"))?;
+  let dockerfile = resolve_dockerfile(obj.get("dockerfile"))?;
   let workdir = resolve_workdir(obj.get("workdir"))?;
   let ports = resolve_ports(obj.get("ports"))?;
+  let compose_files = resolve_string_list(obj.get("composeFiles"), "composeFiles")?;
+  let compose_profiles = resolve_string_list(obj.get("composeProfiles"), "composeProfiles")?;
+  let resources = resolve_resources(obj.get("resources"))?;
 
   Ok(ResolvedContainerConfig {
     version,
@@ -337,6 +534,12 @@ fn resolve_container_config(
     env_file,
     workdir,
     ports,
+    compose_files,
+    compose_profiles,
+    resources,
+    allow_shell_start,
+    image,
+    dockerfile,
   })
 }
 
@@ -403,12 +606,32 @@ fn load_task_container_config(task_path: &Path) -> ContainerConfigLoadResult {
   };
 
   match resolve_container_config(parsed, inferred) {
-    Ok(config) => ContainerConfigLoadResult {
-      ok: true,
-      config: Some(config),
-      source_path,
-      error: None,
-    },
+    Ok(config) => {
+      let missing = config
+        .compose_files
+        .iter()
+        .enumerate()
+        .find(|(_, rel)| !task_path.join(rel).is_file());
+      if let Some((idx, rel)) = missing {
+        return ContainerConfigLoadResult {
+          ok: false,
+          config: None,
+          source_path: source_path.clone(),
+          error: Some(ContainerConfigLoadError {
+            code: "VALIDATION_FAILED".to_string(),
+            message: format!("Compose file not found: {}", task_path.join(rel).display()),
+            config_path: source_path,
+            config_key: Some(format!("composeFiles[{}]", idx)),
+          }),
+        };
+      }
+      ContainerConfigLoadResult {
+        ok: true,
+        config: Some(config),
+        source_path,
+        error: None,
+      }
+    }
     Err(err) => {
       let config_path_value =
         source_path.clone().or_else(|| Some(config_path.to_string_lossy().to_string()));
@@ -452,7 +675,7 @@ impl PortManager {
     }
     let mut allocations = Vec::new();
     for req in requests {
-      let host_port = self.find_available_port().map_err(|e| e)?;
+      let host_port = self.find_available_port(&req.protocol).map_err(|e| e)?;
       self.reserved.insert(host_port);
       allocations.push(RunnerPortMapping {
         service: req.service.clone(),
@@ -464,7 +687,7 @@ impl PortManager {
     Ok(allocations)
   }
 
-  fn find_available_port(&mut self) -> Result<u16, String> {
+  fn find_available_port(&mut self, protocol: &str) -> Result<u16, String> {
     let mut attempted = HashSet::new();
     let range = self.max_port - self.min_port + 1;
     for _ in 0..self.max_attempts_per_port {
@@ -476,14 +699,17 @@ impl PortManager {
       if self.reserved.contains(&candidate) {
         continue;
       }
-      if self.check_port_availability(candidate) {
+      if self.check_port_availability(candidate, protocol) {
         return Ok(candidate);
       }
     }
     Err("Unable to allocate a free host port".to_string())
   }
 
-  fn check_port_availability(&self, port: u16) -> bool {
+  fn check_port_availability(&self, port: u16, protocol: &str) -> bool {
+    if protocol == "udp" {
+      return UdpSocket::bind((self.host.as_str(), port)).is_ok();
+    }
     TcpListener::bind((self.host.as_str(), port)).is_ok()
   }
 }
@@ -517,6 +743,9 @@ fn generate_run_id() -> String {
 }
 
 fn choose_preview_service(requests: &[ResolvedContainerPortConfig]) -> String {
+  let tcp: Vec<&ResolvedContainerPortConfig> =
+    requests.iter().filter(|r| r.protocol == "tcp").collect();
+  let requests = tcp.as_slice();
   let names = ["web", "app", "frontend", "ui"];
   for name in names {
     if let Some(p) = requests.iter().find(|r| r.service == name) {
@@ -533,6 +762,8 @@ fn choose_preview_service(requests: &[ResolvedContainerPortConfig]) -> String {
 }
 
 fn choose_preview_service_from_published(ports: &[RunnerPortMapping]) -> Option<String> {
+  let tcp: Vec<&RunnerPortMapping> = ports.iter().filter(|p| p.protocol == "tcp").collect();
+  let ports = tcp.as_slice();
   if ports.is_empty() {
     return None;
   }
@@ -576,12 +807,17 @@ fn emit_ports(
   let mapped: Vec<Value> = ports
     .iter()
     .map(|p| {
+      let url = if p.protocol == "tcp" {
+        Some(format!("http://localhost:{}", p.host))
+      } else {
+        None
+      };
       json!({
         "service": p.service,
-        "protocol": "tcp",
+        "protocol": p.protocol,
         "container": p.container,
         "host": p.host,
-        "url": format!("http://localhost:{}", p.host),
+        "url": url,
       })
     })
     .collect();
@@ -614,6 +850,201 @@ fn emit_error(app: &AppHandle, task_id: &str, run_id: &str, mode: &str, code: &s
   );
 }
 
+fn emit_log(app: &AppHandle, task_id: &str, run_id: &str, mode: &str, stream: &str, message: &str) {
+  emit_runner_event(
+    app,
+    json!({
+      "ts": now_ms(),
+      "taskId": task_id,
+      "runId": run_id,
+      "mode": mode,
+      "type": "log",
+      "stream": stream,
+      "message": message,
+    }),
+  );
+}
+
+/// Pulls `image` up front, streaming `docker pull` progress as log events, so a cold
+/// `docker run` doesn't sit silently on the download. No-op if the image is already local.
+fn ensure_image(app: &AppHandle, task_id: &str, run_id: &str, mode: &str, image: &str) -> Result<(), String> {
+  let local = Command::new("docker")
+    .args(["image", "inspect", "--format", "{{.Id}}", image])
+    .output()
+    .map(|out| out.status.success())
+    .unwrap_or(false);
+  if local {
+    return Ok(());
+  }
+
+  emit_log(app, task_id, run_id, mode, "stdout", &format!("Pulling image {}...", image));
+  let mut child = Command::new("docker")
+    .args(["pull", image])
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .spawn()
+    .map_err(|err| err.to_string())?;
+
+  let stderr = child.stderr.take();
+  let stderr_reader = std::thread::spawn(move || {
+    let mut buf = String::new();
+    if let Some(mut pipe) = stderr {
+      let _ = pipe.read_to_string(&mut buf);
+    }
+    buf
+  });
+  if let Some(stdout) = child.stdout.take() {
+    for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+      let line = line.trim();
+      if !line.is_empty() {
+        emit_log(app, task_id, run_id, mode, "stdout", line);
+      }
+    }
+  }
+
+  let status = child.wait().map_err(|err| err.to_string())?;
+  let stderr = stderr_reader.join().unwrap_or_default();
+  if status.success() {
+    Ok(())
+  } else {
+    let message = stderr.trim();
+    if message.is_empty() {
+      Err(format!("Failed to pull image {}", image))
+    } else {
+      Err(message.to_string())
+    }
+  }
+}
+
+fn built_image_tag(task_id: &str) -> String {
+  format!("emdash_ws_{}:latest", task_id.to_lowercase())
+}
+
+// Hashes the Dockerfile and any lockfiles in the workdir, so a rebuild only happens
+// when the inputs that usually matter for a dependency layer change.
+fn dockerfile_build_hash(dockerfile: &Path, workdir: &Path) -> Result<String, String> {
+  let mut hasher = Sha256::new();
+  hasher.update(fs::read(dockerfile).map_err(|err| err.to_string())?);
+  for name in LOCKFILES {
+    if let Ok(bytes) = fs::read(workdir.join(name)) {
+      hasher.update(name.as_bytes());
+      hasher.update(&bytes);
+    }
+  }
+  Ok(hex::encode(hasher.finalize()))
+}
+
+fn image_build_hash(tag: &str) -> Option<String> {
+  let format = format!("{{{{ index .Config.Labels \"{}\" }}}}", BUILD_HASH_LABEL);
+  let output = Command::new("docker")
+    .args(["image", "inspect", "--format", &format, tag])
+    .output()
+    .ok()?;
+  if !output.status.success() {
+    return None;
+  }
+  Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Builds `tag` from the task's Dockerfile with the task root as context, streaming build
+/// output as log events. Skipped when an image built from the same inputs already exists.
+#[allow(clippy::too_many_arguments)]
+fn ensure_built_image(
+  app: &AppHandle,
+  task_id: &str,
+  run_id: &str,
+  mode: &str,
+  task_path: &Path,
+  dockerfile: &Path,
+  tag: &str,
+  build_hash: &str,
+) -> Result<(), String> {
+  if image_build_hash(tag).as_deref() == Some(build_hash) {
+    emit_log(app, task_id, run_id, mode, "stdout", &format!("Using cached image {}", tag));
+    return Ok(());
+  }
+
+  emit_log(app, task_id, run_id, mode, "stdout", &format!("Building image {}...", tag));
+  let dockerfile_arg = dockerfile.to_string_lossy().to_string();
+  let label = format!("{}={}", BUILD_HASH_LABEL, build_hash);
+  let mut child = Command::new("docker")
+    .args([
+      "build",
+      "--progress=plain",
+      "-t",
+      tag,
+      "--label",
+      &label,
+      "-f",
+      &dockerfile_arg,
+      ".",
+    ])
+    .current_dir(task_path)
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .spawn()
+    .map_err(|err| err.to_string())?;
+
+  // BuildKit writes progress to stderr, the classic builder to stdout; forward both.
+  let stdout = child.stdout.take();
+  let stdout_app = app.clone();
+  let (stdout_task, stdout_run, stdout_mode) = (task_id.to_string(), run_id.to_string(), mode.to_string());
+  let stdout_reader = std::thread::spawn(move || {
+    if let Some(pipe) = stdout {
+      for line in BufReader::new(pipe).lines().map_while(Result::ok) {
+        let line = line.trim();
+        if !line.is_empty() {
+          emit_log(&stdout_app, &stdout_task, &stdout_run, &stdout_mode, "stdout", line);
+        }
+      }
+    }
+  });
+  let mut tail: Vec<String> = Vec::new();
+  if let Some(stderr) = child.stderr.take() {
+    for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+      let line = line.trim();
+      if line.is_empty() {
+        continue;
+      }
+      emit_log(app, task_id, run_id, mode, "stderr", line);
+      if tail.len() == 20 {
+        tail.remove(0);
+      }
+      tail.push(line.to_string());
+    }
+  }
+
+  let status = child.wait().map_err(|err| err.to_string())?;
+  let _ = stdout_reader.join();
+  if status.success() {
+    Ok(())
+  } else if tail.is_empty() {
+    Err(format!("Failed to build image {}", tag))
+  } else {
+    Err(tail.join("\n"))
+  }
+}
+
+fn resolve_compose_files(task_path: &Path, config: &ResolvedContainerConfig) -> Vec<PathBuf> {
+  if !config.compose_files.is_empty() {
+    return config.compose_files.iter().map(|rel| task_path.join(rel)).collect();
+  }
+  find_compose_file(task_path).into_iter().collect()
+}
+
+fn compose_base_args(compose_files: &[PathBuf], profiles: &[String]) -> Vec<String> {
+  let mut args: Vec<String> = vec!["compose".into()];
+  for profile in profiles {
+    args.push("--profile".into());
+    args.push(profile.clone());
+  }
+  for file in compose_files {
+    args.push("-f".into());
+    args.push(file.to_string_lossy().to_string());
+  }
+  args
+}
+
 fn find_compose_file(task_path: &Path) -> Option<PathBuf> {
   let candidates = [
     "docker-compose.yml",
@@ -648,7 +1079,7 @@ fn build_compose_override_yaml(mappings: &[RunnerPortMapping]) -> String {
       lines.push("      -".to_string());
       lines.push(format!("        target: {}", p.container));
       lines.push(format!("        published: {}", p.host));
-      lines.push("        protocol: tcp".to_string());
+      lines.push(format!("        protocol: {}", p.protocol));
     }
   }
 
@@ -705,10 +1136,16 @@ fn parse_compose_ps(out: &str, fallback: &[RunnerPortMapping]) -> Vec<RunnerPort
         .or_else(|| port.get("published"))
         .or_else(|| port.get("HostPort"))
         .and_then(|v| v.as_i64());
+      let protocol = port
+        .get("Protocol")
+        .or_else(|| port.get("protocol"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("tcp")
+        .to_lowercase();
       if let (Some(target), Some(published)) = (target, published) {
         result.push(RunnerPortMapping {
           service: service.clone(),
-          protocol: "tcp".to_string(),
+          protocol,
           container: target as u16,
           host: published as u16,
         });
@@ -722,16 +1159,15 @@ fn parse_compose_ps(out: &str, fallback: &[RunnerPortMapping]) -> Vec<RunnerPort
   }
 }
 
-fn load_compose_config_json(compose_file: &Path, task_path: &Path) -> Result<Value, String> {
+fn load_compose_config_json(
+  compose_files: &[PathBuf],
+  profiles: &[String],
+  task_path: &Path,
+) -> Result<Value, String> {
+  let mut args = compose_base_args(compose_files, profiles);
+  args.extend(["config".into(), "--format".into(), "json".into()]);
   let output = Command::new("docker")
-    .args([
-      "compose",
-      "-f",
-      compose_file.to_string_lossy().as_ref(),
-      "config",
-      "--format",
-      "json",
-    ])
+    .args(args)
     .current_dir(task_path)
     .output()
     .map_err(|err| err.to_string())?;
@@ -742,7 +1178,11 @@ fn load_compose_config_json(compose_file: &Path, task_path: &Path) -> Result<Val
   serde_json::from_str(&stdout).map_err(|err| err.to_string())
 }
 
-fn sanitize_compose_config(config: &Value, requested: &HashMap<String, Vec<u16>>) -> Value {
+fn sanitize_compose_config(
+  config: &Value,
+  requested: &HashMap<String, Vec<u16>>,
+  resources: Option<&ResolvedContainerResources>,
+) -> Value {
   let mut next = config.clone();
   let services = config.get("services").and_then(|v| v.as_object()).cloned().unwrap_or_default();
   let mut next_services = serde_json::Map::new();
@@ -770,6 +1210,22 @@ fn sanitize_compose_config(config: &Value, requested: &HashMap<String, Vec<u16>>
       let expose_vals: Vec<Value> = expose_ports.into_iter().map(|p| json!(p)).collect();
       svc_obj.insert("expose".to_string(), Value::Array(expose_vals));
     }
+    if let Some(res) = resources {
+      let mut deploy = svc_obj.get("deploy").and_then(|v| v.as_object()).cloned().unwrap_or_default();
+      let mut deploy_resources =
+        deploy.get("resources").and_then(|v| v.as_object()).cloned().unwrap_or_default();
+      let mut limits =
+        deploy_resources.get("limits").and_then(|v| v.as_object()).cloned().unwrap_or_default();
+      if let Some(memory) = &res.memory {
+        limits.insert("memory".to_string(), json!(memory));
+      }
+      if let Some(cpus) = &res.cpus {
+        limits.insert("cpus".to_string(), json!(cpus));
+      }
+      deploy_resources.insert("limits".to_string(), Value::Object(limits));
+      deploy.insert("resources".to_string(), Value::Object(deploy_resources));
+      svc_obj.insert("deploy".to_string(), Value::Object(deploy));
+    }
     next_services.insert(name, Value::Object(svc_obj));
   }
   if let Some(obj) = next.as_object_mut() {
@@ -778,64 +1234,112 @@ fn sanitize_compose_config(config: &Value, requested: &HashMap<String, Vec<u16>>
   next
 }
 
-fn discover_compose_ports(compose_file: &Path, task_path: &Path) -> Vec<(String, u16)> {
-  let output = Command::new("docker")
-    .args([
-      "compose",
-      "-f",
-      compose_file.to_string_lossy().as_ref(),
-      "config",
-      "--format",
-      "json",
-    ])
-    .current_dir(task_path)
-    .output();
-  let output = match output {
-    Ok(out) if out.status.success() => out,
-    _ => return Vec::new(),
-  };
-  let stdout = String::from_utf8_lossy(&output.stdout);
-  let cfg: Value = match serde_json::from_str(&stdout) {
-    Ok(v) => v,
-    Err(_) => return Vec::new(),
+const MAX_COMPOSE_RANGE_PORTS: usize = 100;
+
+// Expands "8000" or "8000-8005" into individual ports, capped at MAX_COMPOSE_RANGE_PORTS.
+fn parse_port_range(spec: &str) -> Vec<u16> {
+  let spec = spec.trim();
+  let (start, end) = match spec.split_once('-') {
+    Some((start, end)) => (start.trim().parse::<u16>(), end.trim().parse::<u16>()),
+    None => (spec.parse::<u16>(), spec.parse::<u16>()),
   };
+  match (start, end) {
+    (Ok(start), Ok(end)) if start > 0 && start <= end => (start..=end)
+      .take(MAX_COMPOSE_RANGE_PORTS)
+      .collect(),
+    _ => Vec::new(),
+  }
+}
+
+fn discover_compose_ports(
+  compose_files: &[PathBuf],
+  profiles: &[String],
+  task_path: &Path,
+) -> Vec<(String, u16, String)> {
+  match load_compose_config_json(compose_files, profiles, task_path) {
+    Ok(cfg) => compose_config_ports(&cfg),
+    Err(_) => Vec::new(),
+  }
+}
+
+fn compose_config_ports(cfg: &Value) -> Vec<(String, u16, String)> {
   let services = cfg.get("services").and_then(|v| v.as_object()).cloned().unwrap_or_default();
-  let mut result: Vec<(String, u16)> = Vec::new();
+  let mut result: Vec<(String, u16, String)> = Vec::new();
   for (svc_name, svc) in services {
     let ports = svc.get("ports").and_then(|v| v.as_array()).cloned().unwrap_or_default();
     for entry in ports {
-      if let Some(obj) = entry.as_object() {
+      let (targets, protocol) = if let Some(obj) = entry.as_object() {
         let target = obj
           .get("target")
           .or_else(|| obj.get("TargetPort"))
-          .or_else(|| obj.get("ContainerPort"))
-          .and_then(|v| v.as_i64());
+          .or_else(|| obj.get("ContainerPort"));
+        let targets = match target {
+          Some(Value::Number(n)) => n
+            .as_u64()
+            .and_then(|t| u16::try_from(t).ok())
+            .filter(|t| *t > 0)
+            .map(|t| vec![t]),
+          Some(Value::String(spec)) => Some(parse_port_range(spec)),
+          _ => None,
+        };
         let protocol = obj
           .get("protocol")
           .and_then(|v| v.as_str())
           .unwrap_or("tcp")
           .to_lowercase();
-        if protocol == "tcp" {
-          if let Some(t) = target {
-            if t > 0 && t <= 65535 {
-              result.push((svc_name.clone(), t as u16));
-            }
-          }
-        }
+        (targets.unwrap_or_default(), protocol)
       } else if let Some(raw) = entry.as_str() {
-        let main = raw.split('/').next().unwrap_or("");
-        let parts: Vec<&str> = main.split(':').collect();
-        let port_str = parts.last().unwrap_or(&"");
-        if let Ok(port) = port_str.parse::<u16>() {
-          result.push((svc_name.clone(), port));
-        }
+        // Short syntax: [ip:][host[-range]:]container[-range][/protocol]
+        let (main, protocol) = match raw.rsplit_once('/') {
+          Some((main, protocol)) => (main, protocol.trim().to_lowercase()),
+          None => (raw, "tcp".to_string()),
+        };
+        let container_spec = main.rsplit(':').next().unwrap_or("");
+        (parse_port_range(container_spec), protocol)
+      } else {
+        continue;
+      };
+      if protocol != "tcp" && protocol != "udp" {
+        continue;
+      }
+      for port in targets {
+        result.push((svc_name.clone(), port, protocol.clone()));
       }
     }
   }
   let mut seen = HashSet::new();
   result
     .into_iter()
-    .filter(|(svc, port)| seen.insert(format!("{}:{}", svc, port)))
+    .filter(|(svc, port, protocol)| seen.insert(format!("{}:{}/{}", svc, port, protocol)))
+    .collect()
+}
+
+// Ports published by the compose file win; the configured ports are only used when the
+// compose config declares none (or can't be loaded).
+fn compose_port_requests(
+  compose_files: &[PathBuf],
+  config: &ResolvedContainerConfig,
+  task_path: &Path,
+) -> Vec<ResolvedContainerPortConfig> {
+  let discovered = discover_compose_ports(compose_files, &config.compose_profiles, task_path);
+  port_requests_from_discovered(discovered, &config.ports)
+}
+
+fn port_requests_from_discovered(
+  discovered: Vec<(String, u16, String)>,
+  configured: &[ResolvedContainerPortConfig],
+) -> Vec<ResolvedContainerPortConfig> {
+  if discovered.is_empty() {
+    return configured.to_vec();
+  }
+  discovered
+    .into_iter()
+    .map(|(service, container, protocol)| ResolvedContainerPortConfig {
+      service,
+      container,
+      protocol,
+      preview: false,
+    })
     .collect()
 }
 
@@ -846,6 +1350,83 @@ fn resolve_preview_service(requests: &[ResolvedContainerPortConfig]) -> String {
   choose_preview_service(requests)
 }
 
+fn container_config_hash(config: &ResolvedContainerConfig, task_path: &Path, image: &str) -> String {
+  let mut hasher = Sha256::new();
+  hasher.update(serde_json::to_string(config).unwrap_or_default().as_bytes());
+  hasher.update(task_path.to_string_lossy().as_bytes());
+  hasher.update(image.as_bytes());
+  hex::encode(hasher.finalize())
+}
+
+// Returns the container id and published ports when a running container for this
+// task was started from the same config and mount, so it can be reused as-is.
+fn find_reusable_container(
+  container_name: &str,
+  task_path: &Path,
+  config_hash: &str,
+  config: &ResolvedContainerConfig,
+) -> Option<(String, Vec<RunnerPortMapping>)> {
+  let output = Command::new("docker")
+    .args(["inspect", "--type", "container", container_name])
+    .output()
+    .ok()?;
+  if !output.status.success() {
+    return None;
+  }
+  let parsed: Value = serde_json::from_slice(&output.stdout).ok()?;
+  let info = parsed.as_array()?.first()?;
+
+  let running = info
+    .get("State")
+    .and_then(|v| v.get("Running"))
+    .and_then(|v| v.as_bool())
+    .unwrap_or(false);
+  let label = info
+    .get("Config")
+    .and_then(|v| v.get("Labels"))
+    .and_then(|v| v.get(CONFIG_HASH_LABEL))
+    .and_then(|v| v.as_str());
+  if !running || label != Some(config_hash) {
+    return None;
+  }
+
+  let mount_source = task_path.to_string_lossy().to_string();
+  let mounted = info
+    .get("Mounts")
+    .and_then(|v| v.as_array())
+    .map(|mounts| {
+      mounts.iter().any(|m| {
+        m.get("Source").and_then(|v| v.as_str()) == Some(mount_source.as_str())
+          && m.get("Destination").and_then(|v| v.as_str()) == Some("/workspace")
+      })
+    })
+    .unwrap_or(false);
+  if !mounted {
+    return None;
+  }
+
+  let published = info.get("NetworkSettings").and_then(|v| v.get("Ports"))?;
+  let mut mappings = Vec::new();
+  for port in &config.ports {
+    let host = published
+      .get(format!("{}/{}", port.container, port.protocol))
+      .and_then(|v| v.as_array())
+      .and_then(|bindings| bindings.first())
+      .and_then(|b| b.get("HostPort"))
+      .and_then(|v| v.as_str())
+      .and_then(|v| v.parse::<u16>().ok())?;
+    mappings.push(RunnerPortMapping {
+      service: port.service.clone(),
+      protocol: port.protocol.clone(),
+      container: port.container,
+      host,
+    });
+  }
+
+  let id = info.get("Id").and_then(|v| v.as_str()).unwrap_or(container_name).to_string();
+  Some((id, mappings))
+}
+
 fn container_start_mock_run(
   app: &AppHandle,
   task_id: &str,
@@ -863,6 +1444,61 @@ fn container_start_mock_run(
   Ok(())
 }
 
+fn select_image(config: &ResolvedContainerConfig, detected_pm: &str, task_id: &str) -> String {
+  if config.dockerfile.is_some() {
+    return built_image_tag(task_id);
+  }
+  match &config.image {
+    Some(custom) => custom.clone(),
+    None if detected_pm == "bun" => "oven/bun:1.3.5".to_string(),
+    None => "node:20".to_string(),
+  }
+}
+
+// Resolves everything a real run would (compose files and their published ports, image, port
+// allocation, preview service) and emits the same events, but never starts anything; the
+// only Docker call is the read-only `docker compose config` a compose run also makes.
+fn container_start_dry_run(
+  app: &AppHandle,
+  task_id: &str,
+  task_path: &Path,
+  workdir_abs: &Path,
+  run_id: &str,
+  mode: &str,
+  config: &ResolvedContainerConfig,
+) -> Result<Value, String> {
+  let compose_files = resolve_compose_files(task_path, config);
+  let image = if compose_files.is_empty() {
+    Some(select_image(config, &detect_package_manager_from_workdir(workdir_abs), task_id))
+  } else {
+    None
+  };
+
+  let port_requests = if compose_files.is_empty() {
+    config.ports.clone()
+  } else {
+    compose_port_requests(&compose_files, config, task_path)
+  };
+  let mut port_manager = PortManager::new();
+  let ports = port_manager.allocate(&port_requests)?;
+  let preview_service = resolve_preview_service(&port_requests);
+
+  emit_lifecycle(app, task_id, run_id, mode, "building", None);
+  emit_lifecycle(app, task_id, run_id, mode, "starting", None);
+  emit_ports(app, task_id, run_id, mode, &ports, &preview_service);
+  emit_lifecycle(app, task_id, run_id, mode, "ready", None);
+
+  Ok(json!({
+    "image": image,
+    "composeFiles": compose_files
+      .iter()
+      .map(|file| file.to_string_lossy().to_string())
+      .collect::<Vec<String>>(),
+    "ports": ports,
+    "previewService": preview_service,
+  }))
+}
+
 fn container_start_compose_run(
   app: &AppHandle,
   task_id: &str,
@@ -870,7 +1506,7 @@ fn container_start_compose_run(
   run_id: &str,
   mode: &str,
   config: &ResolvedContainerConfig,
-  compose_file: &Path,
+  compose_files: &[PathBuf],
 ) -> Result<String, String> {
   let output = Command::new("docker")
     .args(["compose", "version"])
@@ -882,43 +1518,28 @@ fn container_start_compose_run(
     return Err(message.to_string());
   }
 
-  let discovered = discover_compose_ports(compose_file, task_path);
-  let mut port_requests: Vec<ResolvedContainerPortConfig> = Vec::new();
-  if !discovered.is_empty() {
-    for (service, container) in discovered {
-      port_requests.push(ResolvedContainerPortConfig {
-        service,
-        container,
-        protocol: "tcp".to_string(),
-        preview: false,
-      });
-    }
-  } else {
-    port_requests = config.ports.clone();
-  }
+  let port_requests = compose_port_requests(compose_files, config, task_path);
 
   let mut port_manager = PortManager::new();
   let allocations = port_manager.allocate(&port_requests)?;
 
-  let preview_service = if port_requests.iter().any(|p| p.preview) {
-    port_requests.iter().find(|p| p.preview).map(|p| p.service.clone()).unwrap_or_else(|| choose_preview_service(&port_requests))
-  } else {
-    choose_preview_service(&port_requests)
-  };
+  let preview_service = resolve_preview_service(&port_requests);
 
-  let sanitized_path = task_path.join(".emdash").join("compose.sanitized.json");
-  let override_path = task_path.join(".emdash").join("compose.override.yml");
+  let sanitized_path = task_path.join(COMPOSE_SANITIZED_PATH);
+  let override_path = task_path.join(COMPOSE_OVERRIDE_PATH);
   if let Some(parent) = sanitized_path.parent() {
     let _ = fs::create_dir_all(parent);
   }
+  worktree::ensure_git_excluded(task_path, COMPOSE_SANITIZED_PATH);
+  worktree::ensure_git_excluded(task_path, COMPOSE_OVERRIDE_PATH);
 
   let mut requested_map: HashMap<String, Vec<u16>> = HashMap::new();
   for req in &port_requests {
     requested_map.entry(req.service.clone()).or_default().push(req.container);
   }
 
-  if let Ok(cfg_json) = load_compose_config_json(compose_file, task_path) {
-    let sanitized = sanitize_compose_config(&cfg_json, &requested_map);
+  if let Ok(cfg_json) = load_compose_config_json(compose_files, &config.compose_profiles, task_path) {
+    let sanitized = sanitize_compose_config(&cfg_json, &requested_map, config.resources.as_ref());
     let _ = fs::write(&sanitized_path, serde_json::to_string_pretty(&sanitized).unwrap_or_default());
   }
 
@@ -929,6 +1550,10 @@ fn container_start_compose_run(
   let mut args: Vec<String> = vec![
     "compose".into(),
   ];
+  for profile in &config.compose_profiles {
+    args.push("--profile".into());
+    args.push(profile.clone());
+  }
   if let Some(env_file) = &config.env_file {
     let env_abs = task_path.join(env_file);
     if env_abs.exists() {
@@ -936,15 +1561,17 @@ fn container_start_compose_run(
       args.push(env_abs.to_string_lossy().to_string());
     }
   }
-  let compose_path_for_up = if sanitized_path.exists() {
-    sanitized_path.clone()
+  let compose_paths_for_up = if sanitized_path.exists() {
+    vec![sanitized_path.clone()]
   } else {
-    compose_file.to_path_buf()
+    compose_files.to_vec()
   };
   args.push("-p".into());
   args.push(project.clone());
-  args.push("-f".into());
-  args.push(compose_path_for_up.to_string_lossy().to_string());
+  for compose_path in &compose_paths_for_up {
+    args.push("-f".into());
+    args.push(compose_path.to_string_lossy().to_string());
+  }
   args.push("-f".into());
   args.push(override_path.to_string_lossy().to_string());
   args.push("up".into());
@@ -1109,6 +1736,53 @@ pub async fn container_start_run(app: AppHandle, args: ContainerStartArgs) -> Va
         });
       }
 
+      let dockerfile_abs = config.dockerfile.as_ref().map(|rel| abs_task_path.join(rel));
+      if let Some(dockerfile) = dockerfile_abs.as_ref().filter(|path| !path.is_file()) {
+        let message = format!("Dockerfile not found: {}", dockerfile.display());
+        emit_error(&app, task_id, &run_id, &mode, "INVALID_CONFIG", &message);
+        return json!({
+          "ok": false,
+          "error": {
+            "code": "INVALID_ARGUMENT",
+            "message": message,
+            "configPath": dockerfile.to_string_lossy(),
+            "configKey": "dockerfile",
+          }
+        });
+      }
+
+      if args.dry_run.unwrap_or(false) {
+        return match container_start_dry_run(
+          &app,
+          task_id,
+          &abs_task_path,
+          &workdir_abs,
+          &run_id,
+          &mode,
+          &config,
+        ) {
+          Ok(plan) => json!({
+            "ok": true,
+            "runId": run_id,
+            "sourcePath": load_result.source_path,
+            "dryRun": true,
+            "plan": plan,
+          }),
+          Err(err) => {
+            emit_error(&app, task_id, &run_id, &mode, "PORT_ALLOC_FAILED", &err);
+            json!({
+              "ok": false,
+              "error": {
+                "code": "PORT_ALLOC_FAILED",
+                "message": err,
+                "configPath": null,
+                "configKey": null,
+              }
+            })
+          }
+        };
+      }
+
       let docker_info = Command::new("docker")
         .args(["info", "--format", "{{.ServerVersion}}"]) 
         .output();
@@ -1126,8 +1800,9 @@ pub async fn container_start_run(app: AppHandle, args: ContainerStartArgs) -> Va
         });
   }
 
-  if let Some(compose_file) = find_compose_file(&abs_task_path) {
-    if let Err(err) = container_start_compose_run(&app, task_id, &abs_task_path, &run_id, &mode, &config, &compose_file) {
+  let compose_files = resolve_compose_files(&abs_task_path, &config);
+  if !compose_files.is_empty() {
+    if let Err(err) = container_start_compose_run(&app, task_id, &abs_task_path, &run_id, &mode, &config, &compose_files) {
       return json!({
         "ok": false,
         "error": {
@@ -1141,6 +1816,44 @@ pub async fn container_start_run(app: AppHandle, args: ContainerStartArgs) -> Va
     return json!({ "ok": true, "runId": run_id, "sourcePath": load_result.source_path });
   }
 
+  let container_name = format!("emdash_ws_{}", task_id);
+  let detected_pm = detect_package_manager_from_workdir(&workdir_abs);
+  let image = select_image(&config, &detected_pm, task_id);
+  let build_hash = match dockerfile_abs.as_ref() {
+    Some(dockerfile) => match dockerfile_build_hash(dockerfile, &workdir_abs) {
+      Ok(hash) => Some(hash),
+      Err(err) => {
+        emit_error(&app, task_id, &run_id, &mode, "IMAGE_BUILD_FAILED", &err);
+        return json!({
+          "ok": false,
+          "error": {
+            "code": "IMAGE_BUILD_FAILED",
+            "message": err,
+            "configPath": dockerfile.to_string_lossy(),
+            "configKey": "dockerfile",
+          }
+        });
+      }
+    },
+    None => None,
+  };
+  let image_key = match &build_hash {
+    Some(hash) => format!("{}@{}", image, hash),
+    None => image.clone(),
+  };
+  let config_hash = container_config_hash(&config, &abs_task_path, &image_key);
+  let preview_service = resolve_preview_service(&config.ports);
+
+  if args.reuse.unwrap_or(true) {
+    if let Some((container_id, ports)) =
+      find_reusable_container(&container_name, &abs_task_path, &config_hash, &config)
+    {
+      emit_ports(&app, task_id, &run_id, &mode, &ports, &preview_service);
+      emit_lifecycle(&app, task_id, &run_id, &mode, "ready", Some(container_id));
+      return json!({ "ok": true, "runId": run_id, "sourcePath": load_result.source_path, "reused": true });
+    }
+  }
+
   let mut port_manager = PortManager::new();
   let allocations = match port_manager.allocate(&config.ports) {
     Ok(ports) => ports,
@@ -1158,29 +1871,67 @@ pub async fn container_start_run(app: AppHandle, args: ContainerStartArgs) -> Va
     }
   };
 
-  let preview_service = resolve_preview_service(&config.ports);
   let preview_mapping = allocations.iter().find(|m| m.service == preview_service);
 
   emit_lifecycle(&app, task_id, &run_id, &mode, "building", None);
 
-  let container_name = format!("emdash_ws_{}", task_id);
+  let prepared = match (dockerfile_abs.as_ref(), build_hash.as_deref()) {
+    (Some(dockerfile), Some(hash)) => ensure_built_image(
+      &app,
+      task_id,
+      &run_id,
+      &mode,
+      &abs_task_path,
+      dockerfile,
+      &image,
+      hash,
+    )
+    .map_err(|err| ("IMAGE_BUILD_FAILED", err)),
+    _ => ensure_image(&app, task_id, &run_id, &mode, &image).map_err(|err| ("IMAGE_PULL_FAILED", err)),
+  };
+  if let Err((code, err)) = prepared {
+    emit_error(&app, task_id, &run_id, &mode, code, &err);
+    return json!({
+      "ok": false,
+      "error": {
+        "code": code,
+        "message": err,
+        "configPath": null,
+        "configKey": null,
+      }
+    });
+  }
+
   let _ = Command::new("docker")
     .args(["rm", "-f", &container_name])
     .output();
 
-  let detected_pm = detect_package_manager_from_workdir(&workdir_abs);
-  let image = if detected_pm == "bun" { "oven/bun:1.3.5" } else { "node:20" };
-
   let mut args_vec: Vec<String> = vec!["run".into(), "-d".into(), "--name".into(), container_name.clone()];
+  args_vec.push("--label".into());
+  args_vec.push(format!("{}={}", CONFIG_HASH_LABEL, config_hash));
   for mapping in &allocations {
     args_vec.push("-p".into());
-    args_vec.push(format!("{}:{}", mapping.host, mapping.container));
+    if mapping.protocol == "udp" {
+      args_vec.push(format!("{}:{}/udp", mapping.host, mapping.container));
+    } else {
+      args_vec.push(format!("{}:{}", mapping.host, mapping.container));
+    }
   }
   args_vec.push("-v".into());
   args_vec.push(format!("{}:/workspace", abs_task_path.to_string_lossy()));
   let workdir = Path::new("/workspace").join(config.workdir.replace('\\', "/"));
   args_vec.push("-w".into());
   args_vec.push(workdir.to_string_lossy().to_string());
+  if let Some(resources) = &config.resources {
+    if let Some(memory) = &resources.memory {
+      args_vec.push("--memory".into());
+      args_vec.push(memory.clone());
+    }
+    if let Some(cpus) = &resources.cpus {
+      args_vec.push("--cpus".into());
+      args_vec.push(cpus.clone());
+    }
+  }
   args_vec.push("-e".into());
   args_vec.push("HOST=0.0.0.0".into());
   if let Some(preview) = preview_mapping {
@@ -1215,7 +1966,7 @@ pub async fn container_start_run(app: AppHandle, args: ContainerStartArgs) -> Va
   };
   let script = format!("{} && {}", install_cmd, config.start);
 
-  args_vec.push(image.to_string());
+  args_vec.push(image);
   args_vec.push("bash".into());
   args_vec.push("-lc".into());
   args_vec.push(script);
@@ -1265,6 +2016,18 @@ pub async fn container_start_run(app: AppHandle, args: ContainerStartArgs) -> Va
   .await
 }
 
+// Deletes the compose files generated for a run; they're rewritten on every start.
+fn remove_compose_artifacts(task_path: &Path) -> Vec<String> {
+  [COMPOSE_SANITIZED_PATH, COMPOSE_OVERRIDE_PATH]
+    .into_iter()
+    .filter(|rel| {
+      let path = task_path.join(rel);
+      path.is_file() && fs::remove_file(&path).is_ok()
+    })
+    .map(|rel| rel.to_string())
+    .collect()
+}
+
 #[tauri::command]
 pub async fn container_stop_run(app: AppHandle, args: ContainerStopArgs) -> Value {
   run_blocking(
@@ -1285,8 +2048,23 @@ pub async fn container_stop_run(app: AppHandle, args: ContainerStopArgs) -> Valu
         .output();
       let _ = Command::new("docker").args(["rm", "-f", &container_name]).output();
 
+      let task_path = args
+        .task_path
+        .map(|path| path.trim().to_string())
+        .filter(|path| !path.is_empty())
+        .or_else(|| {
+          let state: tauri::State<crate::db::DbState> = app.state();
+          crate::db::task_ids_and_paths(&state)?
+            .into_iter()
+            .find(|(id, _)| id == task_id)
+            .map(|(_, path)| path)
+        });
+      let removed_artifacts = task_path
+        .map(|path| remove_compose_artifacts(Path::new(&path)))
+        .unwrap_or_default();
+
       emit_lifecycle(&app, task_id, &run_id, mode, "stopped", None);
-      json!({ "ok": true })
+      json!({ "ok": true, "removedArtifacts": removed_artifacts })
     },
   )
   .await
@@ -1327,6 +2105,106 @@ pub async fn container_inspect_run(args: ContainerInspectArgs) -> Value {
   .await
 }
 
+const WORKSPACE_PREFIX: &str = "emdash_ws_";
+
+/// Groups every `emdash_ws_*` container by its compose project (or its own name for
+/// containers started without compose).
+fn list_workspace_containers() -> Result<HashMap<String, Vec<String>>, String> {
+  let output = Command::new("docker")
+    .args([
+      "ps",
+      "-a",
+      "--format",
+      "{{.Names}}\t{{.Label \"com.docker.compose.project\"}}",
+    ])
+    .output()
+    .map_err(|err| err.to_string())?;
+  if !output.status.success() {
+    return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+  }
+  Ok(parse_workspace_containers(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parses `docker ps` lines of `<name>\t<compose project label>`.
+fn parse_workspace_containers(stdout: &str) -> HashMap<String, Vec<String>> {
+  let mut projects: HashMap<String, Vec<String>> = HashMap::new();
+  for line in stdout.lines() {
+    let mut parts = line.splitn(2, '\t');
+    let name = parts.next().unwrap_or("").trim();
+    let label = parts.next().unwrap_or("").trim();
+    let project = if label.starts_with(WORKSPACE_PREFIX) {
+      label
+    } else if name.starts_with(WORKSPACE_PREFIX) {
+      name
+    } else {
+      continue;
+    };
+    projects.entry(project.to_string()).or_default().push(name.to_string());
+  }
+  projects
+}
+
+#[tauri::command]
+pub async fn container_prune(app: AppHandle, args: Option<ContainerPruneArgs>) -> Value {
+  run_blocking(
+    json!({ "ok": false, "error": "Task cancelled" }),
+    move || {
+      let all = args.unwrap_or_default().all.unwrap_or(false);
+      let tasks = {
+        let state: tauri::State<crate::db::DbState> = app.state();
+        crate::db::task_ids_and_paths(&state)
+      };
+      if tasks.is_none() && !all {
+        return json!({ "ok": false, "error": "Task list unavailable; pass `all: true` to prune everything" });
+      }
+      // Compose lowercases project names, so match task ids case-insensitively.
+      let task_paths: HashMap<String, String> = tasks
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(id, path)| (id.to_lowercase(), path))
+        .collect();
+
+      let projects = match list_workspace_containers() {
+        Ok(projects) => projects,
+        Err(err) => return json!({ "ok": false, "error": err }),
+      };
+
+      let mut removed: Vec<Value> = Vec::new();
+      let mut kept: Vec<String> = Vec::new();
+      for (project, containers) in projects {
+        let task_id = project[WORKSPACE_PREFIX.len()..].to_lowercase();
+        let task_path = task_paths.get(&task_id);
+        if task_path.is_some() && !all {
+          kept.push(project);
+          continue;
+        }
+
+        let _ = Command::new("docker")
+          .args(["compose", "-p", &project, "down", "-v"])
+          .output();
+        let mut rm_args = vec!["rm".to_string(), "-f".to_string()];
+        rm_args.extend(containers.iter().cloned());
+        let _ = Command::new("docker").args(&rm_args).output();
+
+        let override_removed = task_path
+          .map(|path| Path::new(path).join(COMPOSE_OVERRIDE_PATH))
+          .filter(|path| path.is_file())
+          .map(|path| fs::remove_file(path).is_ok())
+          .unwrap_or(false);
+
+        removed.push(json!({
+          "project": project,
+          "containers": containers,
+          "overrideRemoved": override_removed,
+        }));
+      }
+
+      json!({ "ok": true, "removed": removed, "kept": kept })
+    },
+  )
+  .await
+}
+
 fn to_slug(name: &str) -> String {
   let mut out = String::new();
   for ch in name.trim().to_lowercase().chars() {
@@ -1390,6 +2268,20 @@ fn get_known_domain(service: &str) -> Option<&'static str> {
   }
 }
 
+// User-provided service -> domain overrides from the `serviceIcons` setting (already normalized).
+fn user_service_icon_domains(app: &AppHandle) -> HashMap<String, String> {
+  crate::settings::load_settings(app)
+    .get("serviceIcons")
+    .and_then(|v| v.as_object())
+    .map(|map| {
+      map
+        .iter()
+        .filter_map(|(service, domain)| Some((service.clone(), domain.as_str()?.to_string())))
+        .collect()
+    })
+    .unwrap_or_default()
+}
+
 fn allowlisted(domain: &str) -> bool {
   matches!(
     domain,
@@ -1397,27 +2289,103 @@ fn allowlisted(domain: &str) -> bool {
   )
 }
 
-fn fetch_https(url: &str, max_bytes: usize) -> Option<(Vec<u8>, String)> {
-  let resp = ureq::get(url).call().ok()?;
-  if resp.status() >= 300 && resp.status() < 400 {
-    if let Some(loc) = resp.header("Location") {
-      if loc.starts_with("https://") {
-        return fetch_https(loc, max_bytes);
+const ICON_FETCH_MAX_REDIRECTS: usize = 5;
+
+// Follows up to ICON_FETCH_MAX_REDIRECTS https redirects, stopping at any hop whose
+// host isn't accepted by `host_allowed`.
+fn fetch_https(
+  url: &str,
+  max_bytes: usize,
+  host_allowed: &dyn Fn(&str) -> bool,
+) -> Option<(Vec<u8>, String)> {
+  let agent = http_agent_no_redirects();
+  let mut current = tauri::Url::parse(url).ok()?;
+  for _ in 0..=ICON_FETCH_MAX_REDIRECTS {
+    if current.scheme() != "https" || !host_allowed(current.host_str()?) {
+      return None;
+    }
+    let resp = agent.get(current.as_str()).call().ok()?;
+    if (300..400).contains(&resp.status()) {
+      let loc = resp.header("Location")?;
+      current = current.join(loc).ok()?;
+      continue;
+    }
+    let ct = resp.header("Content-Type").unwrap_or("").to_string();
+    if !ct.to_lowercase().starts_with("image/") {
+      return None;
+    }
+    let mut buf = Vec::new();
+    let _ = resp
+      .into_reader()
+      .take(max_bytes as u64 + 1)
+      .read_to_end(&mut buf);
+    if buf.len() > max_bytes {
+      return None;
+    }
+    return Some((buf, ct));
+  }
+  None
+}
+
+fn icon_cache_dir(app: &AppHandle) -> PathBuf {
+  let dir = app
+    .path()
+    .app_data_dir()
+    .ok()
+    .unwrap_or_else(|| PathBuf::from("."))
+    .join("icons");
+  let _ = fs::create_dir_all(&dir);
+  dir
+}
+
+// Task-local `.emdash/service-icons` overrides first, then the on-disk cache.
+fn local_service_icon(cache_dir: &Path, slug: &str, task_path: Option<&str>) -> Option<String> {
+  if let Some(task_path) = task_path {
+    let base = Path::new(task_path)
+      .join(".emdash")
+      .join("service-icons");
+    let exts = ["svg", "png", "jpg", "jpeg", "ico"];
+    for ext in exts {
+      let candidate = base.join(format!("{}.{}", slug, ext));
+      if candidate.exists() {
+        if let Some(data_url) = read_file_as_data_url(&candidate) {
+          return Some(data_url);
+        }
       }
     }
-    return None;
   }
-  let ct = resp.header("Content-Type").unwrap_or("").to_string();
-  if !ct.to_lowercase().starts_with("image/") {
-    return None;
+
+  let cache_file = cache_dir.join(format!("{}.ico", slug));
+  if cache_file.exists() {
+    return read_file_as_data_url(&cache_file);
   }
-  let mut reader = resp.into_reader();
-  let mut buf = Vec::new();
-  let _ = reader.read_to_end(&mut buf);
-  if buf.len() > max_bytes {
+  None
+}
+
+fn fetch_service_icon(
+  cache_dir: &Path,
+  service: &str,
+  slug: &str,
+  user_domains: &HashMap<String, String>,
+) -> Option<String> {
+  let domain = user_domains
+    .get(&service.to_lowercase())
+    .cloned()
+    .or_else(|| get_known_domain(service).map(|d| d.to_string()))?;
+  if !allowlisted(&domain) && !user_domains.values().any(|d| d == &domain) {
     return None;
   }
-  Some((buf, ct))
+  let ddg_url = format!("https://icons.duckduckgo.com/ip3/{}.ico", domain);
+  let direct_url = format!("https://{}/favicon.ico", domain);
+  let host_allowed = |host: &str| {
+    host == "icons.duckduckgo.com"
+      || host == domain
+      || host.ends_with(&format!(".{}", domain))
+  };
+  let (bytes, ct) = fetch_https(&ddg_url, 200_000, &host_allowed)
+    .or_else(|| fetch_https(&direct_url, 200_000, &host_allowed))?;
+  let _ = fs::write(cache_dir.join(format!("{}.ico", slug)), &bytes);
+  Some(buffer_to_data_url(&bytes, &ct))
 }
 
 #[tauri::command]
@@ -1430,54 +2398,159 @@ pub async fn icons_resolve_service(app: AppHandle, args: ResolveIconArgs) -> Val
         return json!({ "ok": false });
       }
       let slug = to_slug(service);
+      let cache_dir = icon_cache_dir(&app);
 
-      if let Some(task_path) = args.task_path.as_deref() {
-        let base = Path::new(task_path)
-          .join(".emdash")
-          .join("service-icons");
-        let exts = ["svg", "png", "jpg", "jpeg", "ico"];
-        for ext in exts {
-          let candidate = base.join(format!("{}.{}", slug, ext));
-          if candidate.exists() {
-            if let Some(data_url) = read_file_as_data_url(&candidate) {
-              return json!({ "ok": true, "dataUrl": data_url });
-            }
-          }
+      let data_url = local_service_icon(&cache_dir, &slug, args.task_path.as_deref()).or_else(|| {
+        if args.allow_network.unwrap_or(false) {
+          fetch_service_icon(&cache_dir, service, &slug, &user_service_icon_domains(&app))
+        } else {
+          None
         }
+      });
+
+      match data_url {
+        Some(data_url) => json!({ "ok": true, "dataUrl": data_url }),
+        None => json!({ "ok": false }),
       }
+    },
+  )
+  .await
+}
 
-      let cache_dir = app
-        .path()
-        .app_data_dir()
-        .ok()
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join("icons");
-      let _ = fs::create_dir_all(&cache_dir);
-      let cache_file = cache_dir.join(format!("{}.ico", slug));
-      if cache_file.exists() {
-        if let Some(data_url) = read_file_as_data_url(&cache_file) {
-          return json!({ "ok": true, "dataUrl": data_url });
+#[tauri::command]
+pub async fn icons_resolve_services(app: AppHandle, args: ResolveIconsArgs) -> Value {
+  run_blocking(
+    json!({ "ok": false }),
+    move || {
+      let cache_dir = icon_cache_dir(&app);
+      let mut icons = serde_json::Map::new();
+      let mut missing: Vec<(String, String)> = Vec::new();
+      for service in &args.services {
+        let service = service.trim();
+        if service.is_empty() || icons.contains_key(service) {
+          continue;
+        }
+        let slug = to_slug(service);
+        match local_service_icon(&cache_dir, &slug, args.task_path.as_deref()) {
+          Some(data_url) => {
+            icons.insert(service.to_string(), Value::String(data_url));
+          }
+          None => {
+            icons.insert(service.to_string(), Value::Null);
+            missing.push((service.to_string(), slug));
+          }
         }
       }
 
-      if args.allow_network.unwrap_or(false) {
-        if let Some(domain) = get_known_domain(service) {
-          if allowlisted(domain) {
-            let ddg_url = format!("https://icons.duckduckgo.com/ip3/{}.ico", domain);
-            let direct_url = format!("https://{}/favicon.ico", domain);
-            let fetched =
-              fetch_https(&ddg_url, 200_000).or_else(|| fetch_https(&direct_url, 200_000));
-            if let Some((bytes, ct)) = fetched {
-              let _ = fs::write(&cache_file, &bytes);
-              let data_url = buffer_to_data_url(&bytes, &ct);
-              return json!({ "ok": true, "dataUrl": data_url });
-            }
+      if args.allow_network.unwrap_or(false) && !missing.is_empty() {
+        let user_domains = user_service_icon_domains(&app);
+        let fetched: Vec<(String, Option<String>)> = std::thread::scope(|scope| {
+          let handles: Vec<_> = missing
+            .iter()
+            .map(|(service, slug)| {
+              let cache_dir = &cache_dir;
+              let user_domains = &user_domains;
+              scope.spawn(move || {
+                (service.clone(), fetch_service_icon(cache_dir, service, slug, user_domains))
+              })
+            })
+            .collect();
+          handles.into_iter().filter_map(|h| h.join().ok()).collect()
+        });
+        for (service, data_url) in fetched {
+          if let Some(data_url) = data_url {
+            icons.insert(service, Value::String(data_url));
           }
         }
       }
 
-      json!({ "ok": false })
+      json!({ "ok": true, "icons": icons })
     },
   )
   .await
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn port(service: &str, container: u16, preview: bool) -> ResolvedContainerPortConfig {
+    ResolvedContainerPortConfig {
+      service: service.to_string(),
+      container,
+      protocol: "tcp".to_string(),
+      preview,
+    }
+  }
+
+  #[test]
+  fn workspace_containers_group_by_compose_project() {
+    let cases = [
+      ("", vec![]),
+      (
+        "emdash_ws_t1-web-1\temdash_ws_t1\nemdash_ws_t1-db-1\temdash_ws_t1\n",
+        vec![("emdash_ws_t1", vec!["emdash_ws_t1-web-1", "emdash_ws_t1-db-1"])],
+      ),
+      // Containers started without compose are grouped under their own name.
+      ("emdash_ws_t2\t\n", vec![("emdash_ws_t2", vec!["emdash_ws_t2"])]),
+      // A renamed container still belongs to its workspace project.
+      ("custom-name\temdash_ws_t3\n", vec![("emdash_ws_t3", vec!["custom-name"])]),
+      ("postgres\tother_project\nredis\t\n", vec![]),
+      ("  emdash_ws_t4  \n", vec![("emdash_ws_t4", vec!["emdash_ws_t4"])]),
+    ];
+    for (stdout, expected) in cases {
+      let expected: HashMap<String, Vec<String>> = expected
+        .into_iter()
+        .map(|(project, names)| {
+          (project.to_string(), names.into_iter().map(str::to_string).collect())
+        })
+        .collect();
+      assert_eq!(parse_workspace_containers(stdout), expected, "{:?}", stdout);
+    }
+  }
+
+  #[test]
+  fn compose_config_ports_reads_long_and_short_syntax() {
+    let cfg = json!({
+      "services": {
+        "web": { "ports": [{ "target": 3000, "published": "3000" }, "8080:80", "3000"] },
+        "db": { "ports": ["127.0.0.1:5432:5432/tcp", "9000-9001:9000-9001", "53:53/udp"] },
+        "worker": { "ports": ["7000/sctp"] },
+        "cache": {}
+      }
+    });
+    let mut ports = compose_config_ports(&cfg);
+    ports.sort();
+    let expected: Vec<(String, u16, String)> = vec![
+      ("db".into(), 53, "udp".into()),
+      ("db".into(), 5432, "tcp".into()),
+      ("db".into(), 9000, "tcp".into()),
+      ("db".into(), 9001, "tcp".into()),
+      ("web".into(), 80, "tcp".into()),
+      ("web".into(), 3000, "tcp".into()),
+    ];
+    assert_eq!(ports, expected);
+  }
+
+  #[test]
+  fn discovered_compose_ports_replace_configured_ports() {
+    let configured = vec![port("app", 4000, true)];
+
+    let fallback = port_requests_from_discovered(Vec::new(), &configured);
+    assert_eq!(fallback.len(), 1);
+    assert_eq!((fallback[0].service.as_str(), fallback[0].container), ("app", 4000));
+    assert_eq!(resolve_preview_service(&fallback), "app");
+
+    let discovered = vec![
+      ("api".to_string(), 8000, "tcp".to_string()),
+      ("web".to_string(), 5173, "tcp".to_string()),
+    ];
+    let requests = port_requests_from_discovered(discovered, &configured);
+    let services: Vec<(&str, u16, bool)> = requests
+      .iter()
+      .map(|p| (p.service.as_str(), p.container, p.preview))
+      .collect();
+    assert_eq!(services, vec![("api", 8000, false), ("web", 5173, false)]);
+    assert_eq!(resolve_preview_service(&requests), "web");
+  }
+}
@@ -10,9 +10,9 @@ use std::fs::{self, File};
 use std::io::{ErrorKind, Write};
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Mutex;
+use std::sync::{Mutex, OnceLock};
 use std::time::{SystemTime, UNIX_EPOCH};
-use tauri::Manager;
+use tauri::{Emitter, Manager};
 use zip::write::FileOptions;
 use zip::CompressionMethod;
 
@@ -24,13 +24,43 @@ static EMBEDDED_MIGRATIONS: Dir = include_dir!("$CARGO_MANIFEST_DIR/../drizzle")
 pub struct DbState {
   conn: Mutex<Option<Connection>>,
   disabled: AtomicBool,
+  app: OnceLock<tauri::AppHandle>,
+  runtime_error: AtomicBool,
 }
 
 impl DbState {
-  pub fn disabled() -> Self {
+  fn new(conn: Option<Connection>, disabled: bool) -> Self {
     Self {
-      conn: Mutex::new(None),
-      disabled: AtomicBool::new(true),
+      conn: Mutex::new(conn),
+      disabled: AtomicBool::new(disabled),
+      app: OnceLock::new(),
+      runtime_error: AtomicBool::new(false),
+    }
+  }
+
+  pub fn disabled() -> Self {
+    Self::new(None, true)
+  }
+
+  /// Lets runtime failures be reported to the renderer via `db:runtime-error`.
+  pub fn set_app_handle(&self, app: tauri::AppHandle) {
+    let _ = self.app.set(app);
+  }
+
+  // Emitted once per failure; db_retry_init re-arms it after rebuilding the connection.
+  fn report_runtime_error(&self, kind: &str, message: &str) {
+    if self.runtime_error.swap(true, Ordering::SeqCst) {
+      return;
+    }
+    if let Some(app) = self.app.get() {
+      let _ = app.emit(
+        "db:runtime-error",
+        json!({
+          "kind": kind,
+          "message": message,
+          "dbPath": database_path_string(app),
+        }),
+      );
     }
   }
 
@@ -42,8 +72,15 @@ impl DbState {
     self.disabled.store(value, Ordering::SeqCst);
   }
 
+  // Recovers a poisoned lock, since the connection is being swapped out anyway.
   fn replace_conn(&self, conn: Option<Connection>) -> Result<(), String> {
-    let mut guard = self.conn.lock().map_err(|_| "DB lock poisoned".to_string())?;
+    let mut guard = match self.conn.lock() {
+      Ok(guard) => guard,
+      Err(poisoned) => {
+        self.conn.clear_poison();
+        poisoned.into_inner()
+      }
+    };
     *guard = conn;
     Ok(())
   }
@@ -140,12 +177,16 @@ struct MessageInput {
   content: String,
   sender: String,
   metadata: Option<Value>,
+  provider_id: Option<String>,
+  model: Option<String>,
 }
 
 #[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ProjectSettingsUpdate {
   project_id: String,
-  base_ref: String,
+  base_ref: Option<String>,
+  overrides: Option<Value>,
 }
 
 #[derive(Clone)]
@@ -294,45 +335,24 @@ fn is_migrations_dir(path: &Path) -> bool {
   path.join("meta").join("_journal.json").exists()
 }
 
-fn write_embedded_dir(dir: &Dir, target: &Path) -> Result<(), String> {
-  for subdir in dir.dirs() {
-    let dir_path = target.join(subdir.path());
-    fs::create_dir_all(&dir_path).map_err(|err| err.to_string())?;
-    write_embedded_dir(subdir, target)?;
-  }
-  for file in dir.files() {
-    let path = target.join(file.path());
-    if let Some(parent) = path.parent() {
-      fs::create_dir_all(parent).map_err(|err| err.to_string())?;
-    }
-    fs::write(&path, file.contents()).map_err(|err| err.to_string())?;
-  }
-  Ok(())
+enum MigrationSource {
+  Disk(PathBuf),
+  Embedded,
 }
 
-fn write_embedded_migrations(target: &Path) -> Result<(), String> {
-  fs::create_dir_all(target).map_err(|err| err.to_string())?;
-  write_embedded_dir(&EMBEDDED_MIGRATIONS, target)
-}
-
-fn ensure_embedded_migrations(app: &tauri::AppHandle) -> Option<PathBuf> {
-  let base = app
-    .path()
-    .app_data_dir()
-    .ok()
-    .or_else(|| app.path().app_config_dir().ok())
-    .unwrap_or_else(|| storage::config_dir(app));
-  let target = base.join("drizzle");
-  if is_migrations_dir(&target) {
-    return Some(target);
-  }
-  if write_embedded_migrations(&target).is_ok() && is_migrations_dir(&target) {
-    return Some(target);
+impl MigrationSource {
+  fn read(&self, relative: &str) -> Option<String> {
+    match self {
+      MigrationSource::Disk(root) => fs::read_to_string(root.join(relative)).ok(),
+      MigrationSource::Embedded => EMBEDDED_MIGRATIONS
+        .get_file(relative)
+        .and_then(|file| file.contents_utf8())
+        .map(|contents| contents.to_string()),
+    }
   }
-  None
 }
 
-fn resolve_migrations_path(app: &tauri::AppHandle) -> Option<PathBuf> {
+fn resolve_migration_source(app: &tauri::AppHandle) -> MigrationSource {
   let mut candidates: Vec<PathBuf> = Vec::new();
 
   if let Ok(resource_dir) = app.path().resource_dir() {
@@ -360,7 +380,8 @@ fn resolve_migrations_path(app: &tauri::AppHandle) -> Option<PathBuf> {
   candidates
     .into_iter()
     .find(|path| path.is_dir() && is_migrations_dir(path))
-    .or_else(|| ensure_embedded_migrations(app))
+    .map(MigrationSource::Disk)
+    .unwrap_or(MigrationSource::Embedded)
 }
 
 fn open_database_with_path(app: &tauri::AppHandle) -> Result<(Connection, PathBuf), String> {
@@ -370,16 +391,30 @@ fn open_database_with_path(app: &tauri::AppHandle) -> Result<(Connection, PathBu
   }
   let conn = Connection::open(&db_path).map_err(|err| err.to_string())?;
 
-  let migrations_path = resolve_migrations_path(app)
-    .ok_or_else(|| "Drizzle migrations folder not found".to_string())?;
-  ensure_migrations(&conn, &migrations_path)?;
+  let source = resolve_migration_source(app);
+  ensure_migrations(&conn, &source)?;
+  ensure_foreign_keys(&conn)?;
 
   Ok((conn, db_path))
 }
 
-fn read_journal(migrations_path: &Path) -> Option<Vec<MigrationEntry>> {
-  let journal_path = migrations_path.join("meta").join("_journal.json");
-  let raw = fs::read_to_string(journal_path).ok()?;
+// Foreign keys are per-connection and migrations toggle them, so confirm they're on before
+// handing the connection out; otherwise deletes silently stop cascading.
+fn ensure_foreign_keys(conn: &Connection) -> Result<(), String> {
+  conn
+    .execute_batch("PRAGMA foreign_keys=ON;")
+    .map_err(|err| err.to_string())?;
+  let enabled: i64 = conn
+    .query_row("PRAGMA foreign_keys", [], |row| row.get(0))
+    .map_err(|err| err.to_string())?;
+  if enabled != 1 {
+    return Err("SQLite foreign key enforcement could not be enabled".to_string());
+  }
+  Ok(())
+}
+
+fn read_journal(source: &MigrationSource) -> Option<Vec<MigrationEntry>> {
+  let raw = source.read("meta/_journal.json")?;
   let parsed: Value = serde_json::from_str(&raw).ok()?;
   let entries = parsed.get("entries")?.as_array()?;
 
@@ -407,14 +442,14 @@ fn compute_hash(contents: &str) -> String {
   hex::encode(out)
 }
 
-fn load_migrations(migrations_path: &Path) -> Result<Vec<Migration>, String> {
-  let journal = read_journal(migrations_path)
-    .ok_or_else(|| "Drizzle migrations journal not found".to_string())?;
+fn load_migrations(source: &MigrationSource) -> Result<Vec<Migration>, String> {
+  let journal =
+    read_journal(source).ok_or_else(|| "Drizzle migrations journal not found".to_string())?;
   let mut list: Vec<Migration> = Vec::new();
   for entry in journal {
-    let sql_path = migrations_path.join(format!("{}.sql", entry.tag));
-    let contents = fs::read_to_string(&sql_path)
-      .map_err(|_| format!("Missing migration SQL: {}", entry.tag))?;
+    let contents = source
+      .read(&format!("{}.sql", entry.tag))
+      .ok_or_else(|| format!("Missing migration SQL: {}", entry.tag))?;
     let hash = compute_hash(&contents);
     let statements = split_statements(&contents);
     list.push(Migration {
@@ -455,13 +490,23 @@ fn table_has_column(conn: &Connection, table: &str, column: &str) -> Result<bool
   Ok(false)
 }
 
-fn ensure_migrations(conn: &Connection, migrations_path: &Path) -> Result<(), String> {
+fn record_migration(conn: &Connection, migration: &Migration) -> Result<(), String> {
+  conn
+    .execute(
+      "INSERT INTO \"__drizzle_migrations\" (\"hash\", \"created_at\", \"tag\") VALUES (?1, ?2, ?3)",
+      params![migration.hash, migration.when, migration.tag],
+    )
+    .map_err(|err| err.to_string())?;
+  Ok(())
+}
+
+fn ensure_migrations(conn: &Connection, source: &MigrationSource) -> Result<(), String> {
   conn
     .execute_batch("PRAGMA foreign_keys=OFF;")
     .map_err(|err| err.to_string())?;
 
   let result = (|| {
-    let migrations = load_migrations(migrations_path)?;
+    let migrations = load_migrations(source)?;
     conn
       .execute_batch(
         "CREATE TABLE IF NOT EXISTS \"__drizzle_migrations\" (
@@ -471,17 +516,43 @@ fn ensure_migrations(conn: &Connection, migrations_path: &Path) -> Result<(), St
          );",
       )
       .map_err(|err| err.to_string())?;
+    if !table_has_column(conn, "__drizzle_migrations", "tag")? {
+      conn
+        .execute_batch("ALTER TABLE \"__drizzle_migrations\" ADD COLUMN \"tag\" text;")
+        .map_err(|err| err.to_string())?;
+    }
 
     let mut stmt = conn
-      .prepare("SELECT hash FROM \"__drizzle_migrations\"")
+      .prepare("SELECT hash, tag FROM \"__drizzle_migrations\"")
       .map_err(|err| err.to_string())?;
     let rows = stmt
-      .query_map([], |row| row.get::<_, String>(0))
+      .query_map([], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?))
+      })
       .map_err(|err| err.to_string())?;
     let mut applied: HashSet<String> = HashSet::new();
+    let mut applied_tags: HashSet<String> = HashSet::new();
     for row in rows {
-      if let Ok(hash) = row {
+      if let Ok((hash, tag)) = row {
         applied.insert(hash);
+        if let Some(tag) = tag {
+          applied_tags.insert(tag);
+        }
+      }
+    }
+    drop(stmt);
+
+    // Rows written before the tag column existed only carry a hash; backfill the tag
+    // while the hash still matches so later formatting-only edits are recognised.
+    for migration in &migrations {
+      if applied.contains(&migration.hash) && !applied_tags.contains(&migration.tag) {
+        conn
+          .execute(
+            "UPDATE \"__drizzle_migrations\" SET \"tag\" = ?1 WHERE \"hash\" = ?2 AND \"tag\" IS NULL",
+            params![migration.tag, migration.hash],
+          )
+          .map_err(|err| err.to_string())?;
+        applied_tags.insert(migration.tag.clone());
       }
     }
 
@@ -505,13 +576,9 @@ fn ensure_migrations(conn: &Connection, migrations_path: &Path) -> Result<(), St
       let tag = "0002_lyrical_impossible_man";
       if let Some(migration) = migrations.iter().find(|m| m.tag == tag) {
         if !applied.contains(&migration.hash) {
-          conn
-            .execute(
-              "INSERT INTO \"__drizzle_migrations\" (\"hash\", \"created_at\") VALUES (?1, ?2)",
-              params![migration.hash, migration.when],
-            )
-            .map_err(|err| err.to_string())?;
+          record_migration(conn, migration)?;
           applied.insert(migration.hash.clone());
+          applied_tags.insert(migration.tag.clone());
         }
       }
     }
@@ -520,18 +587,25 @@ fn ensure_migrations(conn: &Connection, migrations_path: &Path) -> Result<(), St
       if applied.contains(&migration.hash) {
         continue;
       }
+      // Already applied under an older hash (e.g. a formatting-only edit): adopt the new
+      // hash instead of running it again, so the mismatch is only handled once.
+      if applied_tags.contains(&migration.tag) {
+        conn
+          .execute(
+            "UPDATE \"__drizzle_migrations\" SET \"hash\" = ?1 WHERE \"tag\" = ?2",
+            params![migration.hash, migration.tag],
+          )
+          .map_err(|err| err.to_string())?;
+        applied.insert(migration.hash);
+        continue;
+      }
       if migration.tag == "0002_lyrical_impossible_man"
         && table_exists(conn, "tasks")?
         && !table_exists(conn, "workspaces")?
         && table_exists(conn, "conversations")?
         && table_has_column(conn, "conversations", "task_id")?
       {
-        conn
-          .execute(
-            "INSERT INTO \"__drizzle_migrations\" (\"hash\", \"created_at\") VALUES (?1, ?2)",
-            params![migration.hash, migration.when],
-          )
-          .map_err(|err| err.to_string())?;
+        record_migration(conn, &migration)?;
         applied.insert(migration.hash);
         continue;
       }
@@ -546,12 +620,7 @@ fn ensure_migrations(conn: &Connection, migrations_path: &Path) -> Result<(), St
           .map_err(|err| format!("Migration {} failed: {}", migration.tag, err))?;
       }
 
-      conn
-        .execute(
-          "INSERT INTO \"__drizzle_migrations\" (\"hash\", \"created_at\") VALUES (?1, ?2)",
-          params![migration.hash, migration.when],
-        )
-        .map_err(|err| err.to_string())?;
+      record_migration(conn, &migration)?;
       applied.insert(migration.hash);
     }
 
@@ -571,10 +640,7 @@ pub fn init(app: &tauri::AppHandle) -> Result<DbState, String> {
   }
   let (conn, _path) = open_database_with_path(app)?;
 
-  Ok(DbState {
-    conn: Mutex::new(Some(conn)),
-    disabled: AtomicBool::new(false),
-  })
+  Ok(DbState::new(Some(conn), false))
 }
 
 fn download_dir(app: &tauri::AppHandle) -> PathBuf {
@@ -713,8 +779,73 @@ fn parse_metadata(raw: Option<String>) -> Value {
   }
 }
 
+// Metadata keys written by backend commands (task_set_base_ref, task_set_env). Renderer saves
+// carry a possibly stale copy of the metadata, so an existing task keeps its stored values.
+const BACKEND_METADATA_KEYS: &[&str] = &["baseRef", "env"];
+
+fn keep_backend_metadata(incoming: Option<String>, stored: Option<String>) -> Option<String> {
+  let stored = parse_metadata(stored);
+  let mut metadata = match parse_metadata(incoming.clone()) {
+    Value::Object(map) => map,
+    _ if incoming.is_none() => serde_json::Map::new(),
+    _ => return incoming,
+  };
+  for key in BACKEND_METADATA_KEYS {
+    match stored.get(*key) {
+      Some(value) => {
+        metadata.insert(key.to_string(), value.clone());
+      }
+      None => {
+        metadata.remove(*key);
+      }
+    }
+  }
+  if metadata.is_empty() {
+    None
+  } else {
+    Some(Value::Object(metadata).to_string())
+  }
+}
+
+fn normalize_tags(tags: Vec<String>) -> Vec<String> {
+  let mut seen: HashSet<String> = HashSet::new();
+  let mut out: Vec<String> = Vec::new();
+  for tag in tags {
+    let tag = tag.trim().to_lowercase();
+    if !tag.is_empty() && seen.insert(tag.clone()) {
+      out.push(tag);
+    }
+  }
+  out
+}
+
+fn query_task_tags(conn: &Connection, task_id: &str) -> Result<Vec<String>, String> {
+  let mut stmt = conn
+    .prepare("SELECT tag FROM task_tags WHERE task_id = ?1 ORDER BY tag ASC")
+    .map_err(|err| err.to_string())?;
+  let rows = stmt
+    .query_map(params![task_id], |row| row.get::<_, String>(0))
+    .map_err(|err| err.to_string())?;
+  Ok(rows.flatten().collect())
+}
+
+// Tags are removed explicitly rather than relying on the FK cascade, which only fires
+// when the connection has foreign_keys enabled.
+fn delete_task(conn: &mut Connection, task_id: &str) -> Result<(), String> {
+  let tx = conn.transaction().map_err(|err| err.to_string())?;
+  tx.execute("DELETE FROM task_tags WHERE task_id = ?1", params![task_id])
+    .map_err(|err| err.to_string())?;
+  tx.execute("DELETE FROM tasks WHERE id = ?1", params![task_id])
+    .map_err(|err| err.to_string())?;
+  tx.commit().map_err(|err| err.to_string())
+}
+
 fn lock_conn(state: &DbState) -> Result<std::sync::MutexGuard<'_, Option<Connection>>, String> {
-  state.conn.lock().map_err(|_| "DB lock poisoned".to_string())
+  state.conn.lock().map_err(|_| {
+    let message = "DB lock poisoned".to_string();
+    state.report_runtime_error("lock-poisoned", &message);
+    message
+  })
 }
 
 pub(crate) fn project_settings_row(
@@ -743,6 +874,105 @@ pub(crate) fn project_settings_row(
   row.ok_or_else(|| "Project not found".to_string())
 }
 
+/// Per-task base ref override, kept under `baseRef` in the task's metadata.
+pub(crate) fn task_base_ref(state: &DbState, task_id: &str) -> Option<String> {
+  if state.is_disabled() {
+    return None;
+  }
+  let guard = lock_conn(state).ok()?;
+  let conn = guard.as_ref()?;
+  let raw: Option<Option<String>> = conn
+    .query_row(
+      "SELECT metadata FROM tasks WHERE id = ?1 LIMIT 1",
+      params![task_id],
+      |row| row.get(0),
+    )
+    .optional()
+    .ok()?;
+  parse_metadata(raw.flatten())
+    .get("baseRef")
+    .and_then(|v| v.as_str())
+    .map(|v| v.trim().to_string())
+    .filter(|v| !v.is_empty())
+}
+
+/// Returns the owning project's id, path and git remote for a task.
+pub(crate) fn task_project(
+  state: &DbState,
+  task_id: &str,
+) -> Result<(String, String, Option<String>), String> {
+  if state.is_disabled() {
+    return Err("DB disabled".to_string());
+  }
+  let guard = lock_conn(state)?;
+  let conn = guard.as_ref().ok_or_else(|| "DB not initialized".to_string())?;
+  let row = conn
+    .query_row(
+      "SELECT p.id, p.path, p.git_remote FROM tasks t JOIN projects p ON p.id = t.project_id
+       WHERE t.id = ?1 LIMIT 1",
+      params![task_id],
+      |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+    )
+    .optional()
+    .map_err(|err| err.to_string())?;
+  row.ok_or_else(|| "Task not found".to_string())
+}
+
+pub(crate) fn update_task_base_ref(
+  state: &DbState,
+  task_id: &str,
+  base_ref: Option<&str>,
+) -> Result<(), String> {
+  update_task_metadata_key(state, task_id, "baseRef", base_ref.map(|v| json!(v)))
+}
+
+/// Sets (or removes, when `value` is `None`) a single key in a task's metadata.
+pub(crate) fn update_task_metadata_key(
+  state: &DbState,
+  task_id: &str,
+  key: &str,
+  value: Option<Value>,
+) -> Result<(), String> {
+  if state.is_disabled() {
+    return Err("DB disabled".to_string());
+  }
+  let guard = lock_conn(state)?;
+  let conn = guard.as_ref().ok_or_else(|| "DB not initialized".to_string())?;
+  let raw: Option<Option<String>> = conn
+    .query_row(
+      "SELECT metadata FROM tasks WHERE id = ?1 LIMIT 1",
+      params![task_id],
+      |row| row.get(0),
+    )
+    .optional()
+    .map_err(|err| err.to_string())?;
+  let raw = raw.ok_or_else(|| "Task not found".to_string())?;
+  let mut metadata = match parse_metadata(raw) {
+    Value::Object(map) => map,
+    _ => serde_json::Map::new(),
+  };
+  match value {
+    Some(value) => {
+      metadata.insert(key.to_string(), value);
+    }
+    None => {
+      metadata.remove(key);
+    }
+  }
+  let stored = if metadata.is_empty() {
+    None
+  } else {
+    Some(Value::Object(metadata).to_string())
+  };
+  conn
+    .execute(
+      "UPDATE tasks SET metadata = ?1, updated_at = CURRENT_TIMESTAMP WHERE id = ?2",
+      params![stored, task_id],
+    )
+    .map_err(|err| err.to_string())?;
+  Ok(())
+}
+
 pub(crate) fn update_project_base_ref(
   state: &DbState,
   project_id: &str,
@@ -798,10 +1028,144 @@ pub fn task_agent_id_for_path(state: &DbState, task_path: &str) -> Option<String
   row.flatten()
 }
 
+/// Returns `(id, path)` for every task, or `None` when the DB isn't available.
+pub fn task_ids_and_paths(state: &DbState) -> Option<Vec<(String, String)>> {
+  if state.is_disabled() {
+    return None;
+  }
+  let guard = lock_conn(state).ok()?;
+  let conn = guard.as_ref()?;
+  let mut stmt = conn.prepare("SELECT id, path FROM tasks").ok()?;
+  let rows = stmt
+    .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+    .ok()?;
+  Some(rows.flatten().collect())
+}
+
+pub(crate) fn task_branch_for_path(state: &DbState, task_path: &str) -> Option<String> {
+  if state.is_disabled() {
+    return None;
+  }
+  let guard = lock_conn(state).ok()?;
+  let conn = guard.as_ref()?;
+  conn
+    .query_row(
+      "SELECT branch FROM tasks WHERE path = ?1 LIMIT 1",
+      params![task_path],
+      |row| row.get(0),
+    )
+    .optional()
+    .ok()?
+}
+
+pub(crate) fn update_task_branch_for_path(
+  state: &DbState,
+  task_path: &str,
+  branch: &str,
+) -> Result<(), String> {
+  if state.is_disabled() {
+    return Err("DB disabled".to_string());
+  }
+  let guard = lock_conn(state)?;
+  let conn = guard.as_ref().ok_or_else(|| "DB not initialized".to_string())?;
+  conn
+    .execute(
+      "UPDATE tasks SET branch = ?1, updated_at = CURRENT_TIMESTAMP WHERE path = ?2",
+      params![branch, task_path],
+    )
+    .map_err(|err| err.to_string())?;
+  Ok(())
+}
+
+pub fn task_metadata_for_path(state: &DbState, task_path: &str) -> Option<Value> {
+  if state.is_disabled() {
+    return None;
+  }
+  let guard = lock_conn(state).ok()?;
+  let conn = guard.as_ref()?;
+  let raw: Option<Option<String>> = conn
+    .query_row(
+      "SELECT metadata FROM tasks WHERE path = ?1 LIMIT 1",
+      params![task_path],
+      |row| row.get(0),
+    )
+    .optional()
+    .ok()?;
+  Some(parse_metadata(raw.flatten())).filter(|v| v.is_object())
+}
+
+/// Looks up the configured git remote for a project, given either the project
+/// root or one of its task worktree paths.
+pub fn project_git_remote_for_path(state: &DbState, path: &str) -> Option<String> {
+  if state.is_disabled() {
+    return None;
+  }
+  let guard = lock_conn(state).ok()?;
+  let conn = guard.as_ref()?;
+  let row: Option<Option<String>> = conn
+    .query_row(
+      "SELECT git_remote FROM projects WHERE path = ?1
+       UNION ALL
+       SELECT p.git_remote FROM tasks t JOIN projects p ON p.id = t.project_id WHERE t.path = ?1
+       LIMIT 1",
+      params![path],
+      |row| row.get(0),
+    )
+    .optional()
+    .ok()?;
+  row.flatten()
+}
+
+fn parse_project_overrides(raw: Option<String>) -> Value {
+  raw
+    .and_then(|raw| serde_json::from_str::<Value>(&raw).ok())
+    .filter(|v| v.is_object())
+    .unwrap_or_else(|| json!({}))
+}
+
+/// Per-project settings overrides stored in `projects.settings`, if any are set.
+pub(crate) fn project_settings_overrides(state: &DbState, project_id: &str) -> Option<Value> {
+  if state.is_disabled() {
+    return None;
+  }
+  let guard = lock_conn(state).ok()?;
+  let conn = guard.as_ref()?;
+  let raw: Option<String> = conn
+    .query_row(
+      "SELECT settings FROM projects WHERE id = ?1 LIMIT 1",
+      params![project_id],
+      |row| row.get(0),
+    )
+    .optional()
+    .ok()?
+    .flatten();
+  raw.map(|raw| parse_project_overrides(Some(raw)))
+}
+
+/// Resolves the project id for a project root or one of its task worktree paths.
+pub(crate) fn project_id_for_path(state: &DbState, path: &str) -> Option<String> {
+  if state.is_disabled() {
+    return None;
+  }
+  let guard = lock_conn(state).ok()?;
+  let conn = guard.as_ref()?;
+  conn
+    .query_row(
+      "SELECT id FROM projects WHERE path = ?1
+       UNION ALL
+       SELECT project_id FROM tasks WHERE path = ?1
+       LIMIT 1",
+      params![path],
+      |row| row.get(0),
+    )
+    .optional()
+    .ok()?
+}
+
 fn query_project_settings(conn: &Connection, project_id: &str) -> Result<Value, String> {
   let row = conn
     .query_row(
-      "SELECT id, name, path, git_remote, git_branch, base_ref FROM projects WHERE id = ?1 LIMIT 1",
+      "SELECT id, name, path, git_remote, git_branch, base_ref, settings FROM projects WHERE id = ?1 LIMIT 1",
       params![project_id],
       |row| {
         let git_remote: Option<String> = row.get(3)?;
@@ -818,7 +1182,8 @@ fn query_project_settings(conn: &Connection, project_id: &str) -> Result<Value,
           "path": row.get::<_, String>(2)?,
           "gitRemote": git_remote,
           "gitBranch": git_branch,
-          "baseRef": base_ref
+          "baseRef": base_ref,
+          "overrides": parse_project_overrides(row.get(6)?)
         }))
       },
     )
@@ -965,7 +1330,11 @@ pub async fn db_save_project(app: tauri::AppHandle, project: Value) -> Value {
 }
 
 #[tauri::command]
-pub async fn db_get_tasks(app: tauri::AppHandle, project_id: Option<String>) -> Value {
+pub async fn db_get_tasks(
+  app: tauri::AppHandle,
+  project_id: Option<String>,
+  tags: Option<Vec<String>>,
+) -> Value {
   run_blocking(json!([]), move || {
     let state: tauri::State<DbState> = app.state();
     if state.is_disabled() {
@@ -980,17 +1349,33 @@ pub async fn db_get_tasks(app: tauri::AppHandle, project_id: Option<String>) ->
       None => return json!([]),
     };
 
-    let sql = "SELECT id, project_id, name, branch, path, status, agent_id, metadata, created_at, updated_at
+    let tags = normalize_tags(tags.unwrap_or_default());
+    let mut sql = String::from(
+      "SELECT id, project_id, name, branch, path, status, agent_id, metadata, created_at, updated_at
          FROM tasks
-         WHERE (?1 IS NULL OR project_id = ?1)
-         ORDER BY updated_at DESC";
+         WHERE (?1 IS NULL OR project_id = ?1)",
+    );
+    let mut args: Vec<rusqlite::types::Value> = vec![match project_id {
+      Some(id) => rusqlite::types::Value::Text(id),
+      None => rusqlite::types::Value::Null,
+    }];
+    if !tags.is_empty() {
+      let placeholders: Vec<String> = (0..tags.len()).map(|i| format!("?{}", i + 2)).collect();
+      sql.push_str(&format!(
+        " AND id IN (SELECT task_id FROM task_tags WHERE tag IN ({}) GROUP BY task_id HAVING COUNT(DISTINCT tag) = {})",
+        placeholders.join(", "),
+        tags.len()
+      ));
+      args.extend(tags.into_iter().map(rusqlite::types::Value::Text));
+    }
+    sql.push_str(" ORDER BY updated_at DESC");
 
-    let mut stmt = match conn.prepare(sql) {
+    let mut stmt = match conn.prepare(&sql) {
       Ok(stmt) => stmt,
       Err(_) => return json!([]),
     };
 
-    let rows = stmt.query_map(params![project_id], |row| {
+    let rows = stmt.query_map(rusqlite::params_from_iter(args), |row| {
       let metadata: Option<String> = row.get(7)?;
       Ok(json!({
         "id": row.get::<_, String>(0)?,
@@ -1043,7 +1428,21 @@ pub async fn db_save_task(app: tauri::AppHandle, task: Value) -> Value {
         None => return json!({ "success": false, "error": "DB not initialized" }),
       };
 
-      let metadata_value = metadata_to_string(input.metadata);
+      let stored_metadata: Option<Option<String>> = match conn
+        .query_row(
+          "SELECT metadata FROM tasks WHERE id = ?1 LIMIT 1",
+          params![input.id],
+          |row| row.get(0),
+        )
+        .optional()
+      {
+        Ok(row) => row,
+        Err(err) => return json!({ "success": false, "error": err.to_string() }),
+      };
+      let mut metadata_value = metadata_to_string(input.metadata);
+      if let Some(stored) = stored_metadata {
+        metadata_value = keep_backend_metadata(metadata_value, stored);
+      }
 
       let result = conn.execute(
         "INSERT INTO tasks (id, project_id, name, branch, path, status, agent_id, metadata, updated_at)
@@ -1105,14 +1504,45 @@ pub async fn db_delete_project(app: tauri::AppHandle, project_id: String) -> Val
   .await
 }
 
+const ORPHAN_CLEANUP: &[(&str, &str)] = &[
+  (
+    "tasks",
+    "DELETE FROM tasks WHERE project_id NOT IN (SELECT id FROM projects)",
+  ),
+  (
+    "conversations",
+    "DELETE FROM conversations WHERE task_id NOT IN (SELECT id FROM tasks)",
+  ),
+  (
+    "messages",
+    "DELETE FROM messages WHERE conversation_id NOT IN (SELECT id FROM conversations)",
+  ),
+  (
+    "taskTags",
+    "DELETE FROM task_tags WHERE task_id NOT IN (SELECT id FROM tasks)",
+  ),
+];
+
+/// Removes rows whose parent is gone, parents first so each pass sees the previous deletions.
+fn cleanup_orphans(conn: &Connection) -> Result<serde_json::Map<String, Value>, String> {
+  let tx = conn.unchecked_transaction().map_err(|err| err.to_string())?;
+  let mut counts = serde_json::Map::new();
+  for (name, sql) in ORPHAN_CLEANUP {
+    let removed = tx.execute(sql, []).map_err(|err| err.to_string())?;
+    counts.insert(name.to_string(), json!(removed));
+  }
+  tx.commit().map_err(|err| err.to_string())?;
+  Ok(counts)
+}
+
 #[tauri::command]
-pub async fn db_delete_task(app: tauri::AppHandle, task_id: String) -> Value {
+pub async fn db_cleanup_orphans(app: tauri::AppHandle) -> Value {
   run_blocking(
     json!({ "success": false, "error": "Task cancelled" }),
     move || {
       let state: tauri::State<DbState> = app.state();
       if state.is_disabled() {
-        return json!({ "success": true });
+        return json!({ "success": true, "removed": {} });
       }
       let guard = match lock_conn(&state) {
         Ok(g) => g,
@@ -1123,15 +1553,340 @@ pub async fn db_delete_task(app: tauri::AppHandle, task_id: String) -> Value {
         None => return json!({ "success": false, "error": "DB not initialized" }),
       };
 
-      match conn.execute("DELETE FROM tasks WHERE id = ?1", params![task_id]) {
-        Ok(_) => json!({ "success": true }),
-        Err(err) => json!({ "success": false, "error": err.to_string() }),
+      match cleanup_orphans(conn) {
+        Ok(counts) => json!({ "success": true, "removed": counts }),
+        Err(err) => json!({ "success": false, "error": err }),
+      }
+    },
+  )
+  .await
+}
+
+fn count_rows(conn: &Connection, table: &str) -> rusqlite::Result<i64> {
+  conn.query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| row.get(0))
+}
+
+fn collect_stats(conn: &Connection) -> rusqlite::Result<Value> {
+  let mut by_status = serde_json::Map::new();
+  let mut total_tasks: i64 = 0;
+  let mut stmt = conn.prepare("SELECT status, COUNT(*) FROM tasks GROUP BY status")?;
+  for row in stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)))? {
+    let (status, count) = row?;
+    total_tasks += count;
+    by_status.insert(status, json!(count));
+  }
+
+  let mut latest = serde_json::Map::new();
+  let mut stmt = conn.prepare(
+    "SELECT t.project_id, t.id, t.name, t.status, t.updated_at
+       FROM tasks t
+       WHERE t.id = (
+         SELECT id FROM tasks WHERE project_id = t.project_id ORDER BY updated_at DESC LIMIT 1
+       )",
+  )?;
+  for row in stmt.query_map([], |row| {
+    Ok((
+      row.get::<_, String>(0)?,
+      json!({
+        "id": row.get::<_, String>(1)?,
+        "name": row.get::<_, String>(2)?,
+        "status": row.get::<_, String>(3)?,
+        "updatedAt": row.get::<_, String>(4)?
+      }),
+    ))
+  })? {
+    let (project_id, task) = row?;
+    latest.insert(project_id, task);
+  }
+
+  Ok(json!({
+    "projects": count_rows(conn, "projects")?,
+    "tasks": { "total": total_tasks, "byStatus": by_status },
+    "conversations": count_rows(conn, "conversations")?,
+    "messages": count_rows(conn, "messages")?,
+    "latestTaskByProject": latest
+  }))
+}
+
+#[tauri::command]
+pub async fn db_get_stats(app: tauri::AppHandle) -> Value {
+  run_blocking(
+    json!({ "success": false, "error": "Task cancelled" }),
+    move || {
+      let state: tauri::State<DbState> = app.state();
+      if state.is_disabled() {
+        return json!({
+          "success": true,
+          "stats": {
+            "projects": 0,
+            "tasks": { "total": 0, "byStatus": {} },
+            "conversations": 0,
+            "messages": 0,
+            "latestTaskByProject": {}
+          }
+        });
+      }
+      let guard = match lock_conn(&state) {
+        Ok(g) => g,
+        Err(err) => return json!({ "success": false, "error": err }),
+      };
+      let conn = match guard.as_ref() {
+        Some(conn) => conn,
+        None => return json!({ "success": false, "error": "DB not initialized" }),
+      };
+
+      match collect_stats(conn) {
+        Ok(stats) => json!({ "success": true, "stats": stats }),
+        Err(err) => json!({ "success": false, "error": err.to_string() }),
+      }
+    },
+  )
+  .await
+}
+
+const GLOBAL_SEARCH_DEFAULT_LIMIT: usize = 50;
+const GLOBAL_SEARCH_MAX_LIMIT: usize = 200;
+
+fn like_pattern(query: &str) -> String {
+  let mut escaped = String::with_capacity(query.len() + 2);
+  escaped.push('%');
+  for ch in query.chars() {
+    if matches!(ch, '%' | '_' | '\\') {
+      escaped.push('\\');
+    }
+    escaped.push(ch);
+  }
+  escaped.push('%');
+  escaped
+}
+
+// Lower is better: exact label match, then prefix, then substring anywhere.
+fn match_rank(label: &str, query: &str) -> u8 {
+  let label = label.to_lowercase();
+  if label == query {
+    0
+  } else if label.starts_with(query) {
+    1
+  } else if label.contains(query) {
+    2
+  } else {
+    3
+  }
+}
+
+fn global_search(conn: &Connection, query: &str, limit: usize) -> rusqlite::Result<Vec<Value>> {
+  let pattern = like_pattern(query);
+  let needle = query.to_lowercase();
+  // (match rank, kind order, label, hit)
+  let mut hits: Vec<(u8, u8, String, Value)> = Vec::new();
+
+  let mut stmt = conn.prepare(
+    "SELECT id, name, path FROM projects
+       WHERE name LIKE ?1 ESCAPE '\\' OR path LIKE ?1 ESCAPE '\\'
+       LIMIT ?2",
+  )?;
+  for row in stmt.query_map(params![pattern, limit as i64], |row| {
+    Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?))
+  })? {
+    let (id, name, path) = row?;
+    let rank = match_rank(&name, &needle).min(match_rank(&path, &needle) + 1);
+    let hit = json!({ "kind": "project", "id": id, "label": name, "projectId": id });
+    hits.push((rank, 0, name, hit));
+  }
+
+  let mut stmt = conn.prepare(
+    "SELECT id, project_id, name, branch FROM tasks
+       WHERE name LIKE ?1 ESCAPE '\\' OR branch LIKE ?1 ESCAPE '\\'
+       LIMIT ?2",
+  )?;
+  for row in stmt.query_map(params![pattern, limit as i64], |row| {
+    Ok((
+      row.get::<_, String>(0)?,
+      row.get::<_, String>(1)?,
+      row.get::<_, String>(2)?,
+      row.get::<_, String>(3)?,
+    ))
+  })? {
+    let (id, project_id, name, branch) = row?;
+    let rank = match_rank(&name, &needle).min(match_rank(&branch, &needle) + 1);
+    let hit = json!({ "kind": "task", "id": id, "label": name, "projectId": project_id });
+    hits.push((rank, 1, name, hit));
+  }
+
+  let mut stmt = conn.prepare(
+    "SELECT c.id, c.title, t.project_id FROM conversations c
+       JOIN tasks t ON t.id = c.task_id
+       WHERE c.title LIKE ?1 ESCAPE '\\'
+       LIMIT ?2",
+  )?;
+  for row in stmt.query_map(params![pattern, limit as i64], |row| {
+    Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?))
+  })? {
+    let (id, title, project_id) = row?;
+    let rank = match_rank(&title, &needle);
+    let hit = json!({ "kind": "conversation", "id": id, "label": title, "projectId": project_id });
+    hits.push((rank, 2, title, hit));
+  }
+
+  hits.sort_by(|a, b| {
+    a.0
+      .cmp(&b.0)
+      .then(a.1.cmp(&b.1))
+      .then_with(|| a.2.to_lowercase().cmp(&b.2.to_lowercase()))
+  });
+  Ok(hits.into_iter().take(limit).map(|(_, _, _, hit)| hit).collect())
+}
+
+#[tauri::command]
+pub async fn db_global_search(
+  app: tauri::AppHandle,
+  query: String,
+  limit: Option<usize>,
+) -> Value {
+  run_blocking(
+    json!({ "success": false, "error": "Task cancelled" }),
+    move || {
+      let query = query.trim().to_string();
+      if query.is_empty() {
+        return json!({ "success": true, "results": [] });
+      }
+      let state: tauri::State<DbState> = app.state();
+      if state.is_disabled() {
+        return json!({ "success": true, "results": [] });
+      }
+      let guard = match lock_conn(&state) {
+        Ok(g) => g,
+        Err(err) => return json!({ "success": false, "error": err }),
+      };
+      let conn = match guard.as_ref() {
+        Some(conn) => conn,
+        None => return json!({ "success": false, "error": "DB not initialized" }),
+      };
+
+      let limit = limit
+        .unwrap_or(GLOBAL_SEARCH_DEFAULT_LIMIT)
+        .clamp(1, GLOBAL_SEARCH_MAX_LIMIT);
+      match global_search(conn, &query, limit) {
+        Ok(results) => json!({ "success": true, "results": results }),
+        Err(err) => json!({ "success": false, "error": err.to_string() }),
       }
     },
   )
   .await
 }
 
+#[tauri::command]
+pub async fn db_delete_task(app: tauri::AppHandle, task_id: String) -> Value {
+  run_blocking(
+    json!({ "success": false, "error": "Task cancelled" }),
+    move || {
+      let state: tauri::State<DbState> = app.state();
+      if state.is_disabled() {
+        return json!({ "success": true });
+      }
+      let mut guard = match lock_conn(&state) {
+        Ok(g) => g,
+        Err(err) => return json!({ "success": false, "error": err }),
+      };
+      let conn = match guard.as_mut() {
+        Some(conn) => conn,
+        None => return json!({ "success": false, "error": "DB not initialized" }),
+      };
+
+      match delete_task(conn, &task_id) {
+        Ok(()) => json!({ "success": true }),
+        Err(err) => json!({ "success": false, "error": err }),
+      }
+    },
+  )
+  .await
+}
+
+#[tauri::command]
+pub async fn db_get_task_tags(app: tauri::AppHandle, task_id: String) -> Value {
+  run_blocking(
+    json!({ "success": false, "error": "Task cancelled" }),
+    move || {
+      let state: tauri::State<DbState> = app.state();
+      if state.is_disabled() {
+        return json!({ "success": true, "tags": [] });
+      }
+      let guard = match lock_conn(&state) {
+        Ok(g) => g,
+        Err(err) => return json!({ "success": false, "error": err }),
+      };
+      let conn = match guard.as_ref() {
+        Some(conn) => conn,
+        None => return json!({ "success": false, "error": "DB not initialized" }),
+      };
+
+      match query_task_tags(conn, &task_id) {
+        Ok(tags) => json!({ "success": true, "tags": tags }),
+        Err(err) => json!({ "success": false, "error": err }),
+      }
+    },
+  )
+  .await
+}
+
+#[tauri::command]
+pub async fn db_set_task_tags(app: tauri::AppHandle, task_id: String, tags: Vec<String>) -> Value {
+  run_blocking(
+    json!({ "success": false, "error": "Task cancelled" }),
+    move || {
+      let state: tauri::State<DbState> = app.state();
+      if state.is_disabled() {
+        return json!({ "success": true, "tags": [] });
+      }
+      let tags = normalize_tags(tags);
+
+      let mut guard = match lock_conn(&state) {
+        Ok(g) => g,
+        Err(err) => return json!({ "success": false, "error": err }),
+      };
+      let conn = match guard.as_mut() {
+        Some(conn) => conn,
+        None => return json!({ "success": false, "error": "DB not initialized" }),
+      };
+
+      let exists = conn
+        .query_row("SELECT 1 FROM tasks WHERE id = ?1 LIMIT 1", params![task_id], |_| Ok(()))
+        .optional();
+      match exists {
+        Ok(Some(())) => {}
+        Ok(None) => return json!({ "success": false, "error": "Task not found" }),
+        Err(err) => return json!({ "success": false, "error": err.to_string() }),
+      }
+
+      let tx = match conn.transaction() {
+        Ok(tx) => tx,
+        Err(err) => return json!({ "success": false, "error": err.to_string() }),
+      };
+
+      if let Err(err) = tx.execute("DELETE FROM task_tags WHERE task_id = ?1", params![task_id]) {
+        return json!({ "success": false, "error": err.to_string() });
+      }
+      for tag in &tags {
+        if let Err(err) = tx.execute(
+          "INSERT INTO task_tags (task_id, tag) VALUES (?1, ?2)",
+          params![task_id, tag],
+        ) {
+          return json!({ "success": false, "error": err.to_string() });
+        }
+      }
+
+      if let Err(err) = tx.commit() {
+        return json!({ "success": false, "error": err.to_string() });
+      }
+
+      let mut sorted = tags;
+      sorted.sort();
+      json!({ "success": true, "tags": sorted })
+    },
+  )
+  .await
+}
+
 #[tauri::command]
 pub async fn db_save_conversation(app: tauri::AppHandle, conversation: Value) -> Value {
   run_blocking(
@@ -1173,6 +1928,70 @@ pub async fn db_save_conversation(app: tauri::AppHandle, conversation: Value) ->
   .await
 }
 
+#[tauri::command]
+pub async fn db_rename_conversation(
+  app: tauri::AppHandle,
+  conversation_id: String,
+  title: String,
+) -> Value {
+  run_blocking(
+    json!({ "success": false, "error": "Task cancelled" }),
+    move || {
+      let title = title.trim().to_string();
+      if title.is_empty() {
+        return json!({ "success": false, "error": "Title is required" });
+      }
+      let state: tauri::State<DbState> = app.state();
+      if state.is_disabled() {
+        return json!({ "success": false, "error": "DB disabled" });
+      }
+      let guard = match lock_conn(&state) {
+        Ok(g) => g,
+        Err(err) => return json!({ "success": false, "error": err }),
+      };
+      let conn = match guard.as_ref() {
+        Some(conn) => conn,
+        None => return json!({ "success": false, "error": "DB not initialized" }),
+      };
+
+      match conn.execute(
+        "UPDATE conversations SET title = ?1, updated_at = CURRENT_TIMESTAMP WHERE id = ?2",
+        params![title, conversation_id],
+      ) {
+        Ok(0) => return json!({ "success": false, "error": "Conversation not found" }),
+        Ok(_) => {}
+        Err(err) => return json!({ "success": false, "error": err.to_string() }),
+      }
+
+      let updated = conn
+        .query_row(
+          "SELECT id, task_id, title, created_at, updated_at
+           FROM conversations
+           WHERE id = ?1
+           LIMIT 1",
+          params![conversation_id],
+          |row| {
+            Ok(json!({
+              "id": row.get::<_, String>(0)?,
+              "taskId": row.get::<_, String>(1)?,
+              "title": row.get::<_, String>(2)?,
+              "createdAt": row.get::<_, String>(3)?,
+              "updatedAt": row.get::<_, String>(4)?
+            }))
+          },
+        )
+        .optional();
+
+      match updated {
+        Ok(Some(conversation)) => json!({ "success": true, "conversation": conversation }),
+        Ok(None) => json!({ "success": false, "error": "Conversation not found" }),
+        Err(err) => json!({ "success": false, "error": err.to_string() }),
+      }
+    },
+  )
+  .await
+}
+
 #[tauri::command]
 pub async fn db_get_conversations(app: tauri::AppHandle, task_id: String) -> Value {
   run_blocking(
@@ -1335,6 +2154,59 @@ pub async fn db_get_or_create_default_conversation(
   .await
 }
 
+fn save_message(conn: &mut Connection, input: MessageInput) -> Result<(), String> {
+  let meta = metadata_to_string(input.metadata);
+  let tx = conn.transaction().map_err(|err| err.to_string())?;
+  tx.execute(
+    "INSERT INTO messages (id, conversation_id, content, sender, metadata, provider_id, model, timestamp)
+     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, CURRENT_TIMESTAMP)
+     ON CONFLICT(id) DO NOTHING",
+    params![
+      input.id,
+      input.conversation_id,
+      input.content,
+      input.sender,
+      meta,
+      input.provider_id,
+      input.model
+    ],
+  )
+  .map_err(|err| err.to_string())?;
+  tx.execute(
+    "UPDATE conversations SET updated_at = CURRENT_TIMESTAMP WHERE id = ?1",
+    params![input.conversation_id],
+  )
+  .map_err(|err| err.to_string())?;
+  tx.commit().map_err(|err| err.to_string())
+}
+
+fn query_messages(conn: &Connection, conversation_id: &str) -> Result<Vec<Value>, String> {
+  let mut stmt = conn
+    .prepare(
+      "SELECT id, conversation_id, content, sender, timestamp, metadata, provider_id, model
+       FROM messages
+       WHERE conversation_id = ?1
+       ORDER BY timestamp ASC",
+    )
+    .map_err(|err| err.to_string())?;
+  let rows = stmt
+    .query_map(params![conversation_id], |row| {
+      let metadata: Option<String> = row.get(5)?;
+      Ok(json!({
+        "id": row.get::<_, String>(0)?,
+        "conversationId": row.get::<_, String>(1)?,
+        "content": row.get::<_, String>(2)?,
+        "sender": row.get::<_, String>(3)?,
+        "timestamp": row.get::<_, String>(4)?,
+        "metadata": parse_metadata(metadata),
+        "providerId": row.get::<_, Option<String>>(6)?,
+        "model": row.get::<_, Option<String>>(7)?
+      }))
+    })
+    .map_err(|err| err.to_string())?;
+  Ok(rows.flatten().collect())
+}
+
 #[tauri::command]
 pub async fn db_save_message(app: tauri::AppHandle, message: Value) -> Value {
   run_blocking(
@@ -1358,36 +2230,8 @@ pub async fn db_save_message(app: tauri::AppHandle, message: Value) -> Value {
         None => return json!({ "success": false, "error": "DB not initialized" }),
       };
 
-      let meta = metadata_to_string(input.metadata);
-      let tx = match conn.transaction() {
-        Ok(tx) => tx,
-        Err(err) => return json!({ "success": false, "error": err.to_string() }),
-      };
-
-      if let Err(err) = tx.execute(
-        "INSERT INTO messages (id, conversation_id, content, sender, metadata, timestamp)
-         VALUES (?1, ?2, ?3, ?4, ?5, CURRENT_TIMESTAMP)
-         ON CONFLICT(id) DO NOTHING",
-        params![
-          input.id,
-          input.conversation_id,
-          input.content,
-          input.sender,
-          meta
-        ],
-      ) {
-        return json!({ "success": false, "error": err.to_string() });
-      }
-
-      if let Err(err) = tx.execute(
-        "UPDATE conversations SET updated_at = CURRENT_TIMESTAMP WHERE id = ?1",
-        params![input.conversation_id],
-      ) {
-        return json!({ "success": false, "error": err.to_string() });
-      }
-
-      if let Err(err) = tx.commit() {
-        return json!({ "success": false, "error": err.to_string() });
+      if let Err(err) = save_message(conn, input) {
+        return json!({ "success": false, "error": err });
       }
 
       json!({ "success": true })
@@ -1414,37 +2258,9 @@ pub async fn db_get_messages(app: tauri::AppHandle, conversation_id: String) ->
         None => return json!({ "success": true, "messages": [] }),
       };
 
-      let mut stmt = match conn.prepare(
-        "SELECT id, conversation_id, content, sender, timestamp, metadata
-         FROM messages
-         WHERE conversation_id = ?1
-         ORDER BY timestamp ASC",
-      ) {
-        Ok(stmt) => stmt,
-        Err(err) => return json!({ "success": false, "error": err.to_string() }),
-      };
-
-      let rows = stmt.query_map(params![conversation_id], |row| {
-        let metadata: Option<String> = row.get(5)?;
-        Ok(json!({
-          "id": row.get::<_, String>(0)?,
-          "conversationId": row.get::<_, String>(1)?,
-          "content": row.get::<_, String>(2)?,
-          "sender": row.get::<_, String>(3)?,
-          "timestamp": row.get::<_, String>(4)?,
-          "metadata": parse_metadata(metadata)
-        }))
-      });
-
-      match rows {
-        Ok(iter) => {
-          let mut messages: Vec<Value> = Vec::new();
-          for item in iter.flatten() {
-            messages.push(item);
-          }
-          json!({ "success": true, "messages": messages })
-        }
-        Err(err) => json!({ "success": false, "error": err.to_string() }),
+      match query_messages(conn, &conversation_id) {
+        Ok(messages) => json!({ "success": true, "messages": messages }),
+        Err(err) => json!({ "success": false, "error": err }),
       }
     },
   )
@@ -1517,7 +2333,10 @@ pub async fn project_settings_update(
       if state.is_disabled() {
         return json!({ "success": false, "error": "DB disabled" });
       }
-      if args.base_ref.trim().is_empty() {
+      if args.base_ref.is_none() && args.overrides.is_none() {
+        return json!({ "success": false, "error": "Nothing to update" });
+      }
+      if args.base_ref.as_deref().is_some_and(|v| v.trim().is_empty()) {
         return json!({ "success": false, "error": "baseRef is required" });
       }
       let guard = match lock_conn(&state) {
@@ -1543,17 +2362,33 @@ pub async fn project_settings_update(
         Err(err) => return json!({ "success": false, "error": err.to_string() }),
       };
 
-      let normalized = compute_base_ref(
-        Some(args.base_ref.as_str()),
-        git_remote.as_deref(),
-        git_branch.as_deref(),
-      );
+      if let Some(base_ref) = args.base_ref.as_deref() {
+        let normalized = compute_base_ref(
+          Some(base_ref),
+          git_remote.as_deref(),
+          git_branch.as_deref(),
+        );
+        if let Err(err) = conn.execute(
+          "UPDATE projects SET base_ref = ?1, updated_at = CURRENT_TIMESTAMP WHERE id = ?2",
+          params![normalized, args.project_id],
+        ) {
+          return json!({ "success": false, "error": err.to_string() });
+        }
+      }
 
-      if let Err(err) = conn.execute(
-        "UPDATE projects SET base_ref = ?1, updated_at = CURRENT_TIMESTAMP WHERE id = ?2",
-        params![normalized, args.project_id],
-      ) {
-        return json!({ "success": false, "error": err.to_string() });
+      if let Some(overrides) = args.overrides.as_ref() {
+        // An empty override object clears the column so the project follows global settings.
+        let normalized = crate::settings::normalize_project_overrides(overrides);
+        let stored = normalized
+          .as_object()
+          .filter(|map| !map.is_empty())
+          .map(|_| normalized.to_string());
+        if let Err(err) = conn.execute(
+          "UPDATE projects SET settings = ?1, updated_at = CURRENT_TIMESTAMP WHERE id = ?2",
+          params![stored, args.project_id],
+        ) {
+          return json!({ "success": false, "error": err.to_string() });
+        }
       }
 
       match query_project_settings(conn, &args.project_id) {
@@ -1603,6 +2438,7 @@ pub async fn db_retry_init(app: tauri::AppHandle) -> Value {
         Ok((conn, _)) => match state.replace_conn(Some(conn)) {
           Ok(_) => {
             state.set_disabled(false);
+            state.runtime_error.store(false, Ordering::SeqCst);
             init_state.clear();
             json!({ "success": true })
           }
@@ -1621,6 +2457,109 @@ pub async fn db_retry_init(app: tauri::AppHandle) -> Value {
   .await
 }
 
+#[tauri::command]
+pub async fn db_backup(app: tauri::AppHandle) -> Value {
+  run_blocking(
+    json!({ "success": false, "error": "Task cancelled" }),
+    move || {
+      let state: tauri::State<DbState> = app.state();
+      if state.is_disabled() {
+        return json!({ "success": false, "error": "DB disabled" });
+      }
+      let db_path = match resolve_database_path(&app) {
+        Ok(path) => path,
+        Err(err) => return json!({ "success": false, "error": err }),
+      };
+      let db_name = db_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("emdash.db");
+      let backup_path = db_path.with_file_name(format!("{}.backup-{}", db_name, backup_timestamp()));
+
+      let guard = match lock_conn(&state) {
+        Ok(g) => g,
+        Err(err) => return json!({ "success": false, "error": err }),
+      };
+      let conn = match guard.as_ref() {
+        Some(conn) => conn,
+        None => return json!({ "success": false, "error": "DB not initialized" }),
+      };
+
+      // VACUUM INTO snapshots the live database without closing the connection.
+      match conn.execute("VACUUM INTO ?1", params![backup_path.to_string_lossy()]) {
+        Ok(_) => json!({ "success": true, "backupPath": backup_path.to_string_lossy() }),
+        Err(err) => json!({
+          "success": false,
+          "error": err.to_string(),
+          "backupPath": backup_path.to_string_lossy()
+        }),
+      }
+    },
+  )
+  .await
+}
+
+#[tauri::command]
+pub async fn db_vacuum(app: tauri::AppHandle) -> Value {
+  run_blocking(
+    json!({ "success": false, "error": "Task cancelled" }),
+    move || {
+      let state: tauri::State<DbState> = app.state();
+      if state.is_disabled() {
+        return json!({ "success": false, "error": "DB disabled" });
+      }
+      let guard = match lock_conn(&state) {
+        Ok(g) => g,
+        Err(err) => return json!({ "success": false, "error": err }),
+      };
+      let conn = match guard.as_ref() {
+        Some(conn) => conn,
+        None => return json!({ "success": false, "error": "DB not initialized" }),
+      };
+
+      match conn.execute_batch("VACUUM;") {
+        Ok(_) => json!({ "success": true }),
+        Err(err) => json!({ "success": false, "error": err.to_string() }),
+      }
+    },
+  )
+  .await
+}
+
+#[tauri::command]
+pub async fn db_check_integrity(app: tauri::AppHandle) -> Value {
+  run_blocking(
+    json!({ "success": false, "error": "Task cancelled" }),
+    move || {
+      let state: tauri::State<DbState> = app.state();
+      if state.is_disabled() {
+        return json!({ "success": false, "error": "DB disabled" });
+      }
+      let guard = match lock_conn(&state) {
+        Ok(g) => g,
+        Err(err) => return json!({ "success": false, "error": err }),
+      };
+      let conn = match guard.as_ref() {
+        Some(conn) => conn,
+        None => return json!({ "success": false, "error": "DB not initialized" }),
+      };
+
+      let mut stmt = match conn.prepare("PRAGMA integrity_check") {
+        Ok(stmt) => stmt,
+        Err(err) => return json!({ "success": false, "error": err.to_string() }),
+      };
+      let rows = match stmt.query_map([], |row| row.get::<_, String>(0)) {
+        Ok(rows) => rows,
+        Err(err) => return json!({ "success": false, "error": err.to_string() }),
+      };
+      let problems: Vec<String> = rows.flatten().filter(|line| line != "ok").collect();
+
+      json!({ "success": true, "ok": problems.is_empty(), "problems": problems })
+    },
+  )
+  .await
+}
+
 #[tauri::command]
 pub async fn db_backup_and_reset(app: tauri::AppHandle) -> Value {
   run_blocking(
@@ -1759,3 +2698,330 @@ pub async fn db_backup_and_reset(app: tauri::AppHandle) -> Value {
   )
   .await
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn migrated_conn() -> Connection {
+    let conn = Connection::open_in_memory().unwrap();
+    ensure_migrations(&conn, &MigrationSource::Embedded).unwrap();
+    conn
+  }
+
+  fn journal_rows(conn: &Connection) -> Vec<(String, Option<String>)> {
+    let mut stmt = conn
+      .prepare("SELECT hash, tag FROM \"__drizzle_migrations\" ORDER BY id")
+      .unwrap();
+    let rows = stmt
+      .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+      .unwrap();
+    rows.map(|row| row.unwrap()).collect()
+  }
+
+  #[test]
+  fn ensure_migrations_records_each_tag_once() {
+    let conn = migrated_conn();
+    let migrations = load_migrations(&MigrationSource::Embedded).unwrap();
+    assert!(!migrations.is_empty());
+
+    ensure_migrations(&conn, &MigrationSource::Embedded).unwrap();
+
+    let rows = journal_rows(&conn);
+    assert_eq!(rows.len(), migrations.len());
+    for migration in &migrations {
+      let matching: Vec<_> = rows
+        .iter()
+        .filter(|(_, tag)| tag.as_deref() == Some(migration.tag.as_str()))
+        .collect();
+      assert_eq!(matching.len(), 1, "tag {} recorded once", migration.tag);
+      assert_eq!(matching[0].0, migration.hash);
+    }
+    assert!(table_exists(&conn, "tasks").unwrap());
+    assert!(table_has_column(&conn, "conversations", "task_id").unwrap());
+  }
+
+  fn write_migrations(dir: &Path, migrations: &[(&str, &str)]) {
+    fs::create_dir_all(dir.join("meta")).unwrap();
+    let entries: Vec<Value> = migrations
+      .iter()
+      .enumerate()
+      .map(|(idx, (tag, _))| json!({ "idx": idx, "tag": tag, "when": 1_700_000_000_000i64 }))
+      .collect();
+    fs::write(
+      dir.join("meta").join("_journal.json"),
+      json!({ "entries": entries }).to_string(),
+    )
+    .unwrap();
+    for (tag, sql) in migrations {
+      fs::write(dir.join(format!("{tag}.sql")), sql).unwrap();
+    }
+  }
+
+  #[test]
+  fn ensure_migrations_skips_edited_migration_with_same_tag() {
+    let dir = std::env::temp_dir().join(format!("emdash-migrations-{}", uuid::Uuid::new_v4()));
+    let source = MigrationSource::Disk(dir.clone());
+    let conn = Connection::open_in_memory().unwrap();
+
+    write_migrations(&dir, &[("0000_widgets", "CREATE TABLE widgets (id INTEGER);")]);
+    ensure_migrations(&conn, &source).unwrap();
+
+    // Same tag, different bytes: running it again would fail on the existing table.
+    write_migrations(
+      &dir,
+      &[("0000_widgets", "-- reformatted\nCREATE TABLE widgets (\n  id INTEGER\n);")],
+    );
+    let edited = load_migrations(&source).unwrap();
+    ensure_migrations(&conn, &source).unwrap();
+
+    let rows = journal_rows(&conn);
+    assert_eq!(rows, vec![(edited[0].hash.clone(), Some("0000_widgets".to_string()))]);
+
+    ensure_migrations(&conn, &source).unwrap();
+    assert_eq!(journal_rows(&conn).len(), 1);
+    let _ = fs::remove_dir_all(&dir);
+  }
+
+  #[test]
+  fn keep_backend_metadata_prefers_stored_backend_keys() {
+    let meta = |value: Value| Some(value.to_string());
+    let parsed = |raw: Option<String>| parse_metadata(raw);
+    let cases = [
+      // (incoming, stored, expected)
+      (
+        meta(json!({ "initialPrompt": "hi", "baseRef": "origin/old" })),
+        meta(json!({ "baseRef": "origin/release" })),
+        json!({ "initialPrompt": "hi", "baseRef": "origin/release" }),
+      ),
+      (
+        meta(json!({ "initialPrompt": "hi", "baseRef": "origin/old" })),
+        None,
+        json!({ "initialPrompt": "hi" }),
+      ),
+      (None, meta(json!({ "baseRef": "origin/release" })), json!({ "baseRef": "origin/release" })),
+      (meta(json!({ "autoApprove": true })), None, json!({ "autoApprove": true })),
+      (
+        meta(json!({ "env": { "API_URL": "stale" } })),
+        meta(json!({ "env": { "API_URL": "https://api.test" }, "initialPrompt": "old" })),
+        json!({ "env": { "API_URL": "https://api.test" } }),
+      ),
+      (None, None, Value::Null),
+    ];
+    for (incoming, stored, expected) in cases {
+      assert_eq!(parsed(keep_backend_metadata(incoming, stored)), expected);
+    }
+  }
+
+  #[test]
+  fn cleanup_orphans_removes_only_rows_without_parents() {
+    let conn = migrated_conn();
+    conn.execute_batch("PRAGMA foreign_keys=OFF;").unwrap();
+    conn
+      .execute_batch(
+        "INSERT INTO projects (id, name, path) VALUES ('p1', 'P', '/tmp/p1');
+         INSERT INTO tasks (id, project_id, name, branch, path) VALUES
+           ('t1', 'p1', 'kept', 'b1', '/tmp/t1'),
+           ('t2', 'gone', 'orphan', 'b2', '/tmp/t2');
+         INSERT INTO conversations (id, task_id, title) VALUES
+           ('c1', 't1', 'kept'),
+           ('c2', 't2', 'parent task orphaned'),
+           ('c3', 'missing', 'no task');
+         INSERT INTO messages (id, conversation_id, content, sender) VALUES
+           ('m1', 'c1', 'kept', 'user'),
+           ('m2', 'c2', 'via orphaned task', 'user'),
+           ('m3', 'c3', 'via missing task', 'user'),
+           ('m4', 'nope', 'no conversation', 'user');
+         INSERT INTO task_tags (task_id, tag) VALUES ('t1', 'keep'), ('missing', 'drop');",
+      )
+      .unwrap();
+
+    let counts = cleanup_orphans(&conn).unwrap();
+    assert_eq!(counts.get("tasks"), Some(&json!(1)));
+    assert_eq!(counts.get("conversations"), Some(&json!(2)));
+    assert_eq!(counts.get("messages"), Some(&json!(3)));
+    assert_eq!(counts.get("taskTags"), Some(&json!(1)));
+
+    let ids = |sql: &str| -> Vec<String> {
+      let mut stmt = conn.prepare(sql).unwrap();
+      let rows = stmt.query_map([], |row| row.get(0)).unwrap();
+      rows.map(|row| row.unwrap()).collect()
+    };
+    assert_eq!(ids("SELECT id FROM tasks"), vec!["t1"]);
+    assert_eq!(ids("SELECT id FROM conversations"), vec!["c1"]);
+    assert_eq!(ids("SELECT id FROM messages"), vec!["m1"]);
+    assert_eq!(ids("SELECT task_id FROM task_tags"), vec!["t1"]);
+
+    let again = cleanup_orphans(&conn).unwrap();
+    assert!(again.values().all(|count| count == &json!(0)));
+  }
+
+  #[test]
+  fn delete_task_removes_its_tags_without_fk_cascade() {
+    let mut conn = migrated_conn();
+    conn.execute_batch(
+      "PRAGMA foreign_keys = OFF;
+       INSERT INTO projects (id, name, path) VALUES ('p1', 'project', '/tmp/p1');
+       INSERT INTO tasks (id, project_id, name, branch, path) VALUES
+         ('t1', 'p1', 'deleted', 'b1', '/tmp/t1'),
+         ('t2', 'p1', 'kept', 'b2', '/tmp/t2');
+       INSERT INTO task_tags (task_id, tag) VALUES ('t1', 'a'), ('t1', 'b'), ('t2', 'c');",
+    )
+    .unwrap();
+
+    delete_task(&mut conn, "t1").unwrap();
+
+    assert!(query_task_tags(&conn, "t1").unwrap().is_empty());
+    assert_eq!(query_task_tags(&conn, "t2").unwrap(), vec!["c"]);
+    let remaining: i64 = conn
+      .query_row("SELECT COUNT(*) FROM tasks", [], |row| row.get(0))
+      .unwrap();
+    assert_eq!(remaining, 1);
+  }
+
+  #[test]
+  fn messages_keep_their_provider_and_model() {
+    let mut conn = migrated_conn();
+    conn.execute_batch(
+      "PRAGMA foreign_keys = OFF;
+       INSERT INTO conversations (id, task_id, title) VALUES ('c1', 't1', 'chat');",
+    )
+    .unwrap();
+    let cases = [
+      (
+        json!({ "id": "m1", "conversationId": "c1", "content": "hi", "sender": "agent",
+                "providerId": "claude", "model": "sonnet" }),
+        json!("claude"),
+        json!("sonnet"),
+      ),
+      (
+        json!({ "id": "m2", "conversationId": "c1", "content": "hi", "sender": "agent",
+                "providerId": "codex" }),
+        json!("codex"),
+        Value::Null,
+      ),
+      (
+        json!({ "id": "m3", "conversationId": "c1", "content": "hi", "sender": "user" }),
+        Value::Null,
+        Value::Null,
+      ),
+    ];
+    for (input, _, _) in &cases {
+      save_message(&mut conn, serde_json::from_value(input.clone()).unwrap()).unwrap();
+    }
+
+    let messages = query_messages(&conn, "c1").unwrap();
+    assert_eq!(messages.len(), cases.len());
+    for (input, provider_id, model) in &cases {
+      let saved = messages.iter().find(|m| m["id"] == input["id"]).unwrap();
+      assert_eq!(&saved["providerId"], provider_id, "{}", input["id"]);
+      assert_eq!(&saved["model"], model, "{}", input["id"]);
+    }
+  }
+
+  #[test]
+  fn collect_stats_counts_rows_and_picks_latest_task() {
+    let conn = migrated_conn();
+    let empty = collect_stats(&conn).unwrap();
+    assert_eq!(
+      empty,
+      json!({
+        "projects": 0,
+        "tasks": { "total": 0, "byStatus": {} },
+        "conversations": 0,
+        "messages": 0,
+        "latestTaskByProject": {}
+      })
+    );
+
+    conn.execute_batch(
+      "PRAGMA foreign_keys = OFF;
+       INSERT INTO projects (id, name, path) VALUES
+         ('p1', 'one', '/tmp/p1'),
+         ('p2', 'two', '/tmp/p2'),
+         ('p3', 'empty', '/tmp/p3');
+       INSERT INTO tasks (id, project_id, name, branch, path, status, updated_at) VALUES
+         ('t1', 'p1', 'old', 'b1', '/tmp/t1', 'idle', '2024-01-01 10:00:00'),
+         ('t2', 'p1', 'new', 'b2', '/tmp/t2', 'running', '2024-01-02 10:00:00'),
+         ('t3', 'p2', 'only', 'b3', '/tmp/t3', 'idle', '2024-01-01 09:00:00');
+       INSERT INTO conversations (id, task_id, title) VALUES ('c1', 't1', 'a'), ('c2', 't2', 'b');
+       INSERT INTO messages (id, conversation_id, content, sender) VALUES
+         ('m1', 'c1', 'hi', 'user');",
+    )
+    .unwrap();
+
+    let stats = collect_stats(&conn).unwrap();
+    let cases = [
+      ("/projects", json!(3)),
+      ("/tasks/total", json!(3)),
+      ("/tasks/byStatus/idle", json!(2)),
+      ("/tasks/byStatus/running", json!(1)),
+      ("/conversations", json!(2)),
+      ("/messages", json!(1)),
+      ("/latestTaskByProject/p1/id", json!("t2")),
+      ("/latestTaskByProject/p1/status", json!("running")),
+      ("/latestTaskByProject/p2/id", json!("t3")),
+    ];
+    for (pointer, expected) in cases {
+      assert_eq!(stats.pointer(pointer), Some(&expected), "{}", pointer);
+    }
+    assert!(stats.pointer("/latestTaskByProject/p3").is_none());
+  }
+
+  #[test]
+  fn global_search_escapes_like_wildcards() {
+    let cases = [
+      ("api", "%api%"),
+      ("50%", "%50\\%%"),
+      ("snake_case", "%snake\\_case%"),
+      ("C:\\src", "%C:\\\\src%"),
+      ("", "%%"),
+    ];
+    for (query, expected) in cases {
+      assert_eq!(like_pattern(query), expected, "{:?}", query);
+    }
+  }
+
+  #[test]
+  fn global_search_ranks_exact_then_prefix_then_substring() {
+    let cases = [
+      ("Billing", "billing", 0),
+      ("billing-api", "billing", 1),
+      ("Fix billing", "billing", 2),
+      ("payments", "billing", 3),
+    ];
+    for (label, query, expected) in cases {
+      assert_eq!(match_rank(label, query), expected, "{:?}", label);
+    }
+
+    let conn = migrated_conn();
+    conn.execute_batch(
+      "PRAGMA foreign_keys = OFF;
+       INSERT INTO projects (id, name, path) VALUES
+         ('p1', 'billing', '/src/billing'),
+         ('p2', 'shop', '/src/shop');
+       INSERT INTO tasks (id, project_id, name, branch, path) VALUES
+         ('t1', 'p2', 'Fix billing totals', 'fix/totals', '/tmp/t1'),
+         ('t2', 'p2', 'Billing-export', 'export', '/tmp/t2'),
+         ('t3', 'p2', 'Cleanup', 'billing_cleanup', '/tmp/t3'),
+         ('t4', 'p2', '100% coverage', 'coverage', '/tmp/t4'),
+         ('t5', 'p2', '1000 users', 'users', '/tmp/t5');
+       INSERT INTO conversations (id, task_id, title) VALUES ('c1', 't2', 'billing');",
+    )
+    .unwrap();
+
+    let ids = |query: &str, limit: usize| -> Vec<String> {
+      global_search(&conn, query, limit)
+        .unwrap()
+        .iter()
+        .map(|hit| format!("{}:{}", hit["kind"].as_str().unwrap(), hit["id"].as_str().unwrap()))
+        .collect()
+    };
+    assert_eq!(
+      ids("billing", 50),
+      vec!["project:p1", "conversation:c1", "task:t2", "task:t3", "task:t1"]
+    );
+    assert_eq!(ids("billing", 2), vec!["project:p1", "conversation:c1"]);
+    assert_eq!(ids("100%", 50), vec!["task:t4"]);
+  }
+}
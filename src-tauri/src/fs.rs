@@ -1,10 +1,15 @@
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use serde_json::{json, Value};
 use crate::runtime::run_blocking;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
-use tauri::Emitter;
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{Emitter, Manager};
 
-const DEFAULT_IGNORES: &[&str] = &[
+pub(crate) const DEFAULT_IGNORES: &[&str] = &[
   ".git",
   "node_modules",
   "dist",
@@ -23,6 +28,31 @@ const ALLOWED_IMAGE_EXTENSIONS: &[&str] = &[
 
 const DEFAULT_ATTACHMENTS_SUBDIR: &str = "attachments";
 
+const WATCH_DEBOUNCE_MS: u64 = 200;
+const WATCH_MAX_DELAY_MS: u64 = 1000;
+
+const TREE_ALWAYS_SKIPPED: &[&str] = &[".git", "node_modules"];
+const TREE_MAX_ENTRIES: usize = 10000;
+
+#[derive(Default)]
+pub struct FsWatchState {
+  watchers: Arc<Mutex<HashMap<String, RecommendedWatcher>>>,
+}
+
+impl FsWatchState {
+  pub fn new() -> Self {
+    Self {
+      watchers: Arc::new(Mutex::new(HashMap::new())),
+    }
+  }
+
+  pub fn stop_all(&self) {
+    if let Ok(mut map) = self.watchers.lock() {
+      map.clear();
+    }
+  }
+}
+
 fn normalize_rel_path(path: &str) -> Result<PathBuf, String> {
   if path.trim().is_empty() {
     return Err("Invalid relPath".to_string());
@@ -116,6 +146,186 @@ fn list_files(root: &Path, include_dirs: bool, max_entries: usize) -> Vec<Value>
   items
 }
 
+fn glob_match(pattern: &[u8], text: &[u8]) -> bool {
+  match pattern.first() {
+    None => text.is_empty(),
+    Some(b'*') if pattern.get(1) == Some(&b'*') => {
+      let rest = &pattern[2..];
+      // "**/" may also match zero directories.
+      if rest.first() == Some(&b'/') && glob_match(&rest[1..], text) {
+        return true;
+      }
+      (0..=text.len()).any(|idx| glob_match(rest, &text[idx..]))
+    }
+    Some(b'*') => {
+      let rest = &pattern[1..];
+      for idx in 0..=text.len() {
+        if glob_match(rest, &text[idx..]) {
+          return true;
+        }
+        if idx < text.len() && text[idx] == b'/' {
+          break;
+        }
+      }
+      false
+    }
+    Some(b'?') => !text.is_empty() && text[0] != b'/' && glob_match(&pattern[1..], &text[1..]),
+    Some(ch) => !text.is_empty() && text[0] == *ch && glob_match(&pattern[1..], &text[1..]),
+  }
+}
+
+// Patterns without a slash match the entry name anywhere in the tree, like .gitignore.
+fn matches_any_glob(globs: &[String], rel: &str, name: &str) -> bool {
+  globs.iter().any(|glob| {
+    let glob = glob.trim().trim_end_matches('/');
+    if glob.is_empty() {
+      return false;
+    }
+    if glob.contains('/') {
+      glob_match(glob.trim_start_matches('/').as_bytes(), rel.as_bytes())
+    } else {
+      glob_match(glob.as_bytes(), name.as_bytes())
+    }
+  })
+}
+
+// Whether an include glob names an always-skipped directory explicitly, either by its
+// own name or through a path pattern that leads into it (e.g. "node_modules/pkg/**").
+fn include_reaches(globs: &[String], rel: &str, name: &str) -> bool {
+  globs.iter().any(|glob| {
+    let glob = glob.trim().trim_end_matches('/');
+    if glob.is_empty() {
+      return false;
+    }
+    if !glob.contains('/') {
+      return glob_match(glob.as_bytes(), name.as_bytes());
+    }
+    let mut segments = glob.trim_start_matches('/').split('/');
+    for part in rel.split('/') {
+      match segments.next() {
+        Some("**") => return true,
+        Some(segment) if glob_match(segment.as_bytes(), part.as_bytes()) => continue,
+        _ => return false,
+      }
+    }
+    true
+  })
+}
+
+fn list_tree(
+  root: &Path,
+  max_depth: Option<u32>,
+  include_globs: &[String],
+  exclude_globs: &[String],
+) -> (Vec<Value>, bool) {
+  let mut items: Vec<Value> = Vec::new();
+  let mut stack: Vec<(PathBuf, u32)> = vec![(PathBuf::new(), 0)];
+
+  while let Some((rel, depth)) = stack.pop() {
+    let entries = match fs::read_dir(root.join(&rel)) {
+      Ok(entries) => entries,
+      Err(_) => continue,
+    };
+    let mut children: Vec<(String, PathBuf, fs::Metadata)> = Vec::new();
+    for entry in entries.flatten() {
+      let name = entry.file_name().to_string_lossy().to_string();
+      let child_rel = rel.join(&name);
+      let rel_str = child_rel.to_string_lossy().replace('\\', "/");
+      if TREE_ALWAYS_SKIPPED.contains(&name.as_str())
+        && !include_reaches(include_globs, &rel_str, &name)
+      {
+        continue;
+      }
+      if matches_any_glob(exclude_globs, &rel_str, &name) {
+        continue;
+      }
+      let metadata = match entry.metadata() {
+        Ok(meta) => meta,
+        Err(_) => continue,
+      };
+      children.push((rel_str, child_rel, metadata));
+    }
+    children.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut subdirs: Vec<PathBuf> = Vec::new();
+    for (rel_str, child_rel, metadata) in children {
+      let name = child_rel
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+      let is_dir = metadata.is_dir();
+      let included = include_globs.is_empty() || matches_any_glob(include_globs, &rel_str, &name);
+      if included {
+        if items.len() >= TREE_MAX_ENTRIES {
+          return (items, true);
+        }
+        items.push(json!({
+          "path": rel_str,
+          "isDir": is_dir,
+          "size": if is_dir { 0 } else { metadata.len() }
+        }));
+      }
+      if is_dir && max_depth.map(|max| depth + 1 < max).unwrap_or(true) {
+        subdirs.push(child_rel);
+      }
+    }
+    for dir in subdirs.into_iter().rev() {
+      stack.push((dir, depth + 1));
+    }
+  }
+
+  (items, false)
+}
+
+fn is_ignored_watch_path(rel: &str) -> bool {
+  rel == ".git" || rel.starts_with(".git/") || rel.ends_with("codex-stream.log")
+}
+
+// Coalesces raw notify events and emits once things go quiet, or at least every
+// WATCH_MAX_DELAY_MS while changes keep streaming in. Exits when the watcher is dropped.
+fn spawn_watch_debouncer(
+  app: tauri::AppHandle,
+  id: String,
+  root: PathBuf,
+  rx: mpsc::Receiver<notify::Result<notify::Event>>,
+) {
+  thread::spawn(move || {
+    let mut pending: HashSet<String> = HashSet::new();
+    let mut first_pending: Option<Instant> = None;
+    loop {
+      let received = rx.recv_timeout(Duration::from_millis(WATCH_DEBOUNCE_MS));
+      let disconnected = matches!(received, Err(mpsc::RecvTimeoutError::Disconnected));
+      let quiet = matches!(received, Err(mpsc::RecvTimeoutError::Timeout));
+      if let Ok(Ok(event)) = received {
+        for path in event.paths {
+          let rel = match path.strip_prefix(&root) {
+            Ok(rel) => rel.to_string_lossy().replace('\\', "/"),
+            Err(_) => continue,
+          };
+          if rel.is_empty() || is_ignored_watch_path(&rel) {
+            continue;
+          }
+          pending.insert(rel);
+          first_pending.get_or_insert_with(Instant::now);
+        }
+      }
+
+      let overdue = first_pending
+        .map(|since| since.elapsed() >= Duration::from_millis(WATCH_MAX_DELAY_MS))
+        .unwrap_or(false);
+      if !pending.is_empty() && (quiet || overdue || disconnected) {
+        let mut paths: Vec<String> = pending.drain().collect();
+        paths.sort();
+        first_pending = None;
+        let _ = app.emit("fs:changed", json!({ "id": id, "paths": paths }));
+      }
+      if disconnected {
+        break;
+      }
+    }
+  });
+}
+
 fn emit_plan_event(app: &tauri::AppHandle, payload: Value) {
   let _ = app.emit("plan:event", payload);
 }
@@ -142,6 +352,75 @@ pub async fn fs_list(
   .await
 }
 
+#[tauri::command]
+pub async fn fs_list_tree(
+  path: String,
+  max_depth: Option<u32>,
+  include_globs: Option<Vec<String>>,
+  exclude_globs: Option<Vec<String>>,
+) -> Value {
+  run_blocking(
+    json!({ "success": false, "error": "Task cancelled" }),
+    move || {
+      let root_path = match resolve_root(&path) {
+        Ok(path) => path,
+        Err(err) => return json!({ "success": false, "error": err }),
+      };
+      let include_globs = include_globs.unwrap_or_default();
+      let exclude_globs = exclude_globs.unwrap_or_default();
+      let (items, truncated) = list_tree(&root_path, max_depth, &include_globs, &exclude_globs);
+      json!({ "success": true, "items": items, "truncated": truncated })
+    },
+  )
+  .await
+}
+
+#[tauri::command]
+pub async fn fs_watch_start(app: tauri::AppHandle, path: String, id: String) -> Value {
+  run_blocking(
+    json!({ "success": false, "error": "Task cancelled" }),
+    move || {
+      if id.trim().is_empty() {
+        return json!({ "success": false, "error": "Invalid watch id" });
+      }
+      let root = match resolve_root(&path) {
+        Ok(root) => fs::canonicalize(&root).unwrap_or(root),
+        Err(err) => return json!({ "success": false, "error": err }),
+      };
+
+      let (tx, rx) = mpsc::channel();
+      let mut watcher = match notify::recommended_watcher(tx) {
+        Ok(watcher) => watcher,
+        Err(err) => return json!({ "success": false, "error": err.to_string() }),
+      };
+      if let Err(err) = watcher.watch(&root, RecursiveMode::Recursive) {
+        return json!({ "success": false, "error": err.to_string() });
+      }
+      spawn_watch_debouncer(app.clone(), id.clone(), root, rx);
+
+      let state: tauri::State<FsWatchState> = app.state();
+      let mut map = state.watchers.lock().unwrap();
+      // Replacing an existing watcher drops it, which also ends its debouncer thread.
+      map.insert(id, watcher);
+      json!({ "success": true })
+    },
+  )
+  .await
+}
+
+#[tauri::command]
+pub async fn fs_watch_stop(app: tauri::AppHandle, id: String) -> Value {
+  run_blocking(
+    json!({ "success": false, "error": "Task cancelled" }),
+    move || {
+      let state: tauri::State<FsWatchState> = app.state();
+      let removed = state.watchers.lock().unwrap().remove(&id).is_some();
+      json!({ "success": true, "stopped": removed })
+    },
+  )
+  .await
+}
+
 #[tauri::command]
 pub async fn fs_read(root: String, rel_path: String, max_bytes: Option<usize>) -> Value {
   run_blocking(
@@ -377,6 +656,9 @@ pub async fn fs_save_attachment(
       if let Err(err) = fs::copy(&src, &dest_abs) {
         return json!({ "success": false, "error": err.to_string() });
       }
+      if crate::browser::is_capture_file(&src) {
+        let _ = fs::remove_file(&src);
+      }
 
       let rel = dest_abs
         .strip_prefix(&task_root)
@@ -395,3 +677,57 @@ pub async fn fs_save_attachment(
   )
   .await
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn globs(patterns: &[&str]) -> Vec<String> {
+    patterns.iter().map(|p| p.to_string()).collect()
+  }
+
+  #[test]
+  fn include_reaches_only_explicitly_named_skipped_dirs() {
+    let cases: &[(&[&str], &str, &str, bool)] = &[
+      (&["node_modules"], "node_modules", "node_modules", true),
+      (&["node_*"], "node_modules", "node_modules", true),
+      (&["node_modules/pkg/**"], "node_modules", "node_modules", true),
+      (&["node_modules/pkg/**"], "node_modules/pkg", "pkg", true),
+      (&["node_modules/pkg/**"], "node_modules/other", "other", false),
+      (&["**/node_modules/**"], "web/node_modules", "node_modules", true),
+      (&["web/node_modules"], "web/node_modules", "node_modules", true),
+      (&["api/node_modules"], "web/node_modules", "node_modules", false),
+      // Substrings of an unrelated glob must not unskip the directory.
+      (&["src/git_helpers.rs"], ".git", ".git", false),
+      (&["*.rs"], "node_modules", "node_modules", false),
+      (&["my_node_modules_notes.md"], "node_modules", "node_modules", false),
+    ];
+    for (patterns, rel, name, expected) in cases {
+      assert_eq!(
+        include_reaches(&globs(patterns), rel, name),
+        *expected,
+        "{:?} against {}",
+        patterns,
+        rel
+      );
+    }
+  }
+
+  #[test]
+  fn list_tree_descends_into_skipped_dirs_only_when_included() {
+    let root = std::env::temp_dir().join(format!("emdash-fs-tree-{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(root.join("node_modules").join("pkg")).unwrap();
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(root.join("node_modules").join("pkg").join("index.js"), "").unwrap();
+    fs::write(root.join("src").join("node_modules.rs"), "").unwrap();
+
+    let paths = |include: &[&str]| -> Vec<String> {
+      let (items, _) = list_tree(&root, None, &globs(include), &[]);
+      items.iter().map(|item| item["path"].as_str().unwrap().to_string()).collect()
+    };
+    assert_eq!(paths(&[]), vec!["src", "src/node_modules.rs"]);
+    assert_eq!(paths(&["src/node_modules.rs"]), vec!["src/node_modules.rs"]);
+    assert_eq!(paths(&["node_modules/**/*.js"]), vec!["node_modules/pkg/index.js"]);
+    let _ = fs::remove_dir_all(&root);
+  }
+}
@@ -1,174 +1,182 @@
 use crate::db::{self, DbState};
 use crate::providers;
 use crate::runtime::run_blocking;
+use crate::settings;
 use tauri::Manager;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
-use std::sync::{Arc, Mutex};
+use std::sync::{
+  atomic::{AtomicBool, Ordering},
+  Arc, Mutex,
+};
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 const DEFAULT_REMOTE: &str = "origin";
 const DEFAULT_BRANCH: &str = "main";
+const UNTRACKED_COUNT_MAX_BYTES: u64 = 1024 * 1024;
+const UNTRACKED_SKIP_BYTES: u64 = 16 * 1024 * 1024;
 
 #[derive(Clone, Copy)]
 struct ProviderGenerationConfig {
   id: &'static str,
   cli: &'static str,
-  version_args: Option<&'static [&'static str]>,
   default_args: Option<&'static [&'static str]>,
   auto_approve_flag: Option<&'static str>,
   initial_prompt_flag: Option<&'static str>,
+  model_flag: Option<&'static str>,
 }
 
+// `model_flag` is set for CLIs that accept `--model <name>` (codex, claude, cursor-agent,
+// gemini, qwen, opencode, copilot). Others ignore the configured model and use their default.
 const PROVIDER_GENERATION_CONFIGS: &[ProviderGenerationConfig] = &[
   ProviderGenerationConfig {
     id: "codex",
     cli: "codex",
-    version_args: Some(&["--version"]),
     default_args: None,
     auto_approve_flag: Some("--full-auto"),
     initial_prompt_flag: Some(""),
+    model_flag: Some("--model"),
   },
   ProviderGenerationConfig {
     id: "claude",
     cli: "claude",
-    version_args: Some(&["--version"]),
     default_args: None,
     auto_approve_flag: Some("--dangerously-skip-permissions"),
     initial_prompt_flag: Some(""),
+    model_flag: Some("--model"),
   },
   ProviderGenerationConfig {
     id: "cursor",
     cli: "cursor-agent",
-    version_args: Some(&["--version"]),
     default_args: None,
     auto_approve_flag: Some("-p"),
     initial_prompt_flag: Some(""),
+    model_flag: Some("--model"),
   },
   ProviderGenerationConfig {
     id: "gemini",
     cli: "gemini",
-    version_args: Some(&["--version"]),
     default_args: None,
     auto_approve_flag: Some("--yolomode"),
     initial_prompt_flag: Some("-i"),
+    model_flag: Some("--model"),
   },
   ProviderGenerationConfig {
     id: "qwen",
     cli: "qwen",
-    version_args: Some(&["--version"]),
     default_args: None,
     auto_approve_flag: Some("--yolo"),
     initial_prompt_flag: Some("-i"),
+    model_flag: Some("--model"),
   },
   ProviderGenerationConfig {
     id: "droid",
     cli: "droid",
-    version_args: Some(&["--version"]),
     default_args: None,
     auto_approve_flag: None,
     initial_prompt_flag: Some(""),
+    model_flag: None,
   },
   ProviderGenerationConfig {
     id: "amp",
     cli: "amp",
-    version_args: Some(&["--version"]),
     default_args: None,
     auto_approve_flag: None,
     initial_prompt_flag: None,
+    model_flag: None,
   },
   ProviderGenerationConfig {
     id: "opencode",
     cli: "opencode",
-    version_args: Some(&["--version"]),
     default_args: None,
     auto_approve_flag: None,
     initial_prompt_flag: Some("-p"),
+    model_flag: Some("--model"),
   },
   ProviderGenerationConfig {
     id: "copilot",
     cli: "copilot",
-    version_args: Some(&["--version"]),
     default_args: None,
     auto_approve_flag: None,
     initial_prompt_flag: None,
+    model_flag: Some("--model"),
   },
   ProviderGenerationConfig {
     id: "charm",
     cli: "crush",
-    version_args: Some(&["--version"]),
     default_args: None,
     auto_approve_flag: None,
     initial_prompt_flag: None,
+    model_flag: None,
   },
   ProviderGenerationConfig {
     id: "auggie",
     cli: "auggie",
-    version_args: Some(&["--version"]),
     default_args: Some(&["--allow-indexing"]),
     auto_approve_flag: None,
     initial_prompt_flag: Some(""),
+    model_flag: None,
   },
   ProviderGenerationConfig {
     id: "goose",
     cli: "goose",
-    version_args: None,
     default_args: Some(&["run", "-s"]),
     auto_approve_flag: None,
     initial_prompt_flag: Some("-t"),
+    model_flag: None,
   },
   ProviderGenerationConfig {
     id: "kimi",
     cli: "kimi",
-    version_args: Some(&["--version"]),
     default_args: None,
     auto_approve_flag: None,
     initial_prompt_flag: Some("-c"),
+    model_flag: None,
   },
   ProviderGenerationConfig {
     id: "kilocode",
     cli: "kilocode",
-    version_args: Some(&["--version"]),
     default_args: None,
     auto_approve_flag: Some("--auto"),
     initial_prompt_flag: Some(""),
+    model_flag: None,
   },
   ProviderGenerationConfig {
     id: "kiro",
     cli: "kiro-cli",
-    version_args: Some(&["--version"]),
     default_args: Some(&["chat"]),
     auto_approve_flag: None,
     initial_prompt_flag: Some(""),
+    model_flag: None,
   },
   ProviderGenerationConfig {
     id: "cline",
     cli: "cline",
-    version_args: Some(&["help"]),
     default_args: None,
     auto_approve_flag: None,
     initial_prompt_flag: Some(""),
+    model_flag: None,
   },
   ProviderGenerationConfig {
     id: "codebuff",
     cli: "codebuff",
-    version_args: Some(&["--version"]),
     default_args: None,
     auto_approve_flag: None,
     initial_prompt_flag: Some(""),
+    model_flag: None,
   },
   ProviderGenerationConfig {
     id: "mistral",
     cli: "vibe",
-    version_args: Some(&["-h"]),
     default_args: None,
     auto_approve_flag: Some("--auto-approve"),
     initial_prompt_flag: Some("--prompt"),
+    model_flag: None,
   },
 ];
 
@@ -176,8 +184,23 @@ fn provider_generation_config(id: &str) -> Option<&'static ProviderGenerationCon
   PROVIDER_GENERATION_CONFIGS.iter().find(|provider| provider.id == id)
 }
 
-fn resolve_git_bin() -> String {
-  if let Ok(val) = std::env::var("GIT_PATH") {
+static GIT_BIN: Mutex<Option<String>> = Mutex::new(None);
+
+/// The git binary to run, resolved once and cached for the life of the process.
+pub(crate) fn resolve_git_bin() -> String {
+  cached_git_bin(&GIT_BIN, detect_git_bin)
+}
+
+fn cached_git_bin(cache: &Mutex<Option<String>>, detect: impl FnOnce() -> String) -> String {
+  cache.lock().unwrap().get_or_insert_with(detect).clone()
+}
+
+fn detect_git_bin() -> String {
+  git_bin_from_override(std::env::var("GIT_PATH").ok())
+}
+
+fn git_bin_from_override(git_path: Option<String>) -> String {
+  if let Some(val) = git_path {
     let trimmed = val.trim();
     if !trimmed.is_empty() {
       return trimmed.to_string();
@@ -207,13 +230,35 @@ fn combine_output(stdout: &str, stderr: &str) -> String {
   }
 }
 
+const GH_NOT_INSTALLED: &str = "GitHub CLI (gh) is not installed";
+const PATH_NOT_FOUND: &str = "Path not found";
+
+// Turns a missing binary into an actionable message instead of the bare OS error. A missing
+// working directory (e.g. a deleted task worktree) surfaces as the same NotFound error.
+fn spawn_error(bin: &str, cwd: Option<&Path>, err: std::io::Error) -> String {
+  if err.kind() != std::io::ErrorKind::NotFound {
+    return err.to_string();
+  }
+  if let Some(dir) = cwd.filter(|dir| !dir.is_dir()) {
+    return format!("{}: {}", PATH_NOT_FOUND, dir.display());
+  }
+  if bin == "gh" {
+    format!(
+      "{}. Install it from Settings or https://cli.github.com, then run `gh auth login`.",
+      GH_NOT_INSTALLED
+    )
+  } else {
+    format!("{} is not installed or not on PATH", bin)
+  }
+}
+
 fn run_cmd(bin: &str, args: &[&str], cwd: Option<&Path>) -> Result<String, String> {
   let mut cmd = Command::new(bin);
   cmd.args(args);
   if let Some(dir) = cwd {
     cmd.current_dir(dir);
   }
-  let output = cmd.output().map_err(|err| err.to_string())?;
+  let output = cmd.output().map_err(|err| spawn_error(bin, cwd, err))?;
   let stdout = String::from_utf8_lossy(&output.stdout).to_string();
   let stderr = String::from_utf8_lossy(&output.stderr).to_string();
   if output.status.success() {
@@ -233,7 +278,7 @@ fn run_cmd_output(
   if let Some(dir) = cwd {
     cmd.current_dir(dir);
   }
-  let output = cmd.output().map_err(|err| err.to_string())?;
+  let output = cmd.output().map_err(|err| spawn_error(bin, cwd, err))?;
   let stdout = String::from_utf8_lossy(&output.stdout).to_string();
   let stderr = String::from_utf8_lossy(&output.stderr).to_string();
   Ok((output.status.success(), stdout, stderr))
@@ -244,7 +289,168 @@ fn run_git(cwd: &Path, args: &[&str]) -> Result<String, String> {
   run_cmd(&git, args, Some(cwd))
 }
 
-fn normalize_remote_name(remote: Option<&str>) -> String {
+pub(crate) const GIT_CANCELLED: &str = "Cancelled";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum GitErrorCode {
+  NotARepo,
+  AuthFailed,
+  NothingToCommit,
+  NoUpstream,
+  Conflict,
+  RemoteRefMissing,
+  GhNotInstalled,
+  PathNotFound,
+  Network,
+  Unknown,
+}
+
+impl GitErrorCode {
+  pub(crate) fn as_str(self) -> &'static str {
+    match self {
+      GitErrorCode::NotARepo => "NOT_A_REPO",
+      GitErrorCode::AuthFailed => "AUTH_FAILED",
+      GitErrorCode::NothingToCommit => "NOTHING_TO_COMMIT",
+      GitErrorCode::NoUpstream => "NO_UPSTREAM",
+      GitErrorCode::Conflict => "CONFLICT",
+      GitErrorCode::RemoteRefMissing => "REMOTE_REF_MISSING",
+      GitErrorCode::GhNotInstalled => "GH_NOT_INSTALLED",
+      GitErrorCode::PathNotFound => "PATH_NOT_FOUND",
+      GitErrorCode::Network => "NETWORK_ERROR",
+      GitErrorCode::Unknown => "UNKNOWN",
+    }
+  }
+}
+
+/// Maps git/gh output onto a `GitErrorCode` the renderer can branch on.
+pub(crate) fn classify_git_error(message: &str) -> GitErrorCode {
+  let msg = message.to_lowercase();
+  if msg.starts_with(&PATH_NOT_FOUND.to_lowercase()) {
+    GitErrorCode::PathNotFound
+  } else if msg.contains("not a git repository") {
+    GitErrorCode::NotARepo
+  } else if msg.contains(&GH_NOT_INSTALLED.to_lowercase()) || msg.contains("gh: command not found") {
+    GitErrorCode::GhNotInstalled
+  } else if msg.contains("could not resolve hostname")
+    || msg.contains("could not resolve host")
+    || msg.contains("network is unreachable")
+    || msg.contains("connection timed out")
+    || msg.contains("connection refused")
+  {
+    // Checked before auth: ssh follows these with "could not read from remote repository".
+    GitErrorCode::Network
+  } else if msg.contains("permission denied (publickey")
+    || msg.contains("authentication failed")
+    || msg.contains("could not read username")
+    || msg.contains("could not read password")
+    || msg.contains("terminal prompts disabled")
+    || msg.contains("host key verification failed")
+    || msg.contains("invalid username or password")
+    || msg.contains("could not read from remote repository")
+    || msg.contains("gh auth login")
+  {
+    GitErrorCode::AuthFailed
+  } else if msg.contains("nothing to commit") || msg.contains("no changes added to commit") {
+    GitErrorCode::NothingToCommit
+  } else if is_no_upstream_error(&msg) {
+    GitErrorCode::NoUpstream
+  } else if msg.contains("conflict (")
+    || msg.contains("fix conflicts")
+    || msg.contains("unmerged paths")
+    || msg.contains("unmerged files")
+  {
+    GitErrorCode::Conflict
+  } else if msg.contains("couldn't find remote ref")
+    || msg.contains("could not find remote ref")
+    || msg.contains("remote ref does not exist")
+  {
+    GitErrorCode::RemoteRefMissing
+  } else {
+    GitErrorCode::Unknown
+  }
+}
+
+/// Attaches a `code` to failure payloads that don't already carry one.
+fn with_error_code(mut value: Value) -> Value {
+  let Some(obj) = value.as_object_mut() else {
+    return value;
+  };
+  let failed = obj.get("success").and_then(|v| v.as_bool()) == Some(false);
+  let cancelled = obj.get("cancelled").and_then(|v| v.as_bool()) == Some(true);
+  if !failed || cancelled || obj.contains_key("code") {
+    return value;
+  }
+  let message = ["error", "output"]
+    .iter()
+    .filter_map(|key| obj.get(*key).and_then(|v| v.as_str()))
+    .collect::<Vec<_>>()
+    .join("\n");
+  obj.insert("code".to_string(), json!(classify_git_error(&message).as_str()));
+  value
+}
+
+async fn run_git_command<F>(fallback: Value, f: F) -> Value
+where
+  F: FnOnce() -> Value + Send + 'static,
+{
+  with_error_code(run_blocking(fallback, f).await)
+}
+
+fn spawn_pipe_reader<R: Read + Send + 'static>(
+  pipe: Option<R>,
+) -> std::thread::JoinHandle<String> {
+  std::thread::spawn(move || {
+    let mut buf = Vec::new();
+    if let Some(mut pipe) = pipe {
+      let _ = pipe.read_to_end(&mut buf);
+    }
+    String::from_utf8_lossy(&buf).to_string()
+  })
+}
+
+/// Like `run_git`, but polls `cancel` and kills the child once it is set.
+pub(crate) fn run_git_cancellable(
+  cwd: &Path,
+  args: &[&str],
+  cancel: Option<&AtomicBool>,
+) -> Result<String, String> {
+  let Some(cancel) = cancel else {
+    return run_git(cwd, args);
+  };
+  let mut child = Command::new(resolve_git_bin())
+    .args(args)
+    .current_dir(cwd)
+    .stdin(Stdio::null())
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .spawn()
+    .map_err(|err| err.to_string())?;
+
+  let stdout = spawn_pipe_reader(child.stdout.take());
+  let stderr = spawn_pipe_reader(child.stderr.take());
+
+  let status = loop {
+    if cancel.load(Ordering::SeqCst) {
+      let _ = child.kill();
+      let _ = child.wait();
+      break None;
+    }
+    match child.try_wait() {
+      Ok(Some(status)) => break Some(status),
+      Ok(None) => std::thread::sleep(Duration::from_millis(50)),
+      Err(err) => return Err(err.to_string()),
+    }
+  };
+  let stdout = stdout.join().unwrap_or_default();
+  let stderr = stderr.join().unwrap_or_default();
+  match status {
+    None => Err(GIT_CANCELLED.to_string()),
+    Some(status) if status.success() => Ok(stdout),
+    Some(_) => Err(combine_output(&stdout, &stderr)),
+  }
+}
+
+pub(crate) fn normalize_remote_name(remote: Option<&str>) -> String {
   let trimmed = remote.unwrap_or("").trim();
   if trimmed.is_empty() {
     return DEFAULT_REMOTE.to_string();
@@ -257,6 +463,68 @@ fn normalize_remote_name(remote: Option<&str>) -> String {
   DEFAULT_REMOTE.to_string()
 }
 
+fn configured_remote(state: &DbState, task_path: &str) -> String {
+  let mut remote = db::project_git_remote_for_path(state, task_path);
+  if remote.is_none() {
+    let resolved = resolve_real_path(Path::new(task_path));
+    remote = db::project_git_remote_for_path(state, resolved.to_string_lossy().as_ref());
+  }
+  normalize_remote_name(remote.as_deref())
+}
+
+#[derive(Clone)]
+pub(crate) struct CommitSigning {
+  enabled: bool,
+  format: Option<String>,
+}
+
+pub(crate) fn commit_signing(app: &tauri::AppHandle) -> CommitSigning {
+  let settings = settings::load_settings(app);
+  let repo = settings.get("repository");
+  CommitSigning {
+    enabled: repo
+      .and_then(|v| v.get("signCommits"))
+      .and_then(|v| v.as_bool())
+      .unwrap_or(false),
+    format: repo
+      .and_then(|v| v.get("signingFormat"))
+      .and_then(|v| v.as_str())
+      .map(|v| v.to_string()),
+  }
+}
+
+pub(crate) fn run_git_commit(cwd: &Path, signing: &CommitSigning, commit_args: &[&str]) -> Result<String, String> {
+  let mut args: Vec<&str> = Vec::new();
+  if signing.enabled {
+    args.extend(["-c", "commit.gpgsign=true"]);
+    match signing.format.as_deref() {
+      Some("ssh") => args.extend(["-c", "gpg.format=ssh"]),
+      Some("gpg") => args.extend(["-c", "gpg.format=openpgp"]),
+      _ => {}
+    }
+  }
+  args.push("commit");
+  if signing.enabled {
+    args.push("-S");
+  }
+  args.extend_from_slice(commit_args);
+  run_git(cwd, &args)
+}
+
+pub(crate) fn is_signing_error(signing: &CommitSigning, message: &str) -> bool {
+  let msg = message.to_lowercase();
+  msg.contains("failed to sign") || (signing.enabled && msg.contains("failed to write commit object"))
+}
+
+pub(crate) fn signing_error_response(output: String) -> Value {
+  json!({
+    "success": false,
+    "code": "SIGNING_FAILED",
+    "error": "Commit signing failed. Check that your GPG or SSH signing key is configured and unlocked.",
+    "output": output
+  })
+}
+
 fn compute_base_ref(remote: Option<&str>, branch: Option<&str>) -> String {
   let remote_name = normalize_remote_name(remote);
   let branch_name = branch
@@ -358,11 +626,21 @@ fn parse_numstat_map(output: &str) -> HashMap<String, (i64, i64)> {
   map
 }
 
-fn count_file_lines(path: &Path) -> i64 {
-  if let Ok(buf) = fs::read(path) {
-    return buf.iter().filter(|b| **b == b'\n').count() as i64;
+/// Counts newlines in an untracked file, reading at most `UNTRACKED_COUNT_MAX_BYTES`.
+/// Returns `None` for files too large to be worth counting; the flag marks a truncated count.
+fn count_file_lines(path: &Path) -> Option<(i64, bool)> {
+  let size = fs::metadata(path).ok()?.len();
+  if size > UNTRACKED_SKIP_BYTES {
+    return None;
   }
-  0
+  let file = fs::File::open(path).ok()?;
+  let mut buf: Vec<u8> = Vec::new();
+  file
+    .take(UNTRACKED_COUNT_MAX_BYTES)
+    .read_to_end(&mut buf)
+    .ok()?;
+  let lines = buf.iter().filter(|b| **b == b'\n').count() as i64;
+  Some((lines, size > UNTRACKED_COUNT_MAX_BYTES))
 }
 
 fn parse_diff_lines(diff: &str) -> Vec<DiffLine> {
@@ -450,7 +728,7 @@ fn to_base36(mut value: u128) -> String {
   String::from_utf8_lossy(&buf).to_string()
 }
 
-fn parse_github_repo(url: &str) -> Option<String> {
+pub(crate) fn parse_github_repo(url: &str) -> Option<String> {
   let trimmed = url.trim().trim_end_matches(".git");
   if trimmed.is_empty() {
     return None;
@@ -502,9 +780,15 @@ fn extract_url(text: &str) -> Option<String> {
 struct GitChange {
   path: String,
   status: String,
-  additions: i64,
+  additions: Option<i64>,
   deletions: i64,
+  #[serde(skip_serializing_if = "std::ops::Not::not")]
+  additions_approx: bool,
   is_staged: bool,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  old_sha: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  new_sha: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -517,7 +801,7 @@ struct DiffLine {
   kind: String,
 }
 
-fn git_get_info_sync(project_path: String) -> Value {
+fn git_get_info_sync(project_path: String, remote_name: String) -> Value {
   let resolved_path = resolve_real_path(Path::new(&project_path));
   let resolved_str = resolved_path.to_string_lossy().to_string();
   let git_path = resolved_path.join(".git");
@@ -526,7 +810,7 @@ fn git_get_info_sync(project_path: String) -> Value {
     return json!({ "isGitRepo": false, "path": resolved_str });
   }
 
-  let remote = run_git(&resolved_path, &["remote", "get-url", DEFAULT_REMOTE])
+  let remote = run_git(&resolved_path, &["remote", "get-url", remote_name.as_str()])
     .ok()
     .map(|s| s.trim().to_string())
     .filter(|s| !s.is_empty());
@@ -537,11 +821,16 @@ fn git_get_info_sync(project_path: String) -> Value {
     .filter(|s| !s.is_empty());
 
   let default_branch = if branch.is_none() {
-    detect_default_branch(&resolved_path, remote.as_deref())
+    detect_default_branch(&resolved_path, Some(remote_name.as_str()))
   } else {
     None
   };
 
+  let head_short = run_git(&resolved_path, &["rev-parse", "--short", "HEAD"])
+    .ok()
+    .map(|s| s.trim().to_string())
+    .filter(|s| !s.is_empty());
+
   let upstream = run_git(
     &resolved_path,
     &["rev-parse", "--abbrev-ref", "--symbolic-full-name", "@{u}"],
@@ -575,14 +864,16 @@ fn git_get_info_sync(project_path: String) -> Value {
     .unwrap_or_else(|| resolved_str.clone());
 
   let base_ref = compute_base_ref(
-    remote.as_deref(),
+    Some(remote_name.as_str()),
     branch.as_deref().or(default_branch.as_deref()),
   );
 
   json!({
+    "success": true,
     "isGitRepo": true,
     "remote": remote,
     "branch": branch,
+    "headShort": head_short,
     "baseRef": base_ref,
     "upstream": upstream,
     "aheadCount": ahead_count,
@@ -593,25 +884,87 @@ fn git_get_info_sync(project_path: String) -> Value {
 }
 
 #[tauri::command]
-pub async fn git_get_info(project_path: String) -> Value {
+pub async fn git_get_info(app: tauri::AppHandle, project_path: String) -> Value {
   let fallback_path = project_path.clone();
-  run_blocking(
-    json!({ "isGitRepo": false, "path": fallback_path, "error": "git_get_info failed" }),
-    move || git_get_info_sync(project_path),
+  run_git_command(
+    json!({
+      "success": false,
+      "isGitRepo": false,
+      "path": fallback_path,
+      "error": "git_get_info failed"
+    }),
+    move || {
+      let state: tauri::State<DbState> = app.state();
+      let remote = configured_remote(&state, &project_path);
+      git_get_info_sync(project_path, remote)
+    },
   )
   .await
 }
 
+/// Maps submodule paths with a moved pointer to their (old, new) short SHAs relative to HEAD.
+fn submodule_pointer_changes(cwd: &Path) -> HashMap<String, (String, String)> {
+  let mut map = HashMap::new();
+  if !cwd.join(".gitmodules").exists() {
+    return map;
+  }
+  let Ok(output) = run_git(cwd, &["diff", "--raw", "--abbrev", "HEAD", "--"]) else {
+    return map;
+  };
+  for line in output.lines() {
+    let Some((meta, path)) = line.trim_start_matches(':').split_once('\t') else {
+      continue;
+    };
+    let fields: Vec<&str> = meta.split_whitespace().collect();
+    if fields.len() < 4 || (fields[0] != "160000" && fields[1] != "160000") {
+      continue;
+    }
+    let path = path.trim_end_matches('\r').to_string();
+    let mut new_sha = fields[3].to_string();
+    // The worktree side is reported as all zeros; read the checked-out commit instead.
+    if new_sha.chars().all(|c| c == '0') {
+      if let Ok(head) = run_git(&cwd.join(&path), &["rev-parse", "--short", "HEAD"]) {
+        new_sha = head.trim().to_string();
+      }
+    }
+    map.insert(path, (fields[2].to_string(), new_sha));
+  }
+  map
+}
+
+type NumstatMap = HashMap<String, (i64, i64)>;
+
+/// Runs the staged and unstaged numstat reads as concurrent git processes alongside `alongside`,
+/// since none of them depend on each other.
+fn parallel_numstats<F, A, T>(run: F, alongside: A) -> (NumstatMap, NumstatMap, T)
+where
+  F: Fn(&'static [&'static str]) -> Option<String> + Sync,
+  A: FnOnce() -> T + Send,
+  T: Send + Default,
+{
+  std::thread::scope(|scope| {
+    let run = &run;
+    let numstat = |args: &'static [&'static str]| {
+      scope.spawn(move || run(args).map(|output| parse_numstat_map(&output)).unwrap_or_default())
+    };
+    let staged = numstat(&["diff", "--numstat", "--cached", "--"]);
+    let unstaged = numstat(&["diff", "--numstat", "--"]);
+    let other = alongside();
+    (
+      staged.join().unwrap_or_default(),
+      unstaged.join().unwrap_or_default(),
+      other,
+    )
+  })
+}
+
 fn git_get_status_sync(task_path: String) -> Value {
   let resolved_path = resolve_real_path(Path::new(&task_path));
   if run_git(&resolved_path, &["rev-parse", "--is-inside-work-tree"]).is_err() {
     return json!({ "success": true, "changes": Vec::<GitChange>::new() });
   }
 
-  let status_output = match run_git(
-    &resolved_path,
-    &["status", "--porcelain", "--untracked-files=all"],
-  ) {
+  let status_output = match run_git(&resolved_path, &["status", "--porcelain", "--untracked-files=all"]) {
     Ok(output) => output,
     Err(err) => return json!({ "success": false, "error": err }),
   };
@@ -620,14 +973,11 @@ fn git_get_status_sync(task_path: String) -> Value {
     return json!({ "success": true, "changes": Vec::<GitChange>::new() });
   }
 
-  let staged_map = run_git(&resolved_path, &["diff", "--numstat", "--cached", "--"])
-    .ok()
-    .map(|output| parse_numstat_map(&output))
-    .unwrap_or_default();
-  let unstaged_map = run_git(&resolved_path, &["diff", "--numstat", "--"])
-    .ok()
-    .map(|output| parse_numstat_map(&output))
-    .unwrap_or_default();
+  let cwd = resolved_path.as_path();
+  let (staged_map, unstaged_map, submodules) = parallel_numstats(
+    |args| run_git(cwd, args).ok(),
+    || submodule_pointer_changes(cwd),
+  );
 
   let mut changes: Vec<GitChange> = Vec::new();
   for raw_line in status_output.lines() {
@@ -660,6 +1010,20 @@ fn git_get_status_sync(task_path: String) -> Value {
     let first = status_code.chars().next().unwrap_or(' ');
     let is_staged = first != ' ' && first != '?';
 
+    if let Some((old_sha, new_sha)) = submodules.get(&normalize_git_path(&file_path)) {
+      changes.push(GitChange {
+        path: file_path,
+        status: "submodule".to_string(),
+        additions: Some(0),
+        deletions: 0,
+        additions_approx: false,
+        is_staged,
+        old_sha: Some(old_sha.clone()),
+        new_sha: Some(new_sha.clone()),
+      });
+      continue;
+    }
+
     let mut additions = 0;
     let mut deletions = 0;
 
@@ -680,10 +1044,18 @@ fn git_get_status_sync(task_path: String) -> Value {
       deletions += *del;
     }
 
-    if additions == 0 && deletions == 0 && status_code.contains('?') {
+    let mut additions = Some(additions);
+    let mut additions_approx = false;
+    if additions == Some(0) && deletions == 0 && status_code.contains('?') {
       let abs_path = resolved_path.join(&file_path);
-      if abs_path.exists() {
-        additions = count_file_lines(&abs_path);
+      if abs_path.is_file() {
+        match count_file_lines(&abs_path) {
+          Some((lines, approx)) => {
+            additions = Some(lines);
+            additions_approx = approx;
+          }
+          None => additions = None,
+        }
       }
     }
 
@@ -692,7 +1064,10 @@ fn git_get_status_sync(task_path: String) -> Value {
       status: status.to_string(),
       additions,
       deletions,
+      additions_approx,
       is_staged,
+      old_sha: None,
+      new_sha: None,
     });
   }
 
@@ -702,15 +1077,121 @@ fn git_get_status_sync(task_path: String) -> Value {
 #[tauri::command]
 pub async fn git_get_status(task_path: String) -> Value {
   let fallback_path = task_path.clone();
-  run_blocking(
+  run_git_command(
     json!({ "success": false, "error": "git_get_status failed", "taskPath": fallback_path }),
     move || git_get_status_sync(task_path),
   )
   .await
 }
 
-fn git_get_file_diff_sync(task_path: String, file_path: String) -> Value {
+const EMPTY_TREE_SHA: &str = "4b825dc642cb6eb9a060e54bf8d69288fbee4904";
+
+// Task metadata rather than user changes: what git_commit_and_push unstages before committing,
+// plus the agent's stream log, which git_get_status hides as well. Left out of the change
+// summary and kept through git_discard_all.
+const TASK_METADATA_EXCLUDES: [&str; 4] = [
+  ":(exclude).emdash",
+  ":(exclude)PLANNING.md",
+  ":(exclude)planning.md",
+  ":(exclude,glob)**/codex-stream.log",
+];
+
+fn git_get_change_summary_sync(task_path: String) -> Value {
+  let resolved_path = resolve_real_path(Path::new(&task_path));
+  if run_git(&resolved_path, &["rev-parse", "--is-inside-work-tree"]).is_err() {
+    return json!({ "success": true, "files": 0, "additions": 0, "deletions": 0 });
+  }
+
+  // A repo without commits has no HEAD; diff against the empty tree instead.
+  let base = if run_git(&resolved_path, &["rev-parse", "--verify", "--quiet", "HEAD"]).is_ok() {
+    "HEAD"
+  } else {
+    EMPTY_TREE_SHA
+  };
+  let mut diff_args = vec!["diff", "--shortstat", base, "--", "."];
+  diff_args.extend(TASK_METADATA_EXCLUDES);
+  let shortstat = match run_git(&resolved_path, &diff_args) {
+    Ok(output) => output,
+    Err(err) => return json!({ "success": false, "error": err }),
+  };
+  let (files, additions, deletions) = parse_shortstat(&shortstat);
+  let mut files = files.unwrap_or(0);
+  let mut additions = additions.unwrap_or(0);
+  let deletions = deletions.unwrap_or(0);
+
+  let mut ls_args = vec!["ls-files", "--others", "--exclude-standard", "-z", "--", "."];
+  ls_args.extend(TASK_METADATA_EXCLUDES);
+  let untracked = run_git(&resolved_path, &ls_args).unwrap_or_default();
+  let mut additions_approx = false;
+  for path in untracked.split('\0').filter(|p| !p.is_empty()) {
+    files += 1;
+    let abs_path = resolved_path.join(path);
+    if !abs_path.is_file() {
+      continue;
+    }
+    match count_file_lines(&abs_path) {
+      Some((lines, approx)) => {
+        additions += lines;
+        additions_approx |= approx;
+      }
+      None => additions_approx = true,
+    }
+  }
+
+  json!({
+    "success": true,
+    "files": files,
+    "additions": additions,
+    "deletions": deletions,
+    "additionsApprox": additions_approx
+  })
+}
+
+#[tauri::command]
+pub async fn git_get_change_summary(task_path: String) -> Value {
+  let fallback_path = task_path.clone();
+  run_git_command(
+    json!({ "success": false, "error": "git_get_change_summary failed", "taskPath": fallback_path }),
+    move || git_get_change_summary_sync(task_path),
+  )
+  .await
+}
+
+fn has_crlf(text: &str) -> bool {
+  text.contains("\r\n")
+}
+
+fn whole_file_diff_lines(content: &str, kind: &str) -> Vec<DiffLine> {
+  content
+    .split('\n')
+    .map(|line| {
+      let line = line.trim_end_matches('\r').to_string();
+      if kind == "del" {
+        DiffLine {
+          left: Some(line),
+          right: None,
+          kind: kind.to_string(),
+        }
+      } else {
+        DiffLine {
+          left: None,
+          right: Some(line),
+          kind: kind.to_string(),
+        }
+      }
+    })
+    .collect()
+}
+
+fn diff_payload(lines: Vec<DiffLine>, crlf: bool) -> Value {
+  json!({ "success": true, "diff": { "lines": lines, "crlf": crlf } })
+}
+
+fn file_diff_lines_sync(task_path: String, file_path: String, mode: &str) -> Value {
   let resolved_path = resolve_real_path(Path::new(&task_path));
+  if mode != "head" {
+    return index_diff_lines(&resolved_path, &file_path, mode);
+  }
   let diff_output = run_git(
     &resolved_path,
     &["diff", "--no-color", "--unified=2000", "HEAD", "--", &file_path],
@@ -719,48 +1200,24 @@ fn git_get_file_diff_sync(task_path: String, file_path: String) -> Value {
   if let Ok(output) = diff_output {
     let lines = parse_diff_lines(&output);
     if !lines.is_empty() {
-      return json!({ "success": true, "diff": { "lines": lines } });
+      return diff_payload(lines, has_crlf(&output));
     }
 
     let abs = resolved_path.join(&file_path);
     if abs.exists() {
       if let Ok(content) = fs::read_to_string(&abs) {
-        let lines = content
-          .split('\n')
-          .map(|line| DiffLine {
-            left: None,
-            right: Some(line.to_string()),
-            kind: "add".to_string(),
-          })
-          .collect::<Vec<DiffLine>>();
-        return json!({ "success": true, "diff": { "lines": lines } });
+        return diff_payload(whole_file_diff_lines(&content, "add"), has_crlf(&content));
       }
     } else if let Ok(prev) = run_git(&resolved_path, &["show", &format!("HEAD:{}", file_path)]) {
-      let lines = prev
-        .split('\n')
-        .map(|line| DiffLine {
-          left: Some(line.to_string()),
-          right: None,
-          kind: "del".to_string(),
-        })
-        .collect::<Vec<DiffLine>>();
-      return json!({ "success": true, "diff": { "lines": lines } });
+      return diff_payload(whole_file_diff_lines(&prev, "del"), has_crlf(&prev));
     }
 
-    return json!({ "success": true, "diff": { "lines": Vec::<DiffLine>::new() } });
+    return diff_payload(Vec::new(), false);
   }
 
   let abs = resolved_path.join(&file_path);
   if let Ok(content) = fs::read_to_string(&abs) {
-    let lines = content
-      .split('\n')
-      .map(|line| DiffLine {
-        left: None,
-        right: Some(line.to_string()),
-        kind: "add".to_string(),
-      })
-      .collect::<Vec<DiffLine>>();
-    return json!({ "success": true, "diff": { "lines": lines } });
+    return diff_payload(whole_file_diff_lines(&content, "add"), has_crlf(&content));
   }
 
   if let Ok(output) = run_git(
@@ -769,109 +1226,936 @@ fn git_get_file_diff_sync(task_path: String, file_path: String) -> Value {
   ) {
     let lines = parse_diff_lines(&output);
     if !lines.is_empty() {
-      return json!({ "success": true, "diff": { "lines": lines } });
+      return diff_payload(lines, has_crlf(&output));
     }
     if let Ok(prev) = run_git(&resolved_path, &["show", &format!("HEAD:{}", file_path)]) {
-      let lines = prev
-        .split('\n')
-        .map(|line| DiffLine {
-          left: Some(line.to_string()),
-          right: None,
-          kind: "del".to_string(),
-        })
-        .collect::<Vec<DiffLine>>();
-      return json!({ "success": true, "diff": { "lines": lines } });
+      return diff_payload(whole_file_diff_lines(&prev, "del"), has_crlf(&prev));
     }
   }
 
-  json!({ "success": true, "diff": { "lines": Vec::<DiffLine>::new() } })
+  diff_payload(Vec::new(), false)
 }
 
-#[tauri::command]
-pub async fn git_get_file_diff(task_path: String, file_path: String) -> Value {
-  let fallback_task_path = task_path.clone();
-  run_blocking(
-    json!({
-      "success": false,
-      "error": "git_get_file_diff failed",
-      "taskPath": fallback_task_path,
-    }),
-    move || git_get_file_diff_sync(task_path, file_path),
-  )
-  .await
+const WORD_DIFF_MAX_CELLS: usize = 1_000_000;
+
+fn tokenize_for_word_diff(line: &str) -> Vec<(usize, usize, String)> {
+  let mut tokens: Vec<(usize, usize, String)> = Vec::new();
+  let mut current = String::new();
+  let mut start = 0usize;
+  let mut offset = 0usize;
+  let mut current_class = 0u8;
+  for ch in line.chars() {
+    let class = if ch.is_alphanumeric() || ch == '_' {
+      1
+    } else if ch.is_whitespace() {
+      2
+    } else {
+      3
+    };
+    if !current.is_empty() && (class != current_class || class == 3) {
+      tokens.push((start, offset, std::mem::take(&mut current)));
+    }
+    if current.is_empty() {
+      start = offset;
+      current_class = class;
+    }
+    current.push(ch);
+    // Offsets are UTF-16 code units so the renderer can slice JS strings directly.
+    offset += ch.len_utf16();
+  }
+  if !current.is_empty() {
+    tokens.push((start, offset, current));
+  }
+  tokens
 }
 
-fn git_stage_file_sync(task_path: String, file_path: String) -> Value {
-  let resolved_path = resolve_real_path(Path::new(&task_path));
-  match run_git(&resolved_path, &["add", "--", &file_path]) {
-    Ok(_) => json!({ "success": true }),
-    Err(err) => json!({ "success": false, "error": err }),
+fn push_range(ranges: &mut Vec<[usize; 2]>, start: usize, end: usize) {
+  if let Some(last) = ranges.last_mut() {
+    if last[1] == start {
+      last[1] = end;
+      return;
+    }
   }
+  ranges.push([start, end]);
 }
 
-#[tauri::command]
-pub async fn git_stage_file(task_path: String, file_path: String) -> Value {
-  let fallback_path = task_path.clone();
-  run_blocking(
-    json!({ "success": false, "error": "git_stage_file failed", "taskPath": fallback_path }),
-    move || git_stage_file_sync(task_path, file_path),
-  )
-  .await
-}
+fn word_diff_ranges(left: &str, right: &str) -> (Vec<[usize; 2]>, Vec<[usize; 2]>) {
+  let a = tokenize_for_word_diff(left);
+  let b = tokenize_for_word_diff(right);
+  let mut left_ranges: Vec<[usize; 2]> = Vec::new();
+  let mut right_ranges: Vec<[usize; 2]> = Vec::new();
 
-fn git_revert_file_sync(task_path: String, file_path: String) -> Value {
-  let resolved_path = resolve_real_path(Path::new(&task_path));
-  if let Ok(staged) = run_git(
-    &resolved_path,
-    &["diff", "--cached", "--name-only", "--", &file_path],
-  ) {
-    if !staged.trim().is_empty() {
-      if let Err(err) = run_git(&resolved_path, &["reset", "HEAD", "--", &file_path]) {
-        return json!({ "success": false, "error": err });
-      }
-      return json!({ "success": true, "action": "unstaged" });
+  if a.len() * b.len() > WORD_DIFF_MAX_CELLS {
+    if let (Some(first), Some(last)) = (a.first(), a.last()) {
+      left_ranges.push([first.0, last.1]);
+    }
+    if let (Some(first), Some(last)) = (b.first(), b.last()) {
+      right_ranges.push([first.0, last.1]);
     }
+    return (left_ranges, right_ranges);
   }
 
-  let exists_in_head = run_git(&resolved_path, &["cat-file", "-e", &format!("HEAD:{}", file_path)])
-    .is_ok();
-
-  if !exists_in_head {
-    let abs = resolved_path.join(&file_path);
-    if abs.exists() {
-      if let Ok(meta) = fs::metadata(&abs) {
-        if meta.is_file() {
-          let _ = fs::remove_file(&abs);
-        }
-      }
+  let mut lcs = vec![vec![0u32; b.len() + 1]; a.len() + 1];
+  for i in (0..a.len()).rev() {
+    for j in (0..b.len()).rev() {
+      lcs[i][j] = if a[i].2 == b[j].2 {
+        lcs[i + 1][j + 1] + 1
+      } else {
+        lcs[i + 1][j].max(lcs[i][j + 1])
+      };
     }
-    return json!({ "success": true, "action": "reverted" });
   }
 
-  match run_git(&resolved_path, &["checkout", "HEAD", "--", &file_path]) {
-    Ok(_) => json!({ "success": true, "action": "reverted" }),
-    Err(err) => json!({ "success": false, "error": err }),
+  let (mut i, mut j) = (0usize, 0usize);
+  while i < a.len() && j < b.len() {
+    if a[i].2 == b[j].2 {
+      i += 1;
+      j += 1;
+    } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+      push_range(&mut left_ranges, a[i].0, a[i].1);
+      i += 1;
+    } else {
+      push_range(&mut right_ranges, b[j].0, b[j].1);
+      j += 1;
+    }
+  }
+  for token in &a[i..] {
+    push_range(&mut left_ranges, token.0, token.1);
   }
+  for token in &b[j..] {
+    push_range(&mut right_ranges, token.0, token.1);
+  }
+  (left_ranges, right_ranges)
 }
 
-#[tauri::command]
-pub async fn git_revert_file(task_path: String, file_path: String) -> Value {
-  let fallback_path = task_path.clone();
-  run_blocking(
-    json!({ "success": false, "error": "git_revert_file failed", "taskPath": fallback_path }),
-    move || git_revert_file_sync(task_path, file_path),
-  )
+fn annotate_word_diff(lines: &mut [Value]) {
+  let mut idx = 0;
+  while idx < lines.len() {
+    if lines[idx].get("type").and_then(|v| v.as_str()) != Some("del") {
+      idx += 1;
+      continue;
+    }
+    let del_start = idx;
+    while idx < lines.len() && lines[idx].get("type").and_then(|v| v.as_str()) == Some("del") {
+      idx += 1;
+    }
+    let add_start = idx;
+    while idx < lines.len() && lines[idx].get("type").and_then(|v| v.as_str()) == Some("add") {
+      idx += 1;
+    }
+    let pairs = (add_start - del_start).min(idx - add_start);
+    for offset in 0..pairs {
+      let left = lines[del_start + offset]
+        .get("left")
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string();
+      let right = lines[add_start + offset]
+        .get("right")
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string();
+      let (left_ranges, right_ranges) = word_diff_ranges(&left, &right);
+      if let Some(obj) = lines[del_start + offset].as_object_mut() {
+        obj.insert("changes".to_string(), json!(left_ranges));
+      }
+      if let Some(obj) = lines[add_start + offset].as_object_mut() {
+        obj.insert("changes".to_string(), json!(right_ranges));
+      }
+    }
+  }
+}
+
+const BULK_DIFF_BATCH: usize = 200;
+
+fn diff_section_path(section: &str) -> Option<String> {
+  let mut old_path = None;
+  for line in section.lines() {
+    let line = line.trim_end_matches('\r');
+    if let Some(path) = line.strip_prefix("+++ b/") {
+      return Some(path.to_string());
+    }
+    if let Some(path) = line.strip_prefix("--- a/") {
+      old_path = Some(path.to_string());
+    }
+    if line.starts_with("@@") {
+      break;
+    }
+  }
+  if old_path.is_some() {
+    return old_path;
+  }
+  let header = section.lines().next()?.strip_prefix("diff --git a/")?;
+  header.split(" b/").last().map(|path| path.to_string())
+}
+
+fn git_get_diffs_sync(task_path: String, file_paths: Vec<String>) -> Value {
+  let resolved_path = resolve_real_path(Path::new(&task_path));
+  let mut requested: Vec<String> = Vec::new();
+  for path in file_paths {
+    let path = path.trim().to_string();
+    if !path.is_empty() && !requested.contains(&path) {
+      requested.push(path);
+    }
+  }
+
+  let mut diffs = serde_json::Map::new();
+  for batch in requested.chunks(BULK_DIFF_BATCH) {
+    let mut args = vec![
+      "-c",
+      "core.quotePath=false",
+      "diff",
+      "--no-color",
+      "--unified=2000",
+      "HEAD",
+      "--",
+    ];
+    args.extend(batch.iter().map(String::as_str));
+    let Ok(output) = run_git(&resolved_path, &args) else {
+      continue;
+    };
+    // Each file's section starts at its `diff --git` header.
+    for section in output.split("\ndiff --git ").enumerate().map(|(idx, part)| {
+      if idx == 0 {
+        part.to_string()
+      } else {
+        format!("diff --git {}", part)
+      }
+    }) {
+      let Some(path) = diff_section_path(&section) else {
+        continue;
+      };
+      if !batch.contains(&path) {
+        continue;
+      }
+      let lines = parse_diff_lines(&section);
+      if !lines.is_empty() {
+        diffs.insert(path, json!({ "lines": lines, "crlf": has_crlf(&section) }));
+      }
+    }
+  }
+
+  // Untracked or otherwise unmatched files take the single-file path and its fallbacks.
+  for path in requested {
+    if diffs.contains_key(&path) {
+      continue;
+    }
+    let result = file_diff_lines_sync(task_path.clone(), path.clone(), "head");
+    if let Some(diff) = result.get("diff") {
+      diffs.insert(path, diff.clone());
+    }
+  }
+
+  json!({ "success": true, "diffs": diffs })
+}
+
+#[tauri::command]
+pub async fn git_get_diffs(task_path: String, file_paths: Vec<String>) -> Value {
+  let fallback_path = task_path.clone();
+  run_git_command(
+    json!({ "success": false, "error": "git_get_diffs failed", "taskPath": fallback_path }),
+    move || git_get_diffs_sync(task_path, file_paths),
+  )
+  .await
+}
+
+const BRANCH_DIFF_MAX_FILE_BYTES: usize = 256 * 1024;
+// Patches are fetched in batches whose old+new blob sizes stay under this budget.
+const BRANCH_DIFF_BATCH_BYTES: u64 = 4 * 1024 * 1024;
+
+/// Sizes of the given blobs from a single `cat-file --batch-check`; unknown shas are omitted.
+fn blob_sizes(cwd: &Path, shas: &[&str]) -> HashMap<String, u64> {
+  let mut sizes = HashMap::new();
+  if shas.is_empty() {
+    return sizes;
+  }
+  let Ok(mut child) = Command::new(resolve_git_bin())
+    .args(["cat-file", "--batch-check"])
+    .current_dir(cwd)
+    .stdin(Stdio::piped())
+    .stdout(Stdio::piped())
+    .stderr(Stdio::null())
+    .spawn()
+  else {
+    return sizes;
+  };
+  let stdout = spawn_pipe_reader(child.stdout.take());
+  if let Some(mut stdin) = child.stdin.take() {
+    let mut input = shas.join("\n");
+    input.push('\n');
+    let _ = stdin.write_all(input.as_bytes());
+  }
+  let output = stdout.join().unwrap_or_default();
+  let _ = child.wait();
+
+  for line in output.lines() {
+    let mut parts = line.split(' ');
+    if let (Some(sha), Some("blob"), Some(size)) = (parts.next(), parts.next(), parts.next()) {
+      if let Ok(size) = size.parse::<u64>() {
+        sizes.insert(sha.to_string(), size);
+      }
+    }
+  }
+  sizes
+}
+
+// Matches git's own `--shortstat` wording so the field reads the same as before.
+fn format_shortstat(files: i64, additions: i64, deletions: i64) -> String {
+  if files == 0 {
+    return String::new();
+  }
+  let plural = |n: i64, one: &str, many: &str| format!("{} {}", n, if n == 1 { one } else { many });
+  let mut parts = vec![plural(files, "file changed", "files changed")];
+  if additions > 0 || deletions == 0 {
+    parts.push(plural(additions, "insertion(+)", "insertions(+)"));
+  }
+  if deletions > 0 || additions == 0 {
+    parts.push(plural(deletions, "deletion(-)", "deletions(-)"));
+  }
+  parts.join(", ")
+}
+
+/// Every file changed on the branch since it forked from `base_ref` (`base...HEAD`), with
+/// structured diffs. Binary files and files over BRANCH_DIFF_MAX_FILE_BYTES carry no lines;
+/// both are decided from numstat and blob sizes before any patch text is read.
+pub(crate) fn branch_diff(cwd: &Path, base_ref: &str) -> Result<Value, String> {
+  let range = format!("{}...HEAD", base_ref);
+  let base_args = [
+    "--literal-pathspecs",
+    "-c",
+    "core.quotePath=false",
+    "diff",
+    "--no-color",
+    "--no-renames",
+  ];
+  let with = |extra: &[&str]| -> Result<String, String> {
+    let mut args: Vec<&str> = base_args.to_vec();
+    args.extend_from_slice(extra);
+    run_git(cwd, &args)
+  };
+
+  let raw = with(&["--raw", "--no-abbrev", &range])?;
+  let numstat = with(&["--numstat", &range])?;
+
+  let mut counts: HashMap<String, (Option<i64>, Option<i64>)> = HashMap::new();
+  for line in numstat.lines() {
+    let mut parts = line.splitn(3, '\t');
+    let (Some(add), Some(del), Some(path)) = (parts.next(), parts.next(), parts.next()) else {
+      continue;
+    };
+    counts.insert(path.to_string(), (add.parse().ok(), del.parse().ok()));
+  }
+
+  // `:<old mode> <new mode> <old sha> <new sha> <status>\t<path>`
+  let mut entries: Vec<(String, char, String, String)> = Vec::new();
+  for line in raw.lines() {
+    let Some((meta, path)) = line.trim_start_matches(':').split_once('\t') else {
+      continue;
+    };
+    let fields: Vec<&str> = meta.split_whitespace().collect();
+    if fields.len() < 5 {
+      continue;
+    }
+    let code = fields[4].chars().next().unwrap_or('M');
+    entries.push((path.to_string(), code, fields[2].to_string(), fields[3].to_string()));
+  }
+
+  let shas: Vec<&str> = entries
+    .iter()
+    .flat_map(|(_, _, old, new)| [old.as_str(), new.as_str()])
+    .filter(|sha| !sha.chars().all(|c| c == '0'))
+    .collect();
+  let sizes = blob_sizes(cwd, &shas);
+  let size_of = |sha: &str| sizes.get(sha).copied().unwrap_or(0);
+
+  let mut wanted: Vec<(&str, u64)> = Vec::new();
+  let mut flags: HashMap<&str, (bool, bool)> = HashMap::new();
+  for (path, _, old, new) in &entries {
+    let (additions, deletions) = counts.get(path).cloned().unwrap_or((None, None));
+    let binary = additions.is_none() && deletions.is_none() && counts.contains_key(path);
+    let bytes = size_of(old) + size_of(new);
+    let too_large = !binary && bytes > BRANCH_DIFF_MAX_FILE_BYTES as u64;
+    flags.insert(path.as_str(), (binary, too_large));
+    if !binary && !too_large {
+      wanted.push((path.as_str(), bytes));
+    }
+  }
+
+  let mut sections: HashMap<String, String> = HashMap::new();
+  let mut fetch_batch = |batch: &[&str]| -> Result<(), String> {
+    if batch.is_empty() {
+      return Ok(());
+    }
+    let mut extra = vec!["--unified=2000", range.as_str(), "--"];
+    extra.extend_from_slice(batch);
+    let patch = with(&extra)?;
+    for (idx, part) in patch.split("\ndiff --git ").enumerate() {
+      let section = if idx == 0 {
+        part.to_string()
+      } else {
+        format!("diff --git {}", part)
+      };
+      if let Some(path) = diff_section_path(&section) {
+        sections.insert(path, section);
+      }
+    }
+    Ok(())
+  };
+  let mut batch: Vec<&str> = Vec::new();
+  let mut batch_bytes = 0u64;
+  for (path, bytes) in wanted {
+    if !batch.is_empty()
+      && (batch_bytes + bytes > BRANCH_DIFF_BATCH_BYTES || batch.len() >= BULK_DIFF_BATCH)
+    {
+      fetch_batch(&batch)?;
+      batch.clear();
+      batch_bytes = 0;
+    }
+    batch.push(path);
+    batch_bytes += bytes;
+  }
+  fetch_batch(&batch)?;
+
+  let mut total_additions = 0;
+  let mut total_deletions = 0;
+  let mut files: Vec<Value> = Vec::new();
+  for (path, code, _, _) in &entries {
+    let status = match code {
+      'A' => "added",
+      'D' => "deleted",
+      'T' => "typechange",
+      _ => "modified",
+    };
+    let (additions, deletions) = counts.get(path).cloned().unwrap_or((None, None));
+    total_additions += additions.unwrap_or(0);
+    total_deletions += deletions.unwrap_or(0);
+    let (binary, too_large) = flags.get(path.as_str()).copied().unwrap_or((false, false));
+    let diff = match sections.get(path) {
+      Some(section) if !binary && !too_large => {
+        json!({ "lines": parse_diff_lines(section), "crlf": has_crlf(section) })
+      }
+      _ => Value::Null,
+    };
+    files.push(json!({
+      "path": path,
+      "status": status,
+      "additions": additions.unwrap_or(0),
+      "deletions": deletions.unwrap_or(0),
+      "binary": binary,
+      "tooLarge": too_large,
+      "diff": diff,
+    }));
+  }
+
+  let files_changed = entries.len() as i64;
+  Ok(json!({
+    "baseRef": base_ref,
+    "summary": {
+      "files": files_changed,
+      "additions": total_additions,
+      "deletions": total_deletions,
+    },
+    "shortstat": format_shortstat(files_changed, total_additions, total_deletions),
+    "files": files,
+  }))
+}
+
+/// Diffs one side of the index: "staged" is HEAD vs index, "working" is index vs worktree.
+fn index_diff_lines(cwd: &Path, file_path: &str, mode: &str) -> Value {
+  let mut args = vec!["diff", "--no-color", "--unified=2000"];
+  if mode == "staged" {
+    args.push("--cached");
+  }
+  args.extend(["--", file_path]);
+  let output = match run_git(cwd, &args) {
+    Ok(output) => output,
+    Err(err) => return json!({ "success": false, "error": err }),
+  };
+  let lines = parse_diff_lines(&output);
+  if !lines.is_empty() {
+    return diff_payload(lines, has_crlf(&output));
+  }
+  if mode == "working" {
+    let tracked = run_git(cwd, &["ls-files", "--", file_path])
+      .map(|out| !out.trim().is_empty())
+      .unwrap_or(true);
+    if !tracked {
+      if let Ok(content) = fs::read_to_string(cwd.join(file_path)) {
+        return diff_payload(whole_file_diff_lines(&content, "add"), has_crlf(&content));
+      }
+    }
+  }
+  diff_payload(Vec::new(), false)
+}
+
+fn git_get_file_diff_sync(
+  task_path: String,
+  file_path: String,
+  word_diff: bool,
+  mode: &str,
+) -> Value {
+  let mut result = file_diff_lines_sync(task_path, file_path, mode);
+  if word_diff {
+    if let Some(lines) = result
+      .get_mut("diff")
+      .and_then(|diff| diff.get_mut("lines"))
+      .and_then(|lines| lines.as_array_mut())
+    {
+      annotate_word_diff(lines);
+    }
+  }
+  result
+}
+
+fn parse_blame_porcelain(output: &str) -> Vec<Value> {
+  let mut entries = Vec::new();
+  let mut current: Option<(String, u64)> = None;
+  let mut author = String::new();
+  let mut author_time: Option<i64> = None;
+  let mut summary = String::new();
+  for line in output.lines() {
+    if line.starts_with('\t') {
+      if let Some((commit, line_no)) = current.take() {
+        entries.push(json!({
+          "lineNo": line_no,
+          "commit": commit,
+          "author": author,
+          "authorTime": author_time,
+          "summary": summary,
+          // Lines not yet committed are attributed to the all-zeros commit.
+          "uncommitted": commit.chars().all(|c| c == '0'),
+        }));
+      }
+      continue;
+    }
+    if current.is_none() {
+      let mut parts = line.split(' ');
+      let commit = parts.next().unwrap_or("");
+      let final_line = parts.nth(1).and_then(|v| v.parse::<u64>().ok());
+      let is_sha = commit.len() >= 40 && commit.chars().all(|c| c.is_ascii_hexdigit());
+      if let (true, Some(line_no)) = (is_sha, final_line) {
+        current = Some((commit.to_string(), line_no));
+        author.clear();
+        author_time = None;
+        summary.clear();
+      }
+      continue;
+    }
+    if let Some(value) = line.strip_prefix("author ") {
+      author = value.to_string();
+    } else if let Some(value) = line.strip_prefix("author-time ") {
+      author_time = value.trim().parse::<i64>().ok();
+    } else if let Some(value) = line.strip_prefix("summary ") {
+      summary = value.to_string();
+    }
+  }
+  entries
+}
+
+fn git_blame_sync(
+  task_path: String,
+  file_path: String,
+  start_line: Option<u32>,
+  end_line: Option<u32>,
+) -> Value {
+  let resolved_path = resolve_real_path(Path::new(&task_path));
+  let rel = Path::new(file_path.trim());
+  if file_path.trim().is_empty()
+    || rel.is_absolute()
+    || rel.components().any(|c| matches!(c, std::path::Component::ParentDir))
+  {
+    return json!({ "success": false, "error": "Invalid file path" });
+  }
+  if !resolved_path.join(rel).is_file() {
+    return json!({ "success": false, "error": format!("File not found: {}", file_path) });
+  }
+  if start_line == Some(0) || end_line == Some(0) {
+    return json!({ "success": false, "error": "Line numbers start at 1" });
+  }
+  if let (Some(start), Some(end)) = (start_line, end_line) {
+    if end < start {
+      return json!({ "success": false, "error": "endLine must not be before startLine" });
+    }
+  }
+
+  let range = match (start_line, end_line) {
+    (None, None) => None,
+    (start, end) => Some(format!(
+      "{},{}",
+      start.unwrap_or(1),
+      end.map(|v| v.to_string()).unwrap_or_default()
+    )),
+  };
+  let mut args = vec!["blame", "--line-porcelain"];
+  if let Some(range) = range.as_deref() {
+    args.push("-L");
+    args.push(range);
+  }
+  let rel_str = rel.to_string_lossy();
+  args.push("--");
+  args.push(&rel_str);
+
+  match run_git(&resolved_path, &args) {
+    Ok(output) => json!({ "success": true, "lines": parse_blame_porcelain(&output) }),
+    Err(err) => json!({ "success": false, "error": err }),
+  }
+}
+
+#[tauri::command]
+pub async fn git_blame(
+  task_path: String,
+  file_path: String,
+  start_line: Option<u32>,
+  end_line: Option<u32>,
+) -> Value {
+  let fallback_task_path = task_path.clone();
+  run_git_command(
+    json!({ "success": false, "error": "git_blame failed", "taskPath": fallback_task_path }),
+    move || git_blame_sync(task_path, file_path, start_line, end_line),
+  )
+  .await
+}
+
+#[tauri::command]
+pub async fn git_get_file_diff(
+  task_path: String,
+  file_path: String,
+  word_diff: Option<bool>,
+  mode: Option<String>,
+) -> Value {
+  let fallback_task_path = task_path.clone();
+  run_git_command(
+    json!({
+      "success": false,
+      "error": "git_get_file_diff failed",
+      "taskPath": fallback_task_path,
+    }),
+    move || {
+      let mode = mode.as_deref().unwrap_or("head");
+      if !matches!(mode, "head" | "staged" | "working") {
+        return json!({ "success": false, "error": format!("Unknown diff mode: {}", mode) });
+      }
+      git_get_file_diff_sync(task_path, file_path, word_diff.unwrap_or(false), mode)
+    },
+  )
+  .await
+}
+
+fn is_valid_commit_sha(value: &str) -> bool {
+  (4..=64).contains(&value.len()) && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+struct CommitFileDiff {
+  path: String,
+  old_path: Option<String>,
+  binary: bool,
+  body: String,
+}
+
+// Splits `git show` output into one entry per file. Extended headers (modes, renames,
+// index lines) are consumed here, so `body` starts at the first hunk.
+fn split_commit_diff(output: &str) -> Vec<CommitFileDiff> {
+  let strip = |value: &str, prefix: &str| value.strip_prefix(prefix).map(str::to_string);
+  let mut files: Vec<CommitFileDiff> = Vec::new();
+  let mut in_hunks = false;
+  for raw in output.lines() {
+    let line = raw.trim_end_matches('\r');
+    if let Some(header) = line.strip_prefix("diff --git ") {
+      // `a/<path> b/<path>`; only unambiguous when both sides match (no rename).
+      let half = header.len() / 2;
+      let path = match (header.get(..half), header.get(half + 1..)) {
+        (Some(old), Some(new)) if old.get(2..) == new.get(2..) => {
+          new.get(2..).unwrap_or("").to_string()
+        }
+        _ => String::new(),
+      };
+      files.push(CommitFileDiff { path, old_path: None, binary: false, body: String::new() });
+      in_hunks = false;
+      continue;
+    }
+    let Some(file) = files.last_mut() else {
+      continue;
+    };
+    if line.starts_with("@@") {
+      in_hunks = true;
+    }
+    if in_hunks {
+      file.body.push_str(line);
+      file.body.push('\n');
+    } else if let Some(path) = strip(line, "rename to ") {
+      file.path = path;
+    } else if let Some(path) = strip(line, "rename from ") {
+      file.old_path = Some(path);
+    } else if let Some(path) = strip(line, "+++ b/") {
+      // git appends a tab to ---/+++ paths that contain spaces.
+      file.path = path.trim_end_matches('\t').to_string();
+    } else if let Some(path) = strip(line, "--- a/") {
+      if file.path.is_empty() {
+        file.path = path.trim_end_matches('\t').to_string();
+      }
+    } else if line.starts_with("Binary files ") {
+      file.binary = true;
+    }
+  }
+  files
+}
+
+fn git_get_commit_diff_sync(task_path: String, commit_sha: String, file_path: Option<String>) -> Value {
+  let resolved_path = resolve_real_path(Path::new(&task_path));
+  let sha = commit_sha.trim().to_string();
+  if !is_valid_commit_sha(&sha) {
+    return json!({ "success": false, "error": "Invalid commit sha" });
+  }
+  if run_git(&resolved_path, &["cat-file", "-e", &format!("{}^{{commit}}", sha)]).is_err() {
+    return json!({ "success": false, "error": format!("Commit {} not found", sha) });
+  }
+
+  let file_path = file_path.map(|f| f.trim().to_string()).filter(|f| !f.is_empty());
+  let mut diff_args = vec![
+    "-c",
+    "core.quotePath=false",
+    "show",
+    "--no-color",
+    "--format=",
+    "--unified=2000",
+    sha.as_str(),
+  ];
+  let mut stat_args = vec!["show", "--shortstat", "--format=", sha.as_str()];
+  if let Some(file) = file_path.as_deref() {
+    diff_args.extend(["--", file]);
+    stat_args.extend(["--", file]);
+  }
+
+  let output = match run_git(&resolved_path, &diff_args) {
+    Ok(output) => output,
+    Err(err) => return json!({ "success": false, "error": err }),
+  };
+  let diffs: Vec<Value> = split_commit_diff(&output)
+    .into_iter()
+    .map(|file| {
+      json!({
+        "path": file.path,
+        "oldPath": file.old_path,
+        "binary": file.binary,
+        "lines": parse_diff_lines(&file.body)
+      })
+    })
+    .collect();
+  let (files, additions, deletions) = run_git(&resolved_path, &stat_args)
+    .map(|stat| shortstat_counts(&stat))
+    .unwrap_or((0, 0, 0));
+
+  json!({
+    "success": true,
+    "sha": sha,
+    "files": diffs,
+    "summary": { "files": files, "additions": additions, "deletions": deletions }
+  })
+}
+
+#[tauri::command]
+pub async fn git_get_commit_diff(
+  task_path: String,
+  commit_sha: String,
+  file_path: Option<String>,
+) -> Value {
+  let fallback_path = task_path.clone();
+  run_git_command(
+    json!({ "success": false, "error": "git_get_commit_diff failed", "taskPath": fallback_path }),
+    move || git_get_commit_diff_sync(task_path, commit_sha, file_path),
+  )
+  .await
+}
+
+fn git_stage_file_sync(task_path: String, file_path: String) -> Value {
+  let resolved_path = resolve_real_path(Path::new(&task_path));
+  match run_git(&resolved_path, &["add", "--", &file_path]) {
+    Ok(_) => json!({ "success": true }),
+    Err(err) => json!({ "success": false, "error": err }),
+  }
+}
+
+#[tauri::command]
+pub async fn git_stage_file(task_path: String, file_path: String) -> Value {
+  let fallback_path = task_path.clone();
+  run_git_command(
+    json!({ "success": false, "error": "git_stage_file failed", "taskPath": fallback_path }),
+    move || git_stage_file_sync(task_path, file_path),
+  )
+  .await
+}
+
+fn git_stage_all_sync(task_path: String) -> Value {
+  let resolved_path = resolve_real_path(Path::new(&task_path));
+  if let Err(err) = run_git(&resolved_path, &["add", "-A"]) {
+    return json!({ "success": false, "error": err });
+  }
+  git_get_status_sync(task_path)
+}
+
+#[tauri::command]
+pub async fn git_stage_all(task_path: String) -> Value {
+  let fallback_path = task_path.clone();
+  run_git_command(
+    json!({ "success": false, "error": "git_stage_all failed", "taskPath": fallback_path }),
+    move || git_stage_all_sync(task_path),
+  )
+  .await
+}
+
+fn git_unstage_all_sync(task_path: String) -> Value {
+  let resolved_path = resolve_real_path(Path::new(&task_path));
+  let has_head = run_git(&resolved_path, &["rev-parse", "--verify", "--quiet", "HEAD"]).is_ok();
+  // An unborn branch has no HEAD to reset to, so drop everything from the index instead.
+  let result = if has_head {
+    run_git(&resolved_path, &["reset", "-q", "HEAD"])
+  } else {
+    run_git(
+      &resolved_path,
+      &["rm", "-r", "--cached", "--quiet", "--ignore-unmatch", "--", "."],
+    )
+  };
+  if let Err(err) = result {
+    return json!({ "success": false, "error": err });
+  }
+  git_get_status_sync(task_path)
+}
+
+#[tauri::command]
+pub async fn git_unstage_all(task_path: String) -> Value {
+  let fallback_path = task_path.clone();
+  run_git_command(
+    json!({ "success": false, "error": "git_unstage_all failed", "taskPath": fallback_path }),
+    move || git_unstage_all_sync(task_path),
+  )
+  .await
+}
+
+fn git_revert_file_sync(task_path: String, file_path: String) -> Value {
+  let resolved_path = resolve_real_path(Path::new(&task_path));
+  if let Ok(staged) = run_git(
+    &resolved_path,
+    &["diff", "--cached", "--name-only", "--", &file_path],
+  ) {
+    if !staged.trim().is_empty() {
+      if let Err(err) = run_git(&resolved_path, &["reset", "HEAD", "--", &file_path]) {
+        return json!({ "success": false, "error": err });
+      }
+      return json!({ "success": true, "action": "unstaged" });
+    }
+  }
+
+  let exists_in_head = run_git(&resolved_path, &["cat-file", "-e", &format!("HEAD:{}", file_path)])
+    .is_ok();
+
+  if !exists_in_head {
+    let abs = resolved_path.join(&file_path);
+    if abs.exists() {
+      if let Ok(meta) = fs::metadata(&abs) {
+        if meta.is_file() {
+          let _ = fs::remove_file(&abs);
+        }
+      }
+    }
+    return json!({ "success": true, "action": "reverted" });
+  }
+
+  match run_git(&resolved_path, &["checkout", "HEAD", "--", &file_path]) {
+    Ok(_) => json!({ "success": true, "action": "reverted" }),
+    Err(err) => json!({ "success": false, "error": err }),
+  }
+}
+
+#[tauri::command]
+pub async fn git_revert_file(task_path: String, file_path: String) -> Value {
+  let fallback_path = task_path.clone();
+  run_git_command(
+    json!({ "success": false, "error": "git_revert_file failed", "taskPath": fallback_path }),
+    move || git_revert_file_sync(task_path, file_path),
+  )
+  .await
+}
+
+fn git_discard_all_sync(task_path: String, confirm_token: String) -> Value {
+  let resolved_path = resolve_real_path(Path::new(&task_path));
+  if let Err(err) = run_git(&resolved_path, &["rev-parse", "--is-inside-work-tree"]) {
+    return json!({ "success": false, "error": err });
+  }
+
+  // The caller must echo back the current HEAD short sha, so a stale or accidental call
+  // (e.g. after HEAD moved) is refused instead of wiping the worktree.
+  let head = match run_git(&resolved_path, &["rev-parse", "--short", "HEAD"]) {
+    Ok(out) => out.trim().to_string(),
+    Err(err) => return json!({ "success": false, "error": err }),
+  };
+  let token = confirm_token.trim();
+  if token.is_empty() || !token.eq_ignore_ascii_case(&head) {
+    return json!({
+      "success": false,
+      "code": "CONFIRM_TOKEN_MISMATCH",
+      "error": "Confirmation token does not match the current HEAD; refresh and try again"
+    });
+  }
+
+  let with_pathspec = |args: &[&'static str]| {
+    let mut full: Vec<&str> = args.to_vec();
+    full.extend(["--", "."]);
+    full.extend(TASK_METADATA_EXCLUDES);
+    full
+  };
+
+  let mut discarded: Vec<String> = Vec::new();
+  match run_git(
+    &resolved_path,
+    &with_pathspec(&["diff", "--name-only", "--no-renames", "HEAD"]),
+  ) {
+    Ok(out) => discarded.extend(out.lines().filter(|l| !l.is_empty()).map(str::to_string)),
+    Err(err) => return json!({ "success": false, "error": err }),
+  }
+  match run_git(
+    &resolved_path,
+    &with_pathspec(&["ls-files", "--others", "--exclude-standard", "-z"]),
+  ) {
+    Ok(out) => discarded.extend(out.split('\0').filter(|p| !p.is_empty()).map(str::to_string)),
+    Err(err) => return json!({ "success": false, "error": err }),
+  }
+  discarded.sort();
+  discarded.dedup();
+
+  if discarded.is_empty() {
+    return json!({ "success": true, "discarded": [], "head": head });
+  }
+
+  // Unstage first so newly added files become untracked and are picked up by clean.
+  for args in [&["reset", "-q"][..], &["checkout"][..], &["clean", "-fdq"][..]] {
+    if let Err(err) = run_git(&resolved_path, &with_pathspec(args)) {
+      return json!({ "success": false, "error": err, "head": head });
+    }
+  }
+
+  json!({ "success": true, "discarded": discarded, "head": head })
+}
+
+#[tauri::command]
+pub async fn git_discard_all(task_path: String, confirm_token: String) -> Value {
+  let fallback_path = task_path.clone();
+  run_git_command(
+    json!({ "success": false, "error": "git_discard_all failed", "taskPath": fallback_path }),
+    move || git_discard_all_sync(task_path, confirm_token),
+  )
   .await
 }
 
 fn git_commit_and_push_sync(
   task_path: String,
+  remote: String,
+  signing: CommitSigning,
   commit_message: Option<String>,
+  generate_message_with: Option<&tauri::AppHandle>,
   create_branch_if_on_default: Option<bool>,
   branch_prefix: Option<String>,
 ) -> Value {
   let resolved_path = resolve_real_path(Path::new(&task_path));
-  let commit_message = commit_message.unwrap_or_else(|| "chore: apply task changes".to_string());
   let create_branch_if_on_default = create_branch_if_on_default.unwrap_or(true);
   let branch_prefix = branch_prefix.unwrap_or_else(|| "orch".to_string());
 
@@ -894,7 +2178,7 @@ fn git_commit_and_push_sync(
     if !trimmed.is_empty() {
       default_branch = trimmed.to_string();
     }
-  } else if let Some(db) = detect_default_branch(&resolved_path, Some(DEFAULT_REMOTE)) {
+  } else if let Some(db) = detect_default_branch(&resolved_path, Some(remote.as_str())) {
     default_branch = db;
   }
 
@@ -929,7 +2213,20 @@ fn git_commit_and_push_sync(
 
     staged_files = read_staged_files(&resolved_path);
     if !staged_files.is_empty() {
-      if let Err(err) = run_git(&resolved_path, &["commit", "-m", commit_message.as_str()]) {
+      let commit_message = match commit_message {
+        Some(message) => message,
+        None => generate_message_with
+          .and_then(|app| {
+            with_cancellable(app, &task_path, None, |ctx| {
+              generate_commit_message_sync(app, &task_path, ctx).ok()
+            })
+          })
+          .unwrap_or_else(|| "chore: apply task changes".to_string()),
+      };
+      if let Err(err) = run_git_commit(&resolved_path, &signing, &["-m", commit_message.as_str()]) {
+        if is_signing_error(&signing, &err) {
+          return signing_error_response(err);
+        }
         if !err.to_lowercase().contains("nothing to commit") {
           return json!({ "success": false, "error": err });
         }
@@ -948,7 +2245,7 @@ fn git_commit_and_push_sync(
     };
     if let Err(err2) = run_git(
       &resolved_path,
-      &["push", "--set-upstream", "origin", branch.as_str()],
+      &["push", "--set-upstream", remote.as_str(), branch.as_str()],
     ) {
       return json!({ "success": false, "error": format!("{}\n{}", err, err2) });
     }
@@ -964,24 +2261,39 @@ fn git_commit_and_push_sync(
 
 #[tauri::command]
 pub async fn git_commit_and_push(
+  app: tauri::AppHandle,
   task_path: String,
   commit_message: Option<String>,
   create_branch_if_on_default: Option<bool>,
   branch_prefix: Option<String>,
+  generate_message: Option<bool>,
 ) -> Value {
   let fallback_path = task_path.clone();
-  run_blocking(
+  run_git_command(
     json!({
       "success": false,
       "error": "git_commit_and_push failed",
       "taskPath": fallback_path,
     }),
-    move || git_commit_and_push_sync(task_path, commit_message, create_branch_if_on_default, branch_prefix),
+    move || {
+      let state: tauri::State<DbState> = app.state();
+      let remote = configured_remote(&state, &task_path);
+      let signing = commit_signing(&app);
+      git_commit_and_push_sync(
+        task_path,
+        remote,
+        signing,
+        commit_message,
+        generate_message.unwrap_or(false).then_some(&app),
+        create_branch_if_on_default,
+        branch_prefix,
+      )
+    },
   )
   .await
 }
 
-fn git_get_branch_status_sync(task_path: String) -> Value {
+fn git_get_branch_status_sync(task_path: String, remote: String) -> Value {
   let resolved_path = resolve_real_path(Path::new(&task_path));
   if let Err(err) = run_git(&resolved_path, &["rev-parse", "--is-inside-work-tree"]) {
     return json!({ "success": false, "error": err });
@@ -1004,7 +2316,7 @@ fn git_get_branch_status_sync(task_path: String) -> Value {
     }
   } else if let Ok(output) = run_git(
     &resolved_path,
-    &["symbolic-ref", "--short", "refs/remotes/origin/HEAD"],
+    &["symbolic-ref", "--short", &format!("refs/remotes/{}/HEAD", remote)],
   ) {
     if let Some(last) = output.trim().split('/').last() {
       if !last.is_empty() {
@@ -1021,7 +2333,7 @@ fn git_get_branch_status_sync(task_path: String) -> Value {
       "rev-list",
       "--left-right",
       "--count",
-      &format!("origin/{}...HEAD", default_branch),
+      &format!("{}/{}...HEAD", remote, default_branch),
     ],
   ) {
     let parts: Vec<&str> = output.trim().split_whitespace().collect();
@@ -1055,15 +2367,90 @@ fn git_get_branch_status_sync(task_path: String) -> Value {
 }
 
 #[tauri::command]
-pub async fn git_get_branch_status(task_path: String) -> Value {
+pub async fn git_get_branch_status(app: tauri::AppHandle, task_path: String) -> Value {
   let fallback_path = task_path.clone();
-  run_blocking(
+  run_git_command(
     json!({
       "success": false,
       "error": "git_get_branch_status failed",
       "taskPath": fallback_path,
     }),
-    move || git_get_branch_status_sync(task_path),
+    move || {
+      let state: tauri::State<DbState> = app.state();
+      let remote = configured_remote(&state, &task_path);
+      git_get_branch_status_sync(task_path, remote)
+    },
+  )
+  .await
+}
+
+fn is_no_upstream_error(message: &str) -> bool {
+  let msg = message.to_lowercase();
+  msg.contains("no tracking information") || msg.contains("has no upstream branch")
+}
+
+fn git_pull_sync(task_path: String, remote: String, rebase: Option<bool>) -> Value {
+  let resolved_path = resolve_real_path(Path::new(&task_path));
+  if let Err(err) = run_git(&resolved_path, &["rev-parse", "--is-inside-work-tree"]) {
+    return json!({ "success": false, "error": err });
+  }
+
+  let branch = run_git(&resolved_path, &["branch", "--show-current"])
+    .unwrap_or_default()
+    .trim()
+    .to_string();
+  let rebase = rebase.unwrap_or(false);
+  let args: &[&str] = if rebase {
+    &["pull", "--rebase"]
+  } else {
+    &["pull", "--no-rebase"]
+  };
+
+  match run_git(&resolved_path, args) {
+    Ok(output) => {
+      let output = output.trim().to_string();
+      let updated = !output.to_lowercase().contains("already up to date");
+      json!({ "success": true, "branch": branch, "updated": updated, "output": output })
+    }
+    Err(err) => {
+      if is_no_upstream_error(&err) {
+        return json!({
+          "success": false,
+          "code": "NO_UPSTREAM",
+          "error": format!(
+            "Branch {} has no upstream. Push it first with `git push --set-upstream {} {}`.",
+            branch, remote, branch
+          ),
+          "output": err
+        });
+      }
+      let conflicted = run_git(&resolved_path, &["diff", "--name-only", "--diff-filter=U"])
+        .map(|out| parse_output_lines(&out))
+        .unwrap_or_default();
+      if !conflicted.is_empty() {
+        return json!({
+          "success": false,
+          "conflicted": true,
+          "rebase": rebase,
+          "files": conflicted,
+          "error": err
+        });
+      }
+      json!({ "success": false, "error": err })
+    }
+  }
+}
+
+#[tauri::command]
+pub async fn git_pull(app: tauri::AppHandle, task_path: String, rebase: Option<bool>) -> Value {
+  let fallback_path = task_path.clone();
+  run_git_command(
+    json!({ "success": false, "error": "git_pull failed", "taskPath": fallback_path }),
+    move || {
+      let state: tauri::State<DbState> = app.state();
+      let remote = configured_remote(&state, &task_path);
+      git_pull_sync(task_path, remote, rebase)
+    },
   )
   .await
 }
@@ -1112,7 +2499,64 @@ fn summarize_status_checks(data: &Value) -> Option<Value> {
   }))
 }
 
-fn git_get_pr_status_sync(task_path: String) -> Value {
+// gh returns the full review history; keep each reviewer's latest review. A later plain
+// comment doesn't undo an approval or change request on GitHub, so it doesn't here either.
+fn summarize_reviewers(data: &Value) -> Vec<Value> {
+  let Some(reviews) = data.get("reviews").and_then(|v| v.as_array()) else {
+    return Vec::new();
+  };
+
+  let mut order: Vec<String> = Vec::new();
+  let mut latest: HashMap<String, (String, Value)> = HashMap::new();
+  for review in reviews {
+    let Some(login) = review
+      .get("author")
+      .and_then(|a| a.get("login"))
+      .and_then(|v| v.as_str())
+      .map(str::trim)
+      .filter(|s| !s.is_empty())
+    else {
+      continue;
+    };
+    let state = review
+      .get("state")
+      .and_then(|v| v.as_str())
+      .unwrap_or("")
+      .trim()
+      .to_ascii_uppercase();
+    if state.is_empty() || state == "PENDING" {
+      continue;
+    }
+    let submitted_at = review.get("submittedAt").cloned().unwrap_or(Value::Null);
+
+    match latest.get(login) {
+      Some((prev_state, prev_at)) => {
+        let is_newer = match (prev_at.as_str(), submitted_at.as_str()) {
+          (Some(prev), Some(next)) => next >= prev,
+          _ => true,
+        };
+        let keeps_decision = state == "COMMENTED" && prev_state != "COMMENTED";
+        if is_newer && !keeps_decision {
+          latest.insert(login.to_string(), (state, submitted_at));
+        }
+      }
+      None => {
+        order.push(login.to_string());
+        latest.insert(login.to_string(), (state, submitted_at));
+      }
+    }
+  }
+
+  order
+    .into_iter()
+    .filter_map(|login| {
+      let (state, submitted_at) = latest.remove(&login)?;
+      Some(json!({ "login": login, "state": state, "submittedAt": submitted_at }))
+    })
+    .collect()
+}
+
+fn git_get_pr_status_sync(task_path: String, remote: String) -> Value {
   let resolved_path = resolve_real_path(Path::new(&task_path));
   if let Err(err) = run_git(&resolved_path, &["rev-parse", "--is-inside-work-tree"]) {
     return json!({ "success": false, "error": err });
@@ -1206,7 +2650,7 @@ fn git_get_pr_status_sync(task_path: String) -> Value {
     let target_ref = if base_ref.is_empty() {
       None
     } else {
-      Some(format!("origin/{}", base_ref))
+      Some(format!("{}/{}", remote, base_ref))
     };
     let diff_arg = if let Some(target) = target_ref {
       format!("{}...HEAD", target)
@@ -1238,38 +2682,267 @@ fn git_get_pr_status_sync(task_path: String) -> Value {
     }
   }
 
-  let checks_summary = summarize_status_checks(&data);
-  let comments_count = data
-    .get("comments")
-    .and_then(|v| v.as_array())
-    .map(|arr| arr.len() as i64)
-    .unwrap_or(0);
-  let review_count = data
-    .get("reviews")
+  let checks_summary = summarize_status_checks(&data);
+  let comments_count = data
+    .get("comments")
+    .and_then(|v| v.as_array())
+    .map(|arr| arr.len() as i64)
+    .unwrap_or(0);
+  let review_count = data
+    .get("reviews")
+    .and_then(|v| v.as_array())
+    .map(|arr| arr.len() as i64)
+    .unwrap_or(0);
+  let reviewers = summarize_reviewers(&data);
+
+  if let Some(obj) = data.as_object_mut() {
+    if let Some(summary) = checks_summary {
+      obj.insert("checksSummary".to_string(), summary);
+    }
+    obj.insert("commentsCount".to_string(), json!(comments_count));
+    obj.insert("reviewCount".to_string(), json!(review_count));
+    obj.insert("reviewers".to_string(), json!(reviewers));
+    obj.remove("comments");
+    obj.remove("reviews");
+    obj.remove("statusCheckRollup");
+  }
+
+  json!({ "success": true, "pr": data })
+}
+
+#[tauri::command]
+pub async fn git_get_pr_status(app: tauri::AppHandle, task_path: String) -> Value {
+  let fallback_path = task_path.clone();
+  run_git_command(
+    json!({ "success": false, "error": "git_get_pr_status failed", "taskPath": fallback_path }),
+    move || {
+      let state: tauri::State<DbState> = app.state();
+      let remote = configured_remote(&state, &task_path);
+      git_get_pr_status_sync(task_path, remote)
+    },
+  )
+  .await
+}
+
+fn set_pr_draft_sync(task_path: String, remote: String, draft: bool) -> Value {
+  let resolved_path = resolve_real_path(Path::new(&task_path));
+  if let Err(err) = run_git(&resolved_path, &["rev-parse", "--is-inside-work-tree"]) {
+    return json!({ "success": false, "error": err });
+  }
+
+  let args: &[&str] = if draft {
+    &["pr", "ready", "--undo"]
+  } else {
+    &["pr", "ready"]
+  };
+  if let Err(err) = run_cmd("gh", args, Some(&resolved_path)) {
+    let lowered = err.to_lowercase();
+    if lowered.contains("no pull request") || lowered.contains("not found") {
+      return json!({ "success": true, "pr": null });
+    }
+    return json!({ "success": false, "error": err });
+  }
+
+  git_get_pr_status_sync(task_path, remote)
+}
+
+#[tauri::command]
+pub async fn github_pr_mark_ready(app: tauri::AppHandle, task_path: String) -> Value {
+  run_git_command(
+    json!({ "success": false, "error": "Task cancelled" }),
+    move || {
+      let state: tauri::State<DbState> = app.state();
+      let remote = configured_remote(&state, &task_path);
+      set_pr_draft_sync(task_path, remote, false)
+    },
+  )
+  .await
+}
+
+#[tauri::command]
+pub async fn github_pr_mark_draft(app: tauri::AppHandle, task_path: String) -> Value {
+  run_git_command(
+    json!({ "success": false, "error": "Task cancelled" }),
+    move || {
+      let state: tauri::State<DbState> = app.state();
+      let remote = configured_remote(&state, &task_path);
+      set_pr_draft_sync(task_path, remote, true)
+    },
+  )
+  .await
+}
+
+// Check runs from GitHub Actions link to `/actions/runs/<id>`; anything else (status contexts,
+// third-party check apps) is owned by an external CI service and can't be re-run through gh.
+fn is_actions_check(item: &Value) -> bool {
+  let is_check_run = item.get("__typename").and_then(|v| v.as_str()) == Some("CheckRun")
+    || item.get("workflowName").is_some();
+  let details_url = item.get("detailsUrl").and_then(|v| v.as_str()).unwrap_or("");
+  let workflow = item.get("workflowName").and_then(|v| v.as_str()).unwrap_or("");
+  is_check_run && (!workflow.trim().is_empty() || details_url.contains("/actions/runs/"))
+}
+
+fn status_check_name(item: &Value) -> String {
+  item
+    .get("name")
+    .or_else(|| item.get("context"))
+    .and_then(|v| v.as_str())
+    .unwrap_or("unknown")
+    .to_string()
+}
+
+fn github_rerun_checks_sync(task_path: String, only_failed: bool) -> Value {
+  let resolved_path = resolve_real_path(Path::new(&task_path));
+  if let Err(err) = run_git(&resolved_path, &["rev-parse", "--is-inside-work-tree"]) {
+    return json!({ "success": false, "error": err });
+  }
+
+  let args = ["pr", "view", "--json", "headRefOid,statusCheckRollup", "-q", "."];
+  let raw = match run_cmd("gh", &args, Some(&resolved_path)) {
+    Ok(out) => out,
+    Err(err) => {
+      let lowered = err.to_lowercase();
+      if lowered.contains("no pull request") || lowered.contains("not found") {
+        return json!({
+          "success": false,
+          "code": "NO_PR",
+          "error": "No pull request found for this branch"
+        });
+      }
+      return json!({ "success": false, "error": err });
+    }
+  };
+  let data: Value = match serde_json::from_str(raw.trim()) {
+    Ok(value) => value,
+    Err(err) => return json!({ "success": false, "error": err.to_string() }),
+  };
+  let head_sha = data.get("headRefOid").and_then(|v| v.as_str()).unwrap_or("").trim();
+  if head_sha.is_empty() {
+    return json!({ "success": false, "error": "Pull request has no head commit" });
+  }
+
+  let rollup = data
+    .get("statusCheckRollup")
     .and_then(|v| v.as_array())
-    .map(|arr| arr.len() as i64)
-    .unwrap_or(0);
+    .cloned()
+    .unwrap_or_default();
+  let external_failed: Vec<String> = rollup
+    .iter()
+    .filter(|item| !is_actions_check(item))
+    .filter(|item| {
+      let state = item
+        .get("conclusion")
+        .and_then(|v| v.as_str())
+        .or_else(|| item.get("state").and_then(|v| v.as_str()))
+        .unwrap_or("");
+      normalize_status_check_state(state) == "failed"
+    })
+    .map(status_check_name)
+    .collect();
+  if !rollup.is_empty() && rollup.iter().all(|item| !is_actions_check(item)) {
+    return json!({
+      "success": false,
+      "code": "NOT_GITHUB_ACTIONS",
+      "error": "These checks are reported by an external CI service, not GitHub Actions, and can't be re-run from here. Re-run them from the provider's page.",
+      "externalChecks": rollup.iter().map(status_check_name).collect::<Vec<_>>()
+    });
+  }
 
-  if let Some(obj) = data.as_object_mut() {
-    if let Some(summary) = checks_summary {
-      obj.insert("checksSummary".to_string(), summary);
+  // Ask for the head commit's runs directly; a branch-wide window can miss them once the
+  // branch has more recent activity.
+  let list_args = [
+    "run",
+    "list",
+    "--commit",
+    head_sha,
+    "--json",
+    "databaseId,headSha,status,conclusion,workflowName,url",
+  ];
+  let runs: Vec<Value> = match run_cmd("gh", &list_args, Some(&resolved_path)) {
+    Ok(out) => match serde_json::from_str::<Value>(out.trim()) {
+      Ok(Value::Array(items)) => items,
+      Ok(_) => Vec::new(),
+      Err(err) => return json!({ "success": false, "error": err.to_string() }),
+    },
+    Err(err) => return json!({ "success": false, "error": err }),
+  };
+
+  // Only the PR's head commit counts; re-running an older commit's workflows wouldn't
+  // change the checks the PR shows.
+  let head_runs: Vec<&Value> = runs
+    .iter()
+    .filter(|run| run.get("headSha").and_then(|v| v.as_str()) == Some(head_sha))
+    .collect();
+  let candidates: Vec<&Value> = head_runs
+    .iter()
+    .copied()
+    .filter(|run| run.get("status").and_then(|v| v.as_str()) == Some("completed"))
+    .filter(|run| {
+      !only_failed
+        || normalize_status_check_state(
+          run.get("conclusion").and_then(|v| v.as_str()).unwrap_or(""),
+        ) == "failed"
+    })
+    .collect();
+
+  if candidates.is_empty() {
+    let short_sha = &head_sha[..head_sha.len().min(7)];
+    let message = if head_runs.is_empty() {
+      format!("No GitHub Actions runs found for the PR's head commit {}", short_sha)
+    } else if only_failed {
+      "No failed GitHub Actions runs to re-run".to_string()
+    } else {
+      "No completed GitHub Actions runs to re-run".to_string()
+    };
+    return json!({
+      "success": false,
+      "code": "NO_RUNS",
+      "error": message,
+      "externalFailedChecks": external_failed
+    });
+  }
+
+  let mut results: Vec<Value> = Vec::new();
+  for run in candidates {
+    let Some(run_id) = run.get("databaseId").and_then(|v| v.as_i64()) else {
+      continue;
+    };
+    let run_id_str = run_id.to_string();
+    let mut rerun_args = vec!["run", "rerun", run_id_str.as_str()];
+    if only_failed {
+      rerun_args.push("--failed");
     }
-    obj.insert("commentsCount".to_string(), json!(comments_count));
-    obj.insert("reviewCount".to_string(), json!(review_count));
-    obj.remove("comments");
-    obj.remove("reviews");
-    obj.remove("statusCheckRollup");
+    let outcome = run_cmd("gh", &rerun_args, Some(&resolved_path));
+    results.push(json!({
+      "runId": run_id,
+      "workflowName": run.get("workflowName").cloned().unwrap_or(Value::Null),
+      "url": run.get("url").cloned().unwrap_or(Value::Null),
+      "rerun": outcome.is_ok(),
+      "error": outcome.err()
+    }));
   }
 
-  json!({ "success": true, "pr": data })
+  let any_rerun = results
+    .iter()
+    .any(|r| r.get("rerun").and_then(|v| v.as_bool()) == Some(true));
+  let mut response = json!({
+    "success": any_rerun,
+    "headSha": head_sha,
+    "runs": results,
+    "externalFailedChecks": external_failed
+  });
+  if !any_rerun {
+    response["error"] = json!("Failed to re-run GitHub Actions workflows");
+  }
+  response
 }
 
 #[tauri::command]
-pub async fn git_get_pr_status(task_path: String) -> Value {
+pub async fn github_rerun_checks(task_path: String, only_failed: Option<bool>) -> Value {
   let fallback_path = task_path.clone();
-  run_blocking(
-    json!({ "success": false, "error": "git_get_pr_status failed", "taskPath": fallback_path }),
-    move || git_get_pr_status_sync(task_path),
+  run_git_command(
+    json!({ "success": false, "error": "github_rerun_checks failed", "taskPath": fallback_path }),
+    move || github_rerun_checks_sync(task_path, only_failed.unwrap_or(true)),
   )
   .await
 }
@@ -1360,7 +3033,7 @@ fn git_get_pr_comments_sync(task_path: String) -> Value {
 #[tauri::command]
 pub async fn git_get_pr_comments(task_path: String) -> Value {
   let fallback_path = task_path.clone();
-  run_blocking(
+  run_git_command(
     json!({
       "success": false,
       "error": "git_get_pr_comments failed",
@@ -1514,81 +3187,425 @@ fn git_get_pr_changes_sync(task_path: String) -> Value {
 }
 
 #[tauri::command]
-pub async fn git_get_pr_changes(task_path: String) -> Value {
+pub async fn git_get_pr_changes(task_path: String) -> Value {
+  let fallback_path = task_path.clone();
+  run_git_command(
+    json!({
+      "success": false,
+      "error": "git_get_pr_changes failed",
+      "taskPath": fallback_path,
+    }),
+    move || git_get_pr_changes_sync(task_path),
+  )
+  .await
+}
+
+pub(crate) fn git_list_remote_branches_sync(
+  project_path: String,
+  remote: String,
+  cancel: Option<&AtomicBool>,
+) -> Value {
+  if project_path.trim().is_empty() {
+    return json!({ "success": false, "error": "projectPath is required" });
+  }
+  let resolved_path = resolve_real_path(Path::new(&project_path));
+  if run_git(&resolved_path, &["rev-parse", "--is-inside-work-tree"]).is_err() {
+    return json!({ "success": false, "error": "Not a git repository" });
+  }
+
+  let remote_name = remote;
+  if run_git(&resolved_path, &["remote", "get-url", remote_name.as_str()]).is_ok() {
+    let fetched = run_git_cancellable(
+      &resolved_path,
+      &["fetch", "--prune", remote_name.as_str()],
+      cancel,
+    );
+    if matches!(&fetched, Err(err) if err == GIT_CANCELLED) {
+      return json!({ "success": false, "cancelled": true, "error": GIT_CANCELLED });
+    }
+  }
+
+  let output = match run_git(
+    &resolved_path,
+    &[
+      "for-each-ref",
+      "--format=%(refname:short)",
+      &format!("refs/remotes/{}", remote_name),
+    ],
+  ) {
+    Ok(output) => output,
+    Err(err) => return json!({ "success": false, "error": err }),
+  };
+
+  let branches: Vec<Value> = output
+    .split('\n')
+    .map(|line| line.trim())
+    .filter(|line| !line.is_empty())
+    .filter(|line| !line.ends_with("/HEAD"))
+    .map(|ref_name| {
+      let mut parts = ref_name.split('/');
+      let remote_alias = parts.next().unwrap_or(&remote_name);
+      let branch = parts.collect::<Vec<&str>>().join("/");
+      let branch_name = if branch.is_empty() {
+        ref_name.to_string()
+      } else {
+        branch.clone()
+      };
+      json!({
+        "ref": ref_name,
+        "remote": remote_alias,
+        "branch": branch_name,
+        "label": format!("{}/{}", remote_alias, branch_name)
+      })
+    })
+    .collect();
+
+  json!({ "success": true, "branches": branches })
+}
+
+#[tauri::command]
+pub async fn git_list_remote_branches(
+  app: tauri::AppHandle,
+  project_path: String,
+  remote: Option<String>,
+  request_id: Option<String>,
+) -> Value {
+  let fallback_path = project_path.clone();
+  run_git_command(
+    json!({
+      "success": false,
+      "error": "git_list_remote_branches failed",
+      "projectPath": fallback_path,
+    }),
+    move || {
+      let remote = match remote.as_deref().map(str::trim).filter(|r| !r.is_empty()) {
+        Some(explicit) => normalize_remote_name(Some(explicit)),
+        None => {
+          let state: tauri::State<DbState> = app.state();
+          configured_remote(&state, &project_path)
+        }
+      };
+      with_cancel_flag(&app, request_id.as_deref(), |cancel| {
+        git_list_remote_branches_sync(project_path, remote, cancel.map(|flag| flag.as_ref()))
+      })
+    },
+  )
+  .await
+}
+
+fn git_delete_remote_branch_sync(project_path: String, branch: String, remote: String) -> Value {
+  if project_path.trim().is_empty() {
+    return json!({ "success": false, "error": "projectPath is required" });
+  }
+  let resolved_path = resolve_real_path(Path::new(&project_path));
+  if run_git(&resolved_path, &["rev-parse", "--is-inside-work-tree"]).is_err() {
+    return json!({ "success": false, "error": "Not a git repository" });
+  }
+
+  let mut branch = branch.trim().to_string();
+  if let Some(stripped) = branch.strip_prefix(&format!("{}/", remote)) {
+    branch = stripped.to_string();
+  }
+  if branch.is_empty() {
+    return json!({ "success": false, "error": "branch is required" });
+  }
+
+  let git = resolve_git_bin();
+  let (ok, stdout, stderr) = match run_cmd_output(
+    &git,
+    &["push", remote.as_str(), "--delete", branch.as_str()],
+    Some(&resolved_path),
+  ) {
+    Ok(result) => result,
+    Err(err) => return json!({ "success": false, "error": err }),
+  };
+  let output = combine_output(&stdout, &stderr).trim().to_string();
+
+  let missing = !ok && output.to_lowercase().contains("remote ref does not exist");
+  if !ok && !missing {
+    return json!({ "success": false, "error": output });
+  }
+  json!({
+    "success": true,
+    "deleted": ok,
+    "remote": remote,
+    "branch": branch,
+    "output": output
+  })
+}
+
+#[tauri::command]
+pub async fn git_delete_remote_branch(
+  app: tauri::AppHandle,
+  project_path: String,
+  branch: String,
+  remote: Option<String>,
+) -> Value {
+  run_git_command(
+    json!({ "success": false, "error": "Task cancelled" }),
+    move || {
+      let remote = match remote.as_deref().map(str::trim).filter(|r| !r.is_empty()) {
+        Some(explicit) => normalize_remote_name(Some(explicit)),
+        None => {
+          let state: tauri::State<DbState> = app.state();
+          configured_remote(&state, &project_path)
+        }
+      };
+      git_delete_remote_branch_sync(project_path, branch, remote)
+    },
+  )
+  .await
+}
+
+fn resolve_log_base(cwd: &Path, remote: &str, base: &str) -> Option<String> {
+  let candidates = if base.contains('/') {
+    vec![base.to_string()]
+  } else {
+    vec![format!("{}/{}", remote, base), base.to_string()]
+  };
+  candidates
+    .into_iter()
+    .find(|candidate| run_git(cwd, &["rev-parse", "--verify", "--quiet", candidate.as_str()]).is_ok())
+}
+
+fn git_get_log_sync(
+  task_path: String,
+  remote: String,
+  limit: Option<u32>,
+  base: Option<String>,
+) -> Value {
+  let resolved_path = resolve_real_path(Path::new(&task_path));
+  if let Err(err) = run_git(&resolved_path, &["rev-parse", "--is-inside-work-tree"]) {
+    return json!({ "success": false, "error": err });
+  }
+
+  let limit = limit.unwrap_or(50).clamp(1, 500);
+  let base = base.map(|b| b.trim().to_string()).filter(|b| !b.is_empty());
+  let range = match base {
+    Some(base) => match resolve_log_base(&resolved_path, &remote, &base) {
+      Some(base_ref) => format!("{}..HEAD", base_ref),
+      None => return json!({ "success": false, "error": format!("Unknown base ref '{}'", base) }),
+    },
+    None => "HEAD".to_string(),
+  };
+  let max_count = format!("--max-count={}", limit);
+  let output = match run_git(
+    &resolved_path,
+    &[
+      "log",
+      max_count.as_str(),
+      "--pretty=format:%H%x00%an%x00%at%x00%s",
+      range.as_str(),
+      "--",
+    ],
+  ) {
+    Ok(output) => output,
+    Err(err) => {
+      // A branch without commits yet has no history to show.
+      if err.contains("does not have any commits") {
+        return json!({ "success": true, "commits": Vec::<Value>::new() });
+      }
+      return json!({ "success": false, "error": err });
+    }
+  };
+
+  let commits: Vec<Value> = output
+    .lines()
+    .filter_map(|line| {
+      let mut parts = line.trim_end_matches('\r').splitn(4, '\0');
+      let hash = parts.next()?.trim();
+      if hash.is_empty() {
+        return None;
+      }
+      let author = parts.next().unwrap_or("");
+      let timestamp = parts.next().and_then(|v| v.trim().parse::<i64>().ok()).unwrap_or(0);
+      let subject = parts.next().unwrap_or("");
+      Some(json!({
+        "hash": hash,
+        "author": author,
+        "timestamp": timestamp,
+        "subject": subject
+      }))
+    })
+    .collect();
+
+  json!({ "success": true, "commits": commits })
+}
+
+#[tauri::command]
+pub async fn git_get_log(
+  app: tauri::AppHandle,
+  task_path: String,
+  limit: Option<u32>,
+  base: Option<String>,
+) -> Value {
+  let fallback_path = task_path.clone();
+  run_git_command(
+    json!({ "success": false, "error": "git_get_log failed", "taskPath": fallback_path }),
+    move || {
+      let state: tauri::State<DbState> = app.state();
+      let remote = configured_remote(&state, &task_path);
+      git_get_log_sync(task_path, remote, limit, base)
+    },
+  )
+  .await
+}
+
+fn git_benchmark_sync(task_path: String) -> Value {
+  let resolved_path = resolve_real_path(Path::new(&task_path));
+  // Detect afresh so the report reflects the current GIT_PATH, without touching the cache
+  // every other command relies on.
+  let git_bin = detect_git_bin();
+  let operations: [(&str, &[&str]); 4] = [
+    ("revParse", &["rev-parse", "--is-inside-work-tree"]),
+    ("status", &["status", "--porcelain", "--untracked-files=all"]),
+    ("diffHead", &["diff", "--numstat", "HEAD", "--"]),
+    ("revListCount", &["rev-list", "--count", "HEAD"]),
+  ];
+
+  let mut timings: Vec<Value> = Vec::new();
+  let mut total_ms: u128 = 0;
+  for (name, args) in operations {
+    let started = Instant::now();
+    let result = run_cmd(&git_bin, args, Some(&resolved_path));
+    let elapsed = started.elapsed().as_millis();
+    total_ms += elapsed;
+    let mut entry = json!({ "operation": name, "ms": elapsed as u64, "ok": result.is_ok() });
+    if let Err(err) = result {
+      entry["error"] = Value::String(err);
+    }
+    timings.push(entry);
+  }
+
+  json!({
+    "success": true,
+    "gitPath": git_bin,
+    "taskPath": resolved_path.to_string_lossy(),
+    "timings": timings,
+    "totalMs": total_ms as u64
+  })
+}
+
+#[tauri::command]
+pub async fn git_benchmark(task_path: String) -> Value {
   let fallback_path = task_path.clone();
-  run_blocking(
-    json!({
-      "success": false,
-      "error": "git_get_pr_changes failed",
-      "taskPath": fallback_path,
-    }),
-    move || git_get_pr_changes_sync(task_path),
+  run_git_command(
+    json!({ "success": false, "error": "git_benchmark failed", "taskPath": fallback_path }),
+    move || git_benchmark_sync(task_path),
   )
   .await
 }
 
-fn git_list_remote_branches_sync(project_path: String, remote: Option<String>) -> Value {
-  if project_path.trim().is_empty() {
-    return json!({ "success": false, "error": "projectPath is required" });
-  }
-  let resolved_path = resolve_real_path(Path::new(&project_path));
-  if run_git(&resolved_path, &["rev-parse", "--is-inside-work-tree"]).is_err() {
-    return json!({ "success": false, "error": "Not a git repository" });
+fn git_export_patch_sync(
+  task_path: String,
+  remote: String,
+  base: Option<String>,
+  output_path: Option<String>,
+) -> Value {
+  let resolved_path = resolve_real_path(Path::new(&task_path));
+  if let Err(err) = run_git(&resolved_path, &["rev-parse", "--is-inside-work-tree"]) {
+    return json!({ "success": false, "error": err });
   }
 
-  let remote_name = remote.unwrap_or_else(|| DEFAULT_REMOTE.to_string());
-  if run_git(&resolved_path, &["remote", "get-url", remote_name.as_str()]).is_ok() {
-    let _ = run_git(&resolved_path, &["fetch", "--prune", remote_name.as_str()]);
+  let base = base
+    .map(|b| b.trim().to_string())
+    .filter(|b| !b.is_empty())
+    .or_else(|| detect_default_branch(&resolved_path, Some(remote.as_str())))
+    .unwrap_or_else(|| DEFAULT_BRANCH.to_string());
+  let Some(base_ref) = resolve_log_base(&resolved_path, &remote, &base) else {
+    return json!({ "success": false, "error": format!("Unknown base ref '{}'", base) });
+  };
+
+  let target = match output_path.map(|p| p.trim().to_string()).filter(|p| !p.is_empty()) {
+    // git runs inside the task and fs::metadata in our own cwd, so anchor relative paths
+    // to the task for both.
+    Some(path) => resolved_path.join(path),
+    None => {
+      let branch = run_git(&resolved_path, &["branch", "--show-current"])
+        .map(|b| b.trim().replace('/', "-"))
+        .ok()
+        .filter(|b| !b.is_empty())
+        .unwrap_or_else(|| "HEAD".to_string());
+      let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+      std::env::temp_dir().join(format!("emdash-{}-{}.patch", branch, now))
+    }
+  };
+  if let Some(parent) = target.parent() {
+    if !parent.as_os_str().is_empty() && !parent.is_dir() {
+      let error = format!("Directory does not exist: {}", parent.display());
+      return json!({ "success": false, "error": error });
+    }
   }
 
-  let output = match run_git(
+  // Let git write the file itself so binary hunks are not mangled by UTF-8 decoding.
+  let output_arg = format!("--output={}", target.to_string_lossy());
+  let range = format!("{}...HEAD", base_ref);
+  if let Err(err) = run_git(
     &resolved_path,
-    &[
-      "for-each-ref",
-      "--format=%(refname:short)",
-      &format!("refs/remotes/{}", remote_name),
-    ],
+    &["diff", "--no-color", "--binary", output_arg.as_str(), range.as_str(), "--"],
   ) {
-    Ok(output) => output,
-    Err(err) => return json!({ "success": false, "error": err }),
-  };
+    return json!({ "success": false, "error": err });
+  }
 
-  let branches: Vec<Value> = output
-    .split('\n')
-    .map(|line| line.trim())
-    .filter(|line| !line.is_empty())
-    .filter(|line| !line.ends_with("/HEAD"))
-    .map(|ref_name| {
-      let mut parts = ref_name.split('/');
-      let remote_alias = parts.next().unwrap_or(&remote_name);
-      let branch = parts.collect::<Vec<&str>>().join("/");
-      let branch_name = if branch.is_empty() {
-        ref_name.to_string()
-      } else {
-        branch.clone()
-      };
-      json!({
-        "ref": ref_name,
-        "remote": remote_alias,
-        "branch": branch_name,
-        "label": format!("{}/{}", remote_alias, branch_name)
-      })
-    })
-    .collect();
+  let size = fs::metadata(&target).map(|meta| meta.len()).unwrap_or(0);
+  json!({
+    "success": true,
+    "path": target.to_string_lossy(),
+    "size": size,
+    "base": base_ref,
+    "empty": size == 0
+  })
+}
 
-  json!({ "success": true, "branches": branches })
+#[tauri::command]
+pub async fn git_export_patch(
+  app: tauri::AppHandle,
+  task_path: String,
+  base: Option<String>,
+  output_path: Option<String>,
+) -> Value {
+  let fallback_path = task_path.clone();
+  run_git_command(
+    json!({ "success": false, "error": "git_export_patch failed", "taskPath": fallback_path }),
+    move || {
+      let state: tauri::State<DbState> = app.state();
+      let remote = configured_remote(&state, &task_path);
+      git_export_patch_sync(task_path, remote, base, output_path)
+    },
+  )
+  .await
+}
+
+fn git_apply_patch_sync(task_path: String, patch_path: String, check: bool) -> Value {
+  let resolved_path = resolve_real_path(Path::new(&task_path));
+  let patch = PathBuf::from(patch_path.trim());
+  if !patch.is_file() {
+    let error = format!("Patch file not found: {}", patch.display());
+    return json!({ "success": false, "error": error });
+  }
+  let patch_arg = patch.to_string_lossy().to_string();
+
+  if check {
+    if let Err(err) = run_git(&resolved_path, &["apply", "--check", patch_arg.as_str()]) {
+      return json!({ "success": false, "code": "CHECK_FAILED", "error": err });
+    }
+  }
+
+  match run_git(&resolved_path, &["apply", patch_arg.as_str()]) {
+    Ok(_) => json!({ "success": true, "checked": check }),
+    Err(err) => json!({ "success": false, "error": err }),
+  }
 }
 
 #[tauri::command]
-pub async fn git_list_remote_branches(project_path: String, remote: Option<String>) -> Value {
-  let fallback_path = project_path.clone();
-  run_blocking(
-    json!({
-      "success": false,
-      "error": "git_list_remote_branches failed",
-      "projectPath": fallback_path,
-    }),
-    move || git_list_remote_branches_sync(project_path, remote),
+pub async fn git_apply_patch(task_path: String, patch_path: String, check: Option<bool>) -> Value {
+  let fallback_path = task_path.clone();
+  run_git_command(
+    json!({ "success": false, "error": "git_apply_patch failed", "taskPath": fallback_path }),
+    move || git_apply_patch_sync(task_path, patch_path, check.unwrap_or(false)),
   )
   .await
 }
@@ -1746,7 +3763,9 @@ fn normalize_markdown(text: &str) -> String {
 #[derive(Default)]
 struct ProviderCommandOutput {
   success: bool,
+  timed_out: bool,
   stdout: String,
+  stderr: String,
 }
 
 fn run_provider_command(
@@ -1755,6 +3774,7 @@ fn run_provider_command(
   cwd: &Path,
   prompt: Option<&str>,
   timeout_ms: u64,
+  cancel: Option<&AtomicBool>,
 ) -> Option<ProviderCommandOutput> {
   let mut cmd = Command::new(command);
   cmd
@@ -1812,6 +3832,11 @@ fn run_provider_command(
   let start = Instant::now();
   let mut timed_out = false;
   let status = loop {
+    if cancel.is_some_and(|flag| flag.load(Ordering::SeqCst)) {
+      let _ = child.kill();
+      let _ = child.wait();
+      break None;
+    }
     if start.elapsed() >= Duration::from_millis(timeout_ms) {
       timed_out = true;
       let _ = child.kill();
@@ -1833,64 +3858,279 @@ fn run_provider_command(
   }
 
   let stdout = stdout_buf.lock().unwrap().clone();
-  let _stderr = stderr_buf.lock().unwrap().clone();
+  let stderr = stderr_buf.lock().unwrap().clone();
 
   let success = status.as_ref().map(|s| s.success()).unwrap_or(false) && !timed_out;
   Some(ProviderCommandOutput {
     success,
+    timed_out,
     stdout,
+    stderr,
   })
 }
 
-fn generate_with_provider(
-  provider_id: &str,
-  task_path: &Path,
-  diff: &str,
-  commits: &[String],
-) -> Option<(String, String)> {
-  let provider = provider_generation_config(provider_id)?;
-  let version_args = provider.version_args.unwrap_or(&["--version"]);
-  if run_cmd(provider.cli, version_args, Some(task_path)).is_err() {
-    return None;
+/// The CLI's version output (first line), or `None` when it isn't installed.
+fn provider_cli_version(provider: &ProviderGenerationConfig, cwd: &Path) -> Option<String> {
+  let version_args = providers::version_args(provider.id).unwrap_or(&["--version"]);
+  let output = run_cmd(provider.cli, version_args, Some(cwd)).ok()?;
+  Some(output.lines().map(str::trim).find(|l| !l.is_empty()).unwrap_or("").to_string())
+}
+
+/// Whether each configured flag appears in the CLI's `--help`; `None` means unknown
+/// (no flag configured, or help couldn't be read or doesn't list options), in which case
+/// the flag is still passed.
+#[derive(Clone, Copy, Default)]
+struct ProviderFlagSupport {
+  auto_approve: Option<bool>,
+  initial_prompt: Option<bool>,
+  model: Option<bool>,
+}
+
+static PROVIDER_FLAG_SUPPORT: Mutex<Option<HashMap<String, ProviderFlagSupport>>> =
+  Mutex::new(None);
+
+fn help_mentions_flag(help: &str, flag: &str) -> bool {
+  let is_flag_char = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_';
+  help.match_indices(flag).any(|(idx, _)| {
+    let before = help[..idx].chars().next_back();
+    let after = help[idx + flag.len()..].chars().next();
+    !before.is_some_and(is_flag_char) && !after.is_some_and(is_flag_char)
+  })
+}
+
+// Help that doesn't list at least a couple of options (a usage stub, a launcher banner) can't
+// show that a flag is missing.
+fn help_lists_options(help: &str) -> bool {
+  help
+    .lines()
+    .filter(|line| line.trim_start().starts_with('-'))
+    .take(2)
+    .count()
+    >= 2
+}
+
+// Cached per CLI and version, since `--help` output only changes when the CLI is upgraded.
+// Probed under `default_args` so subcommand flags (e.g. `goose run -t`) are listed.
+fn provider_flag_support(
+  provider: &ProviderGenerationConfig,
+  cwd: &Path,
+  version: &str,
+) -> ProviderFlagSupport {
+  let key = format!("{}:{}@{}", provider.id, provider.cli, version);
+  if let Some(cached) = PROVIDER_FLAG_SUPPORT
+    .lock()
+    .unwrap()
+    .as_ref()
+    .and_then(|cache| cache.get(&key).copied())
+  {
+    return cached;
   }
 
-  let prompt = build_pr_generation_prompt(diff, commits);
+  let mut help_args: Vec<String> = provider
+    .default_args
+    .unwrap_or(&[])
+    .iter()
+    .map(|arg| arg.to_string())
+    .collect();
+  help_args.push("--help".to_string());
+  let help = run_provider_command(provider.cli, &help_args, cwd, None, 5_000, None)
+    .map(|out| format!("{}\n{}", out.stdout, out.stderr))
+    .filter(|text| !text.trim().is_empty());
+  let Some(help) = help else {
+    return ProviderFlagSupport::default();
+  };
+  let lists_options = help_lists_options(&help);
+  let check = |flag: Option<&str>| {
+    flag
+      .map(str::trim)
+      .filter(|f| !f.is_empty())
+      .and_then(|f| {
+        if help_mentions_flag(&help, f) {
+          Some(true)
+        } else if lists_options {
+          Some(false)
+        } else {
+          None
+        }
+      })
+  };
+  let support = ProviderFlagSupport {
+    auto_approve: check(provider.auto_approve_flag),
+    initial_prompt: check(provider.initial_prompt_flag),
+    model: check(provider.model_flag),
+  };
+  PROVIDER_FLAG_SUPPORT
+    .lock()
+    .unwrap()
+    .get_or_insert_with(HashMap::new)
+    .insert(key, support);
+  support
+}
+
+// Runs the provider CLI once, passing the prompt via its prompt flag when it has one and
+// via stdin otherwise.
+// Flags the CLI's help shows it doesn't know are left out rather than failing the run.
+fn run_provider_prompt(
+  provider: &ProviderGenerationConfig,
+  cwd: &Path,
+  prompt: &str,
+  ctx: &GenerationContext,
+  timeout_ms: u64,
+  support: ProviderFlagSupport,
+) -> Option<ProviderCommandOutput> {
+  let model = ctx.models.get(provider.id).map(String::as_str);
   let mut args: Vec<String> = Vec::new();
 
   if let Some(default_args) = provider.default_args {
     args.extend(default_args.iter().map(|arg| arg.to_string()));
   }
   if let Some(flag) = provider.auto_approve_flag {
-    if !flag.trim().is_empty() {
+    if !flag.trim().is_empty() && support.auto_approve != Some(false) {
+      args.push(flag.to_string());
+    }
+  }
+  if let (Some(flag), Some(model)) = (provider.model_flag, model) {
+    if support.model != Some(false) {
       args.push(flag.to_string());
+      args.push(model.to_string());
     }
   }
 
   let mut prompt_via_stdin = true;
   if let Some(flag) = provider.initial_prompt_flag {
-    if !flag.is_empty() {
+    if !flag.is_empty() && support.initial_prompt != Some(false) {
       args.push(flag.to_string());
-      args.push(prompt.clone());
+      args.push(prompt.to_string());
       prompt_via_stdin = false;
     }
   }
 
-  let output = run_provider_command(
+  run_provider_command(
     provider.cli,
     &args,
-    task_path,
-    if prompt_via_stdin { Some(prompt.as_str()) } else { None },
-    30_000,
-  )?;
+    cwd,
+    if prompt_via_stdin { Some(prompt) } else { None },
+    timeout_ms,
+    ctx.cancel.as_deref(),
+  )
+}
+
+fn run_generation_provider(
+  provider_id: &str,
+  task_path: &Path,
+  prompt: &str,
+  ctx: &GenerationContext,
+) -> Option<String> {
+  if ctx.is_cancelled() {
+    return None;
+  }
+  let provider = provider_generation_config(provider_id)?;
+  let version = provider_cli_version(provider, task_path)?;
+  let support = provider_flag_support(provider, task_path, &version);
 
+  let output = run_provider_prompt(provider, task_path, prompt, ctx, 30_000, support)?;
   if !output.success {
     return None;
   }
 
-  let (title, description) = parse_provider_response(&output.stdout)?;
+  Some(output.stdout)
+}
+
+fn generate_with_provider(
+  provider_id: &str,
+  task_path: &Path,
+  diff: &str,
+  commits: &[String],
+  ctx: &GenerationContext,
+) -> Option<(String, String)> {
+  let prompt = build_pr_generation_prompt(diff, commits);
+  let stdout = run_generation_provider(provider_id, task_path, &prompt, ctx)?;
+  let (title, description) = parse_provider_response(&stdout)?;
   Some((title, normalize_markdown(&description)))
 }
 
+fn build_commit_message_prompt(diff: &str, changed_files: &[String]) -> String {
+  let files_context = if changed_files.is_empty() {
+    String::new()
+  } else {
+    format!(
+      "\n\nChanged files:\n{}",
+      changed_files
+        .iter()
+        .map(|file| format!("- {}", file))
+        .collect::<Vec<String>>()
+        .join("\n")
+    )
+  };
+
+  let diff_context = if diff.trim().is_empty() {
+    String::new()
+  } else {
+    let (snippet, truncated) = truncate_string(diff, 2000);
+    format!(
+      "\n\nDiff:\n{}{}",
+      snippet,
+      if truncated { "..." } else { "" }
+    )
+  };
+
+  format!(
+    r#"Generate a git commit message for these staged changes:
+
+{files_context}{diff_context}
+
+Please respond in the following JSON format:
+{{
+  "subject": "A single-line summary (max 72 chars, use conventional commit format such as feat:, fix:, chore:)",
+  "body": "Optional plain-text body explaining what changed and why, wrapped at 72 columns. Use an empty string if the subject is enough."
+}}
+
+Only respond with valid JSON, no other text."#,
+    files_context = files_context,
+    diff_context = diff_context
+  )
+}
+
+fn parse_commit_message_response(response: &str) -> Option<(String, String)> {
+  let start = response.find('{')?;
+  let end = response.rfind('}')?;
+  if end <= start {
+    return None;
+  }
+  let parsed: Value = serde_json::from_str(&response[start..=end]).ok()?;
+  let subject = parsed
+    .get("subject")?
+    .as_str()?
+    .lines()
+    .next()
+    .unwrap_or("")
+    .trim()
+    .to_string();
+  if subject.is_empty() {
+    return None;
+  }
+  let body = parsed
+    .get("body")
+    .and_then(|v| v.as_str())
+    .unwrap_or("")
+    .replace("\\n", "\n")
+    .trim()
+    .to_string();
+  Some((subject, body))
+}
+
+fn generate_commit_message_with_provider(
+  provider_id: &str,
+  task_path: &Path,
+  diff: &str,
+  changed_files: &[String],
+  ctx: &GenerationContext,
+) -> Option<(String, String)> {
+  let prompt = build_commit_message_prompt(diff, changed_files);
+  let stdout = run_generation_provider(provider_id, task_path, &prompt, ctx)?;
+  parse_commit_message_response(&stdout)
+}
+
 fn generate_pr_title(commits: &[String], changed_files: &[String]) -> String {
   let prefixes = [
     "feat", "fix", "chore", "docs", "style", "refactor", "test", "perf", "ci", "build", "revert",
@@ -2063,6 +4303,27 @@ fn generate_pr_description(
   }
 }
 
+/// Builds a commit title/body for squashing `base..head`, reusing the PR heuristics.
+pub(crate) fn generate_squash_message(cwd: &Path, base: &str, head: &str) -> (String, String) {
+  let range = format!("{}..{}", base, head);
+  let commits = run_git(cwd, &["log", range.as_str(), "--pretty=format:%s"])
+    .map(|output| parse_output_lines(&output))
+    .unwrap_or_default();
+  let mut changed_files: Vec<String> = Vec::new();
+  let mut seen: HashSet<String> = HashSet::new();
+  if let Ok(output) = run_git(cwd, &["diff", "--name-only", range.as_str()]) {
+    add_files_from_output(&output, &mut seen, &mut changed_files);
+  }
+  let (file_count, insertions, deletions) = run_git(cwd, &["diff", "--shortstat", range.as_str()])
+    .map(|output| shortstat_counts(&output))
+    .unwrap_or((0, 0, 0));
+
+  let title = generate_pr_title(&commits, &changed_files);
+  let description =
+    generate_pr_description(&commits, &changed_files, file_count, insertions, deletions);
+  (title, description)
+}
+
 fn generate_fallback_content(changed_files: &[String]) -> (String, String) {
   let title = if let Some(first) = changed_files.first() {
     let name = Path::new(first)
@@ -2074,41 +4335,179 @@ fn generate_fallback_content(changed_files: &[String]) -> (String, String) {
     "chore: update code".to_string()
   };
 
-  let description = if !changed_files.is_empty() {
-    format!(
-      "Updated {} file{}.",
-      changed_files.len(),
-      if changed_files.len() == 1 { "" } else { "s" }
-    )
-  } else {
-    "No changes detected.".to_string()
-  };
+  let description = if !changed_files.is_empty() {
+    format!(
+      "Updated {} file{}.",
+      changed_files.len(),
+      if changed_files.len() == 1 { "" } else { "s" }
+    )
+  } else {
+    "No changes detected.".to_string()
+  };
+
+  (title, description)
+}
+
+/// Cancel flags for in-flight provider runs and long git operations, keyed by the caller's
+/// request id.
+#[derive(Default)]
+pub struct CancelRegistry {
+  cancels: Mutex<HashMap<String, Arc<AtomicBool>>>,
+}
+
+impl CancelRegistry {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  fn register(&self, request_id: &str) -> Arc<AtomicBool> {
+    let flag = Arc::new(AtomicBool::new(false));
+    self
+      .cancels
+      .lock()
+      .unwrap()
+      .insert(request_id.to_string(), flag.clone());
+    flag
+  }
+
+  fn unregister(&self, request_id: &str, flag: &Arc<AtomicBool>) {
+    let mut map = self.cancels.lock().unwrap();
+    // A newer run may have reused the id; only drop our own entry.
+    if map.get(request_id).is_some_and(|current| Arc::ptr_eq(current, flag)) {
+      map.remove(request_id);
+    }
+  }
+
+  fn cancel(&self, request_id: &str) -> bool {
+    match self.cancels.lock().unwrap().get(request_id) {
+      Some(flag) => {
+        flag.store(true, Ordering::SeqCst);
+        true
+      }
+      None => false,
+    }
+  }
+}
+
+#[derive(Default)]
+struct GenerationContext {
+  models: HashMap<String, String>,
+  cancel: Option<Arc<AtomicBool>>,
+}
+
+impl GenerationContext {
+  fn is_cancelled(&self) -> bool {
+    self.cancel.as_ref().is_some_and(|flag| flag.load(Ordering::SeqCst))
+  }
+}
+
+// Registers `request_id` (if any) as cancellable for the duration of `run`.
+fn with_cancellable<T>(
+  app: &tauri::AppHandle,
+  task_path: &str,
+  request_id: Option<&str>,
+  run: impl FnOnce(&mut GenerationContext) -> T,
+) -> T {
+  with_cancel_flag(app, request_id, |cancel| {
+    let mut ctx = GenerationContext {
+      models: provider_models(app, task_path),
+      cancel: cancel.cloned(),
+    };
+    run(&mut ctx)
+  })
+}
 
-  (title, description)
+/// Registers `request_id` (if any) in the shared registry and hands `run` the cancel flag.
+pub(crate) fn with_cancel_flag<T>(
+  app: &tauri::AppHandle,
+  request_id: Option<&str>,
+  run: impl FnOnce(Option<&Arc<AtomicBool>>) -> T,
+) -> T {
+  let state: tauri::State<CancelRegistry> = app.state();
+  let request_id = request_id.map(str::trim).filter(|id| !id.is_empty());
+  let cancel = request_id.map(|id| state.register(id));
+  let result = run(cancel.as_ref());
+  if let (Some(id), Some(flag)) = (request_id, cancel.as_ref()) {
+    state.unregister(id, flag);
+  }
+  result
 }
 
-fn git_generate_pr_content_sync(state: &DbState, task_path: String, base: Option<String>) -> Value {
-  let resolved_path = resolve_real_path(Path::new(&task_path));
-  let mut preferred_provider = db::task_agent_id_for_path(state, &task_path);
+// Model overrides per provider: the global `providerModels` setting, shadowed by the
+// task's `metadata.providerModels`.
+fn provider_models(app: &tauri::AppHandle, task_path: &str) -> HashMap<String, String> {
+  let mut models: HashMap<String, String> = HashMap::new();
+  let mut collect = |value: Option<&Value>| {
+    if let Some(map) = value.and_then(|v| v.as_object()) {
+      for (provider, model) in map {
+        if let Some(model) = model.as_str().map(str::trim).filter(|m| !m.is_empty()) {
+          models.insert(provider.clone(), model.to_string());
+        }
+      }
+    }
+  };
+  collect(settings::load_settings(app).get("providerModels"));
+  let state: tauri::State<DbState> = app.state();
+  let metadata = db::task_metadata_for_path(&state, task_path);
+  collect(metadata.as_ref().and_then(|m| m.get("providerModels")));
+  models
+}
+
+fn task_preferred_provider(state: &DbState, task_path: &str, resolved_path: &Path) -> Option<String> {
+  let mut preferred_provider = db::task_agent_id_for_path(state, task_path);
   if preferred_provider.is_none() {
     let resolved_str = resolved_path.to_string_lossy();
     if resolved_str != task_path {
       preferred_provider = db::task_agent_id_for_path(state, resolved_str.as_ref());
     }
   }
-  let preferred_provider = preferred_provider.and_then(|id| {
+  preferred_provider.and_then(|id| {
     let trimmed = id.trim();
     if trimmed.is_empty() {
       None
     } else {
       Some(trimmed.to_string())
     }
-  });
+  })
+}
+
+// The task's own provider first, then the `prGenerationProviders` setting in order.
+fn pr_generation_providers(app: &tauri::AppHandle, preferred: Option<String>) -> Vec<String> {
+  let settings = settings::load_settings(app);
+  let configured = settings
+    .get("prGenerationProviders")
+    .and_then(|v| v.as_array())
+    .map(|list| {
+      list
+        .iter()
+        .filter_map(|id| id.as_str().map(str::trim).map(str::to_string))
+        .collect::<Vec<String>>()
+    })
+    .unwrap_or_default();
+  let mut order: Vec<String> = Vec::new();
+  for id in preferred.into_iter().chain(configured) {
+    if providers::is_valid_provider_id(&id) && !order.contains(&id) {
+      order.push(id);
+    }
+  }
+  order
+}
+
+fn git_generate_pr_content_sync(
+  app: &tauri::AppHandle,
+  task_path: String,
+  base: Option<String>,
+  ctx: &GenerationContext,
+) -> Value {
+  let state: tauri::State<DbState> = app.state();
+  let resolved_path = resolve_real_path(Path::new(&task_path));
+  let preferred_provider = task_preferred_provider(&state, &task_path, &resolved_path);
   if let Err(err) = run_git(&resolved_path, &["rev-parse", "--is-inside-work-tree"]) {
     return json!({ "success": false, "error": err });
   }
 
-  let _ = run_git(&resolved_path, &["fetch", "origin", "--quiet"]);
+  let remote = configured_remote(&state, &task_path);
+  let _ = run_git(&resolved_path, &["fetch", remote.as_str(), "--quiet"]);
 
   let base_branch = base
     .map(|b| b.trim().to_string())
@@ -2116,9 +4515,9 @@ fn git_generate_pr_content_sync(state: &DbState, task_path: String, base: Option
     .unwrap_or_else(|| DEFAULT_BRANCH.to_string());
 
   let mut base_ref: Option<String> = None;
-  let origin_ref = format!("origin/{}", base_branch);
-  if run_git(&resolved_path, &["rev-parse", "--verify", origin_ref.as_str()]).is_ok() {
-    base_ref = Some(origin_ref);
+  let remote_ref = format!("{}/{}", remote, base_branch);
+  if run_git(&resolved_path, &["rev-parse", "--verify", remote_ref.as_str()]).is_ok() {
+    base_ref = Some(remote_ref);
   } else if run_git(&resolved_path, &["rev-parse", "--verify", base_branch.as_str()]).is_ok() {
     base_ref = Some(base_branch.clone());
   }
@@ -2197,27 +4596,20 @@ fn git_generate_pr_content_sync(state: &DbState, task_path: String, base: Option
   let has_context = !diff_for_prompt.is_empty() || !commits.is_empty();
 
   if has_context {
-    if let Some(provider_id) = preferred_provider {
-      if providers::is_valid_provider_id(&provider_id) {
-        if let Some((title, description)) =
-          generate_with_provider(&provider_id, &resolved_path, &diff_for_prompt, &commits)
-        {
-          return json!({ "success": true, "title": title, "description": description });
-        }
+    for provider_id in pr_generation_providers(app, preferred_provider) {
+      if ctx.is_cancelled() {
+        break;
+      }
+      if let Some((title, description)) =
+        generate_with_provider(&provider_id, &resolved_path, &diff_for_prompt, &commits, ctx)
+      {
+        return json!({ "success": true, "title": title, "description": description });
       }
     }
+  }
 
-    if let Some((title, description)) =
-      generate_with_provider("claude", &resolved_path, &diff_for_prompt, &commits)
-    {
-      return json!({ "success": true, "title": title, "description": description });
-    }
-
-    if let Some((title, description)) =
-      generate_with_provider("codex", &resolved_path, &diff_for_prompt, &commits)
-    {
-      return json!({ "success": true, "title": title, "description": description });
-    }
+  if ctx.is_cancelled() {
+    return json!({ "success": false, "cancelled": true, "error": "Generation cancelled" });
   }
 
   let title = generate_pr_title(&commits, &changed_files);
@@ -2227,23 +4619,251 @@ fn git_generate_pr_content_sync(state: &DbState, task_path: String, base: Option
 }
 
 #[tauri::command]
-pub async fn git_generate_pr_content(app: tauri::AppHandle, task_path: String, base: Option<String>) -> Value {
+pub async fn git_generate_pr_content(
+  app: tauri::AppHandle,
+  task_path: String,
+  base: Option<String>,
+  request_id: Option<String>,
+) -> Value {
   let fallback_path = task_path.clone();
-  run_blocking(
+  run_git_command(
     json!({
       "success": false,
       "error": "git_generate_pr_content failed",
       "taskPath": fallback_path,
     }),
     move || {
-      let state: tauri::State<DbState> = app.state();
-      git_generate_pr_content_sync(&state, task_path, base)
+      let path = task_path.clone();
+      with_cancellable(&app, &path, request_id.as_deref(), |ctx| {
+        git_generate_pr_content_sync(&app, task_path, base, ctx)
+      })
+    },
+  )
+  .await
+}
+
+fn generate_commit_message_sync(
+  app: &tauri::AppHandle,
+  task_path: &str,
+  ctx: &GenerationContext,
+) -> Result<String, String> {
+  let state: tauri::State<DbState> = app.state();
+  let resolved_path = resolve_real_path(Path::new(task_path));
+  run_git(&resolved_path, &["rev-parse", "--is-inside-work-tree"])?;
+  let preferred_provider = task_preferred_provider(&state, task_path, &resolved_path);
+
+  let mut changed_files: Vec<String> = Vec::new();
+  let mut seen: HashSet<String> = HashSet::new();
+  let mut diff = String::new();
+
+  if let Ok(output) = run_git(&resolved_path, &["diff", "--cached", "--name-only"]) {
+    add_files_from_output(&output, &mut seen, &mut changed_files);
+  }
+  if !changed_files.is_empty() {
+    diff = run_git(&resolved_path, &["diff", "--cached", "--no-color"]).unwrap_or_default();
+  } else {
+    // Nothing staged yet: describe what git_commit_and_push would stage with `add -A`.
+    if let Ok(output) = run_git(&resolved_path, &["diff", "--name-only"]) {
+      add_files_from_output(&output, &mut seen, &mut changed_files);
+    }
+    if let Ok(output) = run_git(&resolved_path, &["ls-files", "--others", "--exclude-standard"]) {
+      add_files_from_output(&output, &mut seen, &mut changed_files);
+    }
+    if let Ok(output) = run_git(&resolved_path, &["diff", "--no-color"]) {
+      diff = output;
+    }
+  }
+
+  if changed_files.is_empty() {
+    return Err("No changes to commit".to_string());
+  }
+
+  let diff = diff.trim().to_string();
+  let mut candidates: Vec<String> = Vec::new();
+  if let Some(provider_id) = preferred_provider {
+    if providers::is_valid_provider_id(&provider_id) {
+      candidates.push(provider_id);
+    }
+  }
+  for fallback in ["claude", "codex"] {
+    if !candidates.iter().any(|id| id == fallback) {
+      candidates.push(fallback.to_string());
+    }
+  }
+
+  for provider_id in candidates {
+    if let Some((subject, body)) =
+      generate_commit_message_with_provider(
+        &provider_id,
+        &resolved_path,
+        &diff,
+        &changed_files,
+        ctx,
+      )
+    {
+      if body.is_empty() {
+        return Ok(subject);
+      }
+      return Ok(format!("{}\n\n{}", subject, body));
+    }
+  }
+
+  if ctx.is_cancelled() {
+    return Err("Generation cancelled".to_string());
+  }
+
+  Ok(generate_pr_title(&[], &changed_files))
+}
+
+#[tauri::command]
+pub async fn git_generate_commit_message(
+  app: tauri::AppHandle,
+  task_path: String,
+  request_id: Option<String>,
+) -> Value {
+  let fallback_path = task_path.clone();
+  run_git_command(
+    json!({
+      "success": false,
+      "error": "git_generate_commit_message failed",
+      "taskPath": fallback_path,
+    }),
+    move || {
+      let result = with_cancellable(&app, &task_path, request_id.as_deref(), |ctx| {
+        generate_commit_message_sync(&app, &task_path, ctx)
+      });
+      match result {
+        Ok(message) => json!({ "success": true, "message": message }),
+        Err(err) => json!({ "success": false, "error": err }),
+      }
+    },
+  )
+  .await
+}
+
+fn provider_run_sync(
+  provider_id: &str,
+  task_path: &str,
+  prompt: &str,
+  model: Option<String>,
+  timeout_ms: Option<u64>,
+  ctx: &mut GenerationContext,
+) -> Result<String, String> {
+  let provider_id = provider_id.trim();
+  if !providers::is_valid_provider_id(provider_id) {
+    return Err(format!("Unknown provider: {}", provider_id));
+  }
+  let provider = provider_generation_config(provider_id)
+    .ok_or_else(|| format!("Provider {} does not support one-shot runs", provider_id))?;
+  if prompt.trim().is_empty() {
+    return Err("Prompt is required".to_string());
+  }
+  let cwd = Path::new(task_path);
+  if !cwd.is_dir() {
+    return Err("Task path not found".to_string());
+  }
+  let Some(version) = provider_cli_version(provider, cwd) else {
+    return Err(format!("{} CLI not found", provider.cli));
+  };
+  let support = provider_flag_support(provider, cwd, &version);
+
+  if let Some(model) = model.map(|m| m.trim().to_string()).filter(|m| !m.is_empty()) {
+    ctx.models.insert(provider_id.to_string(), model);
+  }
+  let timeout_ms = timeout_ms.unwrap_or(60_000).clamp(1_000, 600_000);
+  let output = run_provider_prompt(provider, cwd, prompt, ctx, timeout_ms, support)
+    .ok_or_else(|| format!("Failed to run {}", provider.cli))?;
+  if ctx.is_cancelled() {
+    return Err("Generation cancelled".to_string());
+  }
+  if output.timed_out {
+    return Err(format!("{} timed out after {}ms", provider.cli, timeout_ms));
+  }
+  if !output.success {
+    return Err(combine_output(&output.stdout, &output.stderr));
+  }
+  Ok(output.stdout)
+}
+
+#[tauri::command]
+pub async fn provider_capabilities(provider_id: String) -> Value {
+  run_blocking(
+    json!({ "success": false, "error": "Task cancelled" }),
+    move || {
+      let provider_id = provider_id.trim();
+      let Some(provider) = provider_generation_config(provider_id) else {
+        return json!({ "success": false, "error": format!("Unknown provider: {}", provider_id) });
+      };
+      let cwd = std::env::temp_dir();
+      let Some(version) = provider_cli_version(provider, &cwd) else {
+        return json!({
+          "success": false,
+          "code": "CLI_NOT_FOUND",
+          "error": format!("{} CLI not found", provider.cli)
+        });
+      };
+      let support = provider_flag_support(provider, &cwd, &version);
+      let flag = |flag: Option<&str>, supported: Option<bool>| {
+        json!({ "flag": flag.filter(|f| !f.trim().is_empty()), "supported": supported })
+      };
+      json!({
+        "success": true,
+        "providerId": provider.id,
+        "cli": provider.cli,
+        "version": version,
+        "flags": {
+          "autoApprove": flag(provider.auto_approve_flag, support.auto_approve),
+          "initialPrompt": flag(provider.initial_prompt_flag, support.initial_prompt),
+          "model": flag(provider.model_flag, support.model),
+        }
+      })
+    },
+  )
+  .await
+}
+
+#[tauri::command]
+pub async fn provider_run(
+  app: tauri::AppHandle,
+  provider_id: String,
+  task_path: String,
+  prompt: String,
+  model: Option<String>,
+  timeout_ms: Option<u64>,
+  request_id: Option<String>,
+) -> Value {
+  run_blocking(
+    json!({ "success": false, "error": "Task cancelled" }),
+    move || {
+      let result = with_cancellable(&app, &task_path, request_id.as_deref(), |ctx| {
+        provider_run_sync(&provider_id, &task_path, &prompt, model, timeout_ms, ctx)
+      });
+      match result {
+        Ok(stdout) => json!({ "success": true, "stdout": stdout }),
+        Err(err) => json!({ "success": false, "error": err }),
+      }
     },
   )
   .await
 }
 
-fn git_create_pr_sync(
+#[tauri::command]
+pub async fn provider_cancel(app: tauri::AppHandle, request_id: String) -> Value {
+  let state: tauri::State<CancelRegistry> = app.state();
+  let cancelled = state.cancel(request_id.trim());
+  json!({ "success": true, "cancelled": cancelled })
+}
+
+#[tauri::command]
+pub async fn git_cancel(app: tauri::AppHandle, request_id: String) -> Value {
+  let state: tauri::State<CancelRegistry> = app.state();
+  let cancelled = state.cancel(request_id.trim());
+  json!({ "success": true, "cancelled": cancelled })
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitCreatePrArgs {
   task_path: String,
   title: Option<String>,
   body: Option<String>,
@@ -2252,7 +4872,24 @@ fn git_create_pr_sync(
   draft: Option<bool>,
   web: Option<bool>,
   fill: Option<bool>,
-) -> Value {
+  assign_self: Option<bool>,
+  /// GitHub project title to add the PR to.
+  project: Option<String>,
+}
+
+fn git_create_pr_sync(args: GitCreatePrArgs, remote: String, signing: CommitSigning) -> Value {
+  let GitCreatePrArgs {
+    task_path,
+    title,
+    body,
+    base,
+    head,
+    draft,
+    web,
+    fill,
+    assign_self,
+    project,
+  } = args;
   let resolved_path = resolve_real_path(Path::new(&task_path));
   if let Err(err) = run_git(&resolved_path, &["rev-parse", "--is-inside-work-tree"]) {
     return json!({ "success": false, "error": err });
@@ -2272,13 +4909,16 @@ fn git_create_pr_sync(
       }
 
       let commit_msg = "stagehand: prepare pull request";
-      match run_git(&resolved_path, &["commit", "-m", commit_msg]) {
+      match run_git_commit(&resolved_path, &signing, &["-m", commit_msg]) {
         Ok(commit_out) => {
           if !commit_out.trim().is_empty() {
             outputs.push(commit_out.trim().to_string());
           }
         }
         Err(err) => {
+          if is_signing_error(&signing, &err) {
+            return signing_error_response(err);
+          }
           if err.to_lowercase().contains("nothing to commit") {
             outputs.push("git commit: nothing to commit".to_string());
           } else {
@@ -2298,15 +4938,18 @@ fn git_create_pr_sync(
         .to_string();
       if let Err(err) = run_git(
         &resolved_path,
-        &["push", "--set-upstream", "origin", branch.as_str()],
+        &["push", "--set-upstream", remote.as_str(), branch.as_str()],
       ) {
         return json!({
           "success": false,
-          "error": "Failed to push branch to origin. Please check your Git remotes and authentication.",
+          "error": format!(
+            "Failed to push branch to {}. Please check your Git remotes and authentication.",
+            remote
+          ),
           "output": err
         });
       }
-      outputs.push(format!("git push --set-upstream origin {}: success", branch));
+      outputs.push(format!("git push --set-upstream {} {}: success", remote, branch));
     }
   }
 
@@ -2320,7 +4963,7 @@ fn git_create_pr_sync(
     if !trimmed.is_empty() {
       repo_name_with_owner = trimmed.to_string();
     }
-  } else if let Ok(url_out) = run_git(&resolved_path, &["remote", "get-url", "origin"]) {
+  } else if let Ok(url_out) = run_git(&resolved_path, &["remote", "get-url", remote.as_str()]) {
     if let Some(repo) = parse_github_repo(url_out.trim()) {
       repo_name_with_owner = repo;
     }
@@ -2341,7 +4984,7 @@ fn git_create_pr_sync(
     if !trimmed.is_empty() {
       default_branch = trimmed.to_string();
     }
-  } else if let Some(db) = detect_default_branch(&resolved_path, Some(DEFAULT_REMOTE)) {
+  } else if let Some(db) = detect_default_branch(&resolved_path, Some(remote.as_str())) {
     default_branch = db;
   }
 
@@ -2351,7 +4994,8 @@ fn git_create_pr_sync(
       "rev-list",
       "--count",
       &format!(
-        "origin/{}..HEAD",
+        "{}/{}..HEAD",
+        remote,
         base.clone().unwrap_or_else(|| default_branch.clone())
       ),
     ],
@@ -2443,6 +5087,17 @@ fn git_create_pr_sync(
   if fill.unwrap_or(false) {
     args.push("--fill".to_string());
   }
+  if assign_self.unwrap_or(false) {
+    args.push("--assignee".to_string());
+    args.push("@me".to_string());
+  }
+  let project = project
+    .map(|p| p.trim().to_string())
+    .filter(|p| !p.is_empty());
+  if let Some(project) = project.as_ref() {
+    args.push("--project".to_string());
+    args.push(project.clone());
+  }
 
   let (success, stdout, stderr) = match run_cmd_output(
     "gh",
@@ -2495,6 +5150,28 @@ fn git_create_pr_sync(
       });
     }
 
+    if let Some(project) = project.as_ref() {
+      if lower.contains("missing required scopes") && lower.contains("project") {
+        return json!({
+          "success": false,
+          "code": "PROJECT_SCOPE_MISSING",
+          "error": "Adding a PR to a project needs the `project` scope. Run `gh auth refresh -s project` and try again.",
+          "output": combined
+        });
+      }
+      if lower.contains(&format!("'{}' not found", project.to_lowercase())) {
+        return json!({
+          "success": false,
+          "code": "PROJECT_NOT_FOUND",
+          "error": format!(
+            "GitHub project '{}' was not found. Check the project title and that you have access to it.",
+            project
+          ),
+          "output": combined
+        });
+      }
+    }
+
     return json!({ "success": false, "error": combined, "output": combined });
   }
 
@@ -2507,26 +5184,23 @@ fn git_create_pr_sync(
 }
 
 #[tauri::command]
-pub async fn git_create_pr(
-  task_path: String,
-  title: Option<String>,
-  body: Option<String>,
-  base: Option<String>,
-  head: Option<String>,
-  draft: Option<bool>,
-  web: Option<bool>,
-  fill: Option<bool>,
-) -> Value {
-  let fallback_path = task_path.clone();
-  run_blocking(
+pub async fn git_create_pr(app: tauri::AppHandle, args: GitCreatePrArgs) -> Value {
+  let fallback_path = args.task_path.clone();
+  run_git_command(
     json!({ "success": false, "error": "git_create_pr failed", "taskPath": fallback_path }),
-    move || git_create_pr_sync(task_path, title, body, base, head, draft, web, fill),
+    move || {
+      let state: tauri::State<DbState> = app.state();
+      let remote = configured_remote(&state, &args.task_path);
+      let signing = commit_signing(&app);
+      git_create_pr_sync(args, remote, signing)
+    },
   )
   .await
 }
 
 fn git_merge_pr_sync(
   task_path: String,
+  remote: String,
   method: Option<String>,
   delete_branch: Option<bool>,
   pr_number: Option<i64>,
@@ -2593,22 +5267,477 @@ fn git_merge_pr_sync(
     return json!({ "success": false, "error": combined, "output": combined });
   }
 
-  let pr_status = git_get_pr_status_sync(task_path);
+  let pr_status = git_get_pr_status_sync(task_path, remote);
   let pr_value = pr_status.get("pr").cloned();
   json!({ "success": true, "output": combined, "pr": pr_value })
 }
 
 #[tauri::command]
 pub async fn git_merge_pr(
+  app: tauri::AppHandle,
   task_path: String,
   method: Option<String>,
   delete_branch: Option<bool>,
   pr_number: Option<i64>,
 ) -> Value {
   let fallback_path = task_path.clone();
-  run_blocking(
+  run_git_command(
     json!({ "success": false, "error": "git_merge_pr failed", "taskPath": fallback_path }),
-    move || git_merge_pr_sync(task_path, method, delete_branch, pr_number),
+    move || {
+      let state: tauri::State<DbState> = app.state();
+      let remote = configured_remote(&state, &task_path);
+      git_merge_pr_sync(task_path, remote, method, delete_branch, pr_number)
+    },
   )
   .await
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_support::{remove_temp_repo, temp_repo};
+
+  fn commit_all(repo: &Path) {
+    run_git(repo, &["add", "-A"]).unwrap();
+    run_git(repo, &["commit", "-q", "-m", "seed"]).unwrap();
+  }
+
+  fn diff_lines_have_no_cr(diff: &Value) -> bool {
+    diff["lines"].as_array().unwrap().iter().all(|line| {
+      ["left", "right"]
+        .iter()
+        .all(|side| !line[side].as_str().unwrap_or("").contains('\r'))
+    })
+  }
+
+  #[test]
+  fn crlf_file_diff_reports_crlf_without_stray_carriage_returns() {
+    let repo = temp_repo("crlf-diff");
+    fs::write(repo.join("a.txt"), "one\r\ntwo\r\nthree\r\n").unwrap();
+    commit_all(&repo);
+    fs::write(repo.join("a.txt"), "one\r\nTWO\r\nthree\r\n").unwrap();
+    fs::write(repo.join("new.txt"), "fresh\r\nfile\r\n").unwrap();
+
+    let repo_str = repo.to_string_lossy().to_string();
+    let modified = file_diff_lines_sync(repo_str.clone(), "a.txt".to_string(), "head");
+    assert_eq!(modified["diff"]["crlf"], json!(true));
+    assert!(diff_lines_have_no_cr(&modified["diff"]));
+    let kinds: Vec<&str> = modified["diff"]["lines"]
+      .as_array()
+      .unwrap()
+      .iter()
+      .filter_map(|line| line["type"].as_str())
+      .collect();
+    assert!(kinds.contains(&"add") && kinds.contains(&"del"));
+
+    let untracked = file_diff_lines_sync(repo_str, "new.txt".to_string(), "head");
+    assert_eq!(untracked["diff"]["crlf"], json!(true));
+    assert!(diff_lines_have_no_cr(&untracked["diff"]));
+
+    remove_temp_repo(&repo);
+  }
+
+  #[test]
+  fn status_numstats_run_concurrently() {
+    use std::sync::Condvar;
+    use std::time::Duration;
+    // Each call checks in and then waits for the other two. Run one after another, the
+    // first call would never see the rest arrive and would give up at the timeout.
+    let arrived = (Mutex::new(0usize), Condvar::new());
+    let rendezvous = || {
+      let (count, all_in) = &arrived;
+      let mut count = count.lock().unwrap();
+      *count += 1;
+      all_in.notify_all();
+      let (count, _) = all_in
+        .wait_timeout_while(count, Duration::from_secs(10), |count| *count < 3)
+        .unwrap();
+      *count == 3
+    };
+
+    let (staged, unstaged, other) = parallel_numstats(
+      |args| {
+        assert!(rendezvous(), "{:?} did not overlap with the other calls", args);
+        Some(if args.contains(&"--cached") { "1\t2\tstaged.txt" } else { "3\t4\twork.txt" }.to_string())
+      },
+      || {
+        assert!(rendezvous(), "the alongside call did not overlap with the numstats");
+        7
+      },
+    );
+
+    assert_eq!(staged.get("staged.txt"), Some(&(1, 2)));
+    assert_eq!(unstaged.get("work.txt"), Some(&(3, 4)));
+    assert_eq!(other, 7);
+  }
+
+  #[test]
+  fn actions_checks_are_told_apart_from_external_ci() {
+    let cases = [
+      (json!({ "__typename": "CheckRun", "name": "build", "workflowName": "CI" }), true),
+      (
+        json!({
+          "__typename": "CheckRun",
+          "name": "lint",
+          "detailsUrl": "https://github.com/o/r/actions/runs/42/job/7"
+        }),
+        true,
+      ),
+      (json!({ "name": "test", "workflowName": "CI" }), true),
+      (
+        json!({
+          "__typename": "CheckRun",
+          "name": "codecov/patch",
+          "workflowName": "",
+          "detailsUrl": "https://app.codecov.io/gh/o/r/pull/1"
+        }),
+        false,
+      ),
+      (
+        json!({
+          "__typename": "StatusContext",
+          "context": "ci/circleci",
+          "state": "FAILURE",
+          "targetUrl": "https://circleci.com/gh/o/r/1"
+        }),
+        false,
+      ),
+      (
+        json!({
+          "__typename": "StatusContext",
+          "context": "legacy",
+          "detailsUrl": "https://github.com/o/r/actions/runs/42"
+        }),
+        false,
+      ),
+    ];
+    for (item, expected) in cases {
+      assert_eq!(is_actions_check(&item), expected, "{}", item);
+    }
+  }
+
+  #[test]
+  fn reviewers_keep_their_latest_decision() {
+    let review = |login: &str, state: &str, at: &str| {
+      json!({ "author": { "login": login }, "state": state, "submittedAt": at })
+    };
+    let data = json!({
+      "reviews": [
+        review("carol", "COMMENTED", "2024-01-01T09:00:00Z"),
+        review("alice", "CHANGES_REQUESTED", "2024-01-01T10:00:00Z"),
+        review("bob", "APPROVED", "2024-01-01T11:00:00Z"),
+        review("bob", "COMMENTED", "2024-01-01T12:00:00Z"),
+        review("alice", "APPROVED", "2024-01-01T13:00:00Z"),
+        review("alice", "COMMENTED", "2024-01-01T14:00:00Z"),
+        // Out of order: an older decision doesn't replace a newer one.
+        review("bob", "CHANGES_REQUESTED", "2024-01-01T10:30:00Z"),
+        review("carol", "PENDING", "2024-01-01T15:00:00Z"),
+        review("dave", "PENDING", "2024-01-01T15:00:00Z"),
+        json!({ "author": { "login": " " }, "state": "APPROVED" }),
+      ]
+    });
+
+    let summary: Vec<(String, String)> = summarize_reviewers(&data)
+      .iter()
+      .map(|r| {
+        (
+          r["login"].as_str().unwrap().to_string(),
+          r["state"].as_str().unwrap().to_string(),
+        )
+      })
+      .collect();
+    assert_eq!(
+      summary,
+      vec![
+        ("carol".to_string(), "COMMENTED".to_string()),
+        ("alice".to_string(), "APPROVED".to_string()),
+        ("bob".to_string(), "APPROVED".to_string()),
+      ]
+    );
+    assert!(summarize_reviewers(&json!({})).is_empty());
+  }
+
+  #[test]
+  fn blame_porcelain_yields_one_entry_per_line() {
+    let sha = "1234567890abcdef1234567890abcdef12345678";
+    let zero = "0000000000000000000000000000000000000000";
+    let entry = |commit: &str, line: u64, author: &str, time: Option<i64>, summary: &str| {
+      json!({
+        "lineNo": line,
+        "commit": commit,
+        "author": author,
+        "authorTime": time,
+        "summary": summary,
+        "uncommitted": commit == zero,
+      })
+    };
+    let committed = format!(
+      "{sha} 3 10 1\nauthor Ada\nauthor-mail <ada@x>\nauthor-time 1700000000\n\
+       summary Add parser\nfilename src/lib.rs\n\tfn parse() {{}}\n"
+    );
+    let uncommitted = format!(
+      "{zero} 1 1 1\nauthor Not Committed Yet\nauthor-time 1700000100\n\
+       summary Version of src/lib.rs\nfilename src/lib.rs\n\t// wip\n"
+    );
+    // Content that looks like a header line is still just content.
+    let header_like_content = format!("{sha} 5 12 1\nauthor Bob\nsummary Fix\n\t{zero} 1 1 1\n");
+    let two_lines = format!(
+      "{sha} 1 1 2\nauthor Ada\nsummary One\n\tfirst\n\
+       {sha} 2 2\nauthor Ada\nsummary One\n\tsecond\n"
+    );
+    let cases = [
+      (String::new(), vec![]),
+      ("not a blame header\n\tcontent\n".to_string(), vec![]),
+      (committed, vec![entry(sha, 10, "Ada", Some(1_700_000_000), "Add parser")]),
+      (
+        uncommitted,
+        vec![entry(zero, 1, "Not Committed Yet", Some(1_700_000_100), "Version of src/lib.rs")],
+      ),
+      (header_like_content, vec![entry(sha, 12, "Bob", None, "Fix")]),
+      (two_lines, vec![entry(sha, 1, "Ada", None, "One"), entry(sha, 2, "Ada", None, "One")]),
+    ];
+    for (output, expected) in cases {
+      assert_eq!(parse_blame_porcelain(&output), expected, "{:?}", output);
+    }
+  }
+
+  #[test]
+  fn help_flag_matching_respects_token_boundaries() {
+    let help = "Usage: goose run [OPTIONS]\n  -t, --text <TEXT>  Prompt text\n  --no-session\n";
+    assert!(help_lists_options(help));
+    assert!(help_mentions_flag(help, "-t"));
+    assert!(help_mentions_flag(help, "--text"));
+    assert!(!help_mentions_flag(help, "--session"));
+    assert!(!help_mentions_flag(help, "-y"));
+    assert!(!help_lists_options("goose 1.0.0\nRun `goose help` for usage."));
+  }
+
+  #[test]
+  fn missing_cwd_is_not_reported_as_missing_binary() {
+    let gone = std::env::temp_dir().join(format!("emdash-missing-{}", std::process::id()));
+    let err = run_cmd("gh", &["--version"], Some(&gone)).unwrap_err();
+    assert!(err.starts_with(PATH_NOT_FOUND), "{}", err);
+    assert_eq!(classify_git_error(&err), GitErrorCode::PathNotFound);
+
+    let err = run_cmd("emdash-no-such-binary", &[], None).unwrap_err();
+    assert!(err.contains("is not installed"), "{}", err);
+  }
+
+  #[test]
+  fn conflict_classification_matches_git_markers_only() {
+    let conflict = "CONFLICT (content): Merge conflict in a.txt\nAutomatic merge failed; fix conflicts and then commit the result.";
+    assert_eq!(classify_git_error(conflict), GitErrorCode::Conflict);
+    assert_eq!(
+      classify_git_error("error: Committing is not possible because you have unmerged files."),
+      GitErrorCode::Conflict
+    );
+    assert_eq!(
+      classify_git_error("error: pathspec 'fix-conflict-banner' did not match any file(s) known to git"),
+      GitErrorCode::Unknown
+    );
+    assert_eq!(classify_git_error("Merge made, no conflicts"), GitErrorCode::Unknown);
+  }
+
+  #[test]
+  fn dns_failures_are_network_errors_not_auth() {
+    let dns = "ssh: Could not resolve hostname github-work: Name or service not known\nfatal: Could not read from remote repository.";
+    assert_eq!(classify_git_error(dns), GitErrorCode::Network);
+    let auth = "git@github.com: Permission denied (publickey).\nfatal: Could not read from remote repository.";
+    assert_eq!(classify_git_error(auth), GitErrorCode::AuthFailed);
+  }
+
+  #[test]
+  fn discard_all_requires_head_token_and_keeps_task_metadata() {
+    let repo = temp_repo("discard-all");
+    fs::write(repo.join("tracked.txt"), "original\n").unwrap();
+    fs::create_dir_all(repo.join(".emdash")).unwrap();
+    fs::write(repo.join(".emdash").join("task.json"), "{}\n").unwrap();
+    commit_all(&repo);
+    let head = run_git(&repo, &["rev-parse", "--short", "HEAD"]).unwrap().trim().to_string();
+
+    fs::write(repo.join("tracked.txt"), "modified\n").unwrap();
+    fs::write(repo.join("staged new.txt"), "staged\n").unwrap();
+    run_git(&repo, &["add", "staged new.txt"]).unwrap();
+    fs::create_dir_all(repo.join("scratch")).unwrap();
+    fs::write(repo.join("scratch").join("untracked.txt"), "untracked\n").unwrap();
+    fs::write(repo.join(".emdash").join("task.json"), "{\"changed\":true}\n").unwrap();
+    fs::write(repo.join(".emdash").join("notes.md"), "notes\n").unwrap();
+    fs::write(repo.join("codex-stream.log"), "stream\n").unwrap();
+    let repo_str = repo.to_string_lossy().to_string();
+
+    let refused = git_discard_all_sync(repo_str.clone(), "0000000".to_string());
+    assert_eq!(refused["success"], json!(false));
+    assert_eq!(refused["code"], json!("CONFIRM_TOKEN_MISMATCH"));
+    assert_eq!(fs::read_to_string(repo.join("tracked.txt")).unwrap(), "modified\n");
+    assert!(repo.join("staged new.txt").exists());
+
+    let result = git_discard_all_sync(repo_str, head.clone());
+    assert_eq!(result["success"], json!(true), "{}", result);
+    assert_eq!(
+      result["discarded"],
+      json!(["scratch/untracked.txt", "staged new.txt", "tracked.txt"])
+    );
+    assert_eq!(fs::read_to_string(repo.join("tracked.txt")).unwrap(), "original\n");
+    assert!(!repo.join("staged new.txt").exists());
+    assert!(!repo.join("scratch").exists());
+    assert_eq!(
+      fs::read_to_string(repo.join(".emdash").join("task.json")).unwrap(),
+      "{\"changed\":true}\n"
+    );
+    assert!(repo.join(".emdash").join("notes.md").exists());
+    assert!(repo.join("codex-stream.log").exists());
+    remove_temp_repo(&repo);
+  }
+
+  #[test]
+  fn export_patch_resolves_relative_output_against_task() {
+    let repo = temp_repo("export-patch");
+    fs::write(repo.join("a.txt"), "one\n").unwrap();
+    commit_all(&repo);
+    run_git(&repo, &["branch", "patch-base"]).unwrap();
+    fs::write(repo.join("a.txt"), "two\n").unwrap();
+    commit_all(&repo);
+    fs::create_dir_all(repo.join("out")).unwrap();
+
+    let result = git_export_patch_sync(
+      repo.to_string_lossy().to_string(),
+      DEFAULT_REMOTE.to_string(),
+      Some("patch-base".to_string()),
+      Some("out/changes.patch".to_string()),
+    );
+    assert_eq!(result["success"], json!(true), "{}", result);
+    let written = resolve_real_path(&repo).join("out").join("changes.patch");
+    assert_eq!(result["path"], json!(written.to_string_lossy()));
+    assert_eq!(result["empty"], json!(false));
+    assert_eq!(result["size"], json!(fs::metadata(&written).unwrap().len()));
+    assert!(fs::read_to_string(&written).unwrap().contains("+two"));
+    remove_temp_repo(&repo);
+  }
+
+  #[test]
+  fn unknown_revisions_are_not_missing_remote_refs() {
+    let local = "fatal: ambiguous argument 'HEAD~3': unknown revision or path not in the working tree.";
+    assert_eq!(classify_git_error(local), GitErrorCode::Unknown);
+    let remote = "fatal: couldn't find remote ref feature/gone";
+    assert_eq!(classify_git_error(remote), GitErrorCode::RemoteRefMissing);
+  }
+
+  #[test]
+  fn git_info_for_plain_directory_is_not_an_error() {
+    let dir = std::env::temp_dir().join(format!("emdash-not-a-repo-{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(&dir).unwrap();
+    let info = git_get_info_sync(dir.to_string_lossy().to_string(), DEFAULT_REMOTE.to_string());
+    assert_eq!(info["isGitRepo"], json!(false));
+    assert!(info.get("success").is_none(), "{}", info);
+    assert!(info.get("code").is_none(), "{}", info);
+    let _ = fs::remove_dir_all(&dir);
+  }
+
+  #[test]
+  fn branch_diff_skips_binary_and_oversized_patches() {
+    let repo = temp_repo("branch-diff");
+    fs::write(repo.join("small.txt"), "a\nb\n").unwrap();
+    fs::write(repo.join("gone.txt"), "bye\n").unwrap();
+    commit_all(&repo);
+    run_git(&repo, &["branch", "base"]).unwrap();
+
+    fs::write(repo.join("small.txt"), "a\nB\nc\n").unwrap();
+    fs::remove_file(repo.join("gone.txt")).unwrap();
+    fs::write(repo.join("star*name.txt"), "literal\n").unwrap();
+    fs::write(repo.join("blob.bin"), [0u8, 1, 2, 0, 255]).unwrap();
+    fs::write(repo.join("big.txt"), "x\n".repeat(BRANCH_DIFF_MAX_FILE_BYTES)).unwrap();
+    commit_all(&repo);
+
+    let result = branch_diff(&repo, "base").unwrap();
+    let expected = run_git(&repo, &["diff", "--shortstat", "base...HEAD"]).unwrap();
+    assert_eq!(result["shortstat"].as_str().unwrap(), expected.trim());
+
+    let files = result["files"].as_array().unwrap();
+    let file = |path: &str| files.iter().find(|f| f["path"] == path).unwrap().clone();
+    assert_eq!(files.len(), 5);
+    assert!(file("small.txt")["diff"]["lines"].as_array().is_some_and(|l| !l.is_empty()));
+    assert_eq!(file("gone.txt")["status"], "deleted");
+    assert!(file("star*name.txt")["diff"]["lines"].is_array());
+    assert_eq!(file("blob.bin")["binary"], true);
+    assert!(file("blob.bin")["diff"].is_null());
+    assert_eq!(file("big.txt")["tooLarge"], true);
+    assert!(file("big.txt")["diff"].is_null());
+
+    remove_temp_repo(&repo);
+  }
+
+  #[test]
+  fn git_bin_is_detected_once_and_honours_the_override() {
+    let cache = Mutex::new(None);
+    assert_eq!(cached_git_bin(&cache, || "/first/git".to_string()), "/first/git");
+    assert_eq!(
+      cached_git_bin(&cache, || panic!("detected again")),
+      "/first/git",
+      "cached value is kept"
+    );
+    *cache.lock().unwrap() = None;
+    assert_eq!(cached_git_bin(&cache, || "/second/git".to_string()), "/second/git");
+
+    assert_eq!(git_bin_from_override(Some(" /custom/git ".to_string())), "/custom/git");
+    let fallback = git_bin_from_override(None);
+    assert!(!fallback.is_empty());
+    assert_eq!(git_bin_from_override(Some("  ".to_string())), fallback);
+  }
+
+  #[test]
+  fn commit_diff_is_split_per_file() {
+    let repo = temp_repo("commit-diff");
+    fs::write(repo.join("a.txt"), "one\ntwo\n").unwrap();
+    fs::write(repo.join("gone.txt"), "bye\n").unwrap();
+    commit_all(&repo);
+    fs::write(repo.join("a.txt"), "one\nTWO\n").unwrap();
+    fs::remove_file(repo.join("gone.txt")).unwrap();
+    fs::write(repo.join("new file.txt"), "hi\n").unwrap();
+    commit_all(&repo);
+    let sha = run_git(&repo, &["rev-parse", "HEAD"]).unwrap().trim().to_string();
+
+    let result = git_get_commit_diff_sync(repo.to_string_lossy().to_string(), sha, None);
+    assert_eq!(result["success"], json!(true));
+    let files = result["files"].as_array().unwrap();
+    let paths: Vec<&str> = files.iter().filter_map(|f| f["path"].as_str()).collect();
+    assert_eq!(paths, vec!["a.txt", "gone.txt", "new file.txt"]);
+    let kinds = |idx: usize| -> Vec<&str> {
+      files[idx]["lines"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|line| line["type"].as_str())
+        .collect()
+    };
+    assert_eq!(kinds(0), vec!["context", "del", "add"]);
+    assert_eq!(kinds(1), vec!["del"]);
+    assert_eq!(kinds(2), vec!["add"]);
+    assert_eq!(result["summary"]["files"], json!(3));
+
+    remove_temp_repo(&repo);
+  }
+
+  #[test]
+  fn split_commit_diff_handles_renames_and_binaries() {
+    let output = "diff --git a/old.rs b/new.rs\n\
+similarity index 90%\n\
+rename from old.rs\n\
+rename to new.rs\n\
+--- a/old.rs\n\
++++ b/new.rs\n\
+@@ -1 +1 @@\n\
+-a\n\
++b\n\
+diff --git a/img.png b/img.png\n\
+index 1..2 100644\n\
+Binary files a/img.png and b/img.png differ\n";
+    let files = split_commit_diff(output);
+    assert_eq!(files.len(), 2);
+    assert_eq!(files[0].path, "new.rs");
+    assert_eq!(files[0].old_path.as_deref(), Some("old.rs"));
+    assert_eq!(files[0].body, "@@ -1 +1 @@\n-a\n+b\n");
+    assert_eq!(files[1].path, "img.png");
+    assert!(files[1].binary);
+    assert!(files[1].body.is_empty());
+  }
+}
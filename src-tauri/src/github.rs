@@ -2,6 +2,7 @@ use chrono::Utc;
 use serde::Deserialize;
 use serde_json::{json, Value};
 use std::fs;
+use std::io::Read;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 use std::sync::{
@@ -11,6 +12,9 @@ use std::sync::{
 use std::time::{Duration, Instant};
 use tauri::{AppHandle, Emitter, Manager};
 
+use crate::db::{self, DbState};
+use crate::git;
+use crate::http::http_agent;
 use crate::runtime::run_blocking;
 use crate::settings;
 use crate::worktree::{self, WorktreeCreateFromBranchArgs, WorktreeState};
@@ -27,6 +31,36 @@ fn github_oauth_client_id() -> Result<String, String> {
   Ok(override_id.unwrap_or_else(|| DEFAULT_GITHUB_OAUTH_CLIENT_ID.to_string()))
 }
 
+const KEYRING_SERVICE: &str = "emdash-github";
+const KEYRING_ACCOUNT: &str = "oauth-token";
+
+fn keyring_entry() -> Result<keyring::Entry, String> {
+  keyring::Entry::new(KEYRING_SERVICE, KEYRING_ACCOUNT).map_err(|err| err.to_string())
+}
+
+pub(crate) fn get_stored_token() -> Result<Option<String>, String> {
+  let entry = keyring_entry()?;
+  match entry.get_password() {
+    Ok(token) => Ok(Some(token)),
+    Err(keyring::Error::NoEntry) => Ok(None),
+    Err(err) => Err(err.to_string()),
+  }
+}
+
+fn store_token(token: &str) -> Result<(), String> {
+  let entry = keyring_entry()?;
+  entry.set_password(token).map_err(|err| err.to_string())
+}
+
+fn clear_stored_token() -> Result<(), String> {
+  let entry = keyring_entry()?;
+  match entry.delete_password() {
+    Ok(_) => Ok(()),
+    Err(keyring::Error::NoEntry) => Ok(()),
+    Err(err) => Err(err.to_string()),
+  }
+}
+
 #[derive(Default)]
 pub struct GitHubState {
   cancel_flag: Arc<Mutex<Option<Arc<AtomicBool>>>>,
@@ -94,6 +128,63 @@ fn run_command(command: &str, args: &[&str], cwd: Option<&Path>) -> Result<Strin
   }
 }
 
+const RATE_LIMIT_ATTEMPTS: u32 = 3;
+const RATE_LIMIT_BASE_DELAY_MS: u64 = 1_000;
+const RATE_LIMIT_DEFAULT_RETRY_MS: u64 = 60_000;
+
+fn is_rate_limit_error(message: &str) -> bool {
+  let msg = message.to_lowercase();
+  msg.contains("rate limit") || msg.contains("abuse detection") || msg.contains("http 429")
+}
+
+fn retry_after_ms(message: &str) -> u64 {
+  let msg = message.to_lowercase();
+  msg
+    .find("retry-after:")
+    .map(|idx| idx + "retry-after:".len())
+    .or_else(|| msg.find("retry after").map(|idx| idx + "retry after".len()))
+    .and_then(|start| {
+      msg[start..]
+        .trim_start()
+        .split(|c: char| !c.is_ascii_digit())
+        .next()
+        .and_then(|digits| digits.parse::<u64>().ok())
+    })
+    .map(|secs| secs * 1000)
+    .unwrap_or(RATE_LIMIT_DEFAULT_RETRY_MS)
+}
+
+fn rate_limited_response(err: &str) -> Value {
+  json!({
+    "success": false,
+    "code": "RATE_LIMITED",
+    "error": "GitHub rate limit exceeded. Please wait a moment and try again.",
+    "retryAfterMs": retry_after_ms(err),
+    "output": err
+  })
+}
+
+fn gh_error_response(err: String) -> Value {
+  if is_rate_limit_error(&err) {
+    return rate_limited_response(&err);
+  }
+  json!({ "success": false, "error": err })
+}
+
+// Retries on rate limits, so only use this for idempotent `gh` calls.
+fn run_gh_read(args: &[&str], cwd: Option<&Path>) -> Result<String, String> {
+  let mut attempt = 0;
+  loop {
+    match run_command("gh", args, cwd) {
+      Err(err) if is_rate_limit_error(&err) && attempt + 1 < RATE_LIMIT_ATTEMPTS => {
+        std::thread::sleep(Duration::from_millis(RATE_LIMIT_BASE_DELAY_MS << attempt));
+        attempt += 1;
+      }
+      result => return result,
+    }
+  }
+}
+
 fn slugify(name: &str) -> String {
   let mut out = String::new();
   for ch in name.to_lowercase().chars() {
@@ -119,11 +210,56 @@ fn slugify(name: &str) -> String {
   cleaned.trim_matches('-').to_string()
 }
 
+fn local_branch_tip(project_path: &Path, branch: &str) -> Option<String> {
+  run_command(
+    "git",
+    &["rev-parse", "--verify", "--quiet", &format!("refs/heads/{}", branch)],
+    Some(project_path),
+  )
+  .ok()
+  .map(|s| s.trim().to_string())
+  .filter(|s| !s.is_empty())
+}
+
+// Makes the PR head commit available locally so it can be compared against.
+fn pull_request_head(project_path: &Path, pr_number: i64, remote: &str) -> Result<String, String> {
+  let pr_str = pr_number.to_string();
+  let stdout = run_gh_read(
+    &["pr", "view", pr_str.as_str(), "--json", "headRefOid", "--jq", ".headRefOid"],
+    Some(project_path),
+  )?;
+  let oid = stdout.trim().to_string();
+  if oid.is_empty() {
+    return Err("Could not resolve pull request head".to_string());
+  }
+  let spec = format!("{}^{{commit}}", oid);
+  if run_command("git", &["cat-file", "-e", &spec], Some(project_path)).is_err() {
+    run_command(
+      "git",
+      &["fetch", remote, &format!("refs/pull/{}/head", pr_number)],
+      Some(project_path),
+    )?;
+  }
+  Ok(oid)
+}
+
+/// Count of commits on the local branch that the PR head doesn't have.
+fn local_commits_ahead(project_path: &Path, branch: &str, head: &str) -> Result<u64, String> {
+  let range = format!("{}..refs/heads/{}", head, branch);
+  let stdout = run_command("git", &["rev-list", "--count", &range], Some(project_path))?;
+  stdout
+    .trim()
+    .parse::<u64>()
+    .map_err(|err| err.to_string())
+}
+
 fn ensure_pull_request_branch(
   project_path: &Path,
   pr_number: i64,
   branch_name: &str,
-) -> Result<String, String> {
+  remote: &str,
+  force: bool,
+) -> Result<String, Value> {
   let previous = run_command("git", &["rev-parse", "--abbrev-ref", "HEAD"], Some(project_path))
     .ok()
     .map(|s| s.trim().to_string());
@@ -135,6 +271,43 @@ fn ensure_pull_request_branch(
     branch_name.to_string()
   };
 
+  // `gh pr checkout --force` resets an existing branch to the PR head, so refuse when
+  // that would drop local commits unless the caller explicitly asked for it.
+  if !force && local_branch_tip(project_path, &safe_branch).is_some() {
+    let ahead = pull_request_head(project_path, pr_number, remote)
+      .and_then(|head| local_commits_ahead(project_path, &safe_branch, &head));
+    match ahead {
+      Ok(0) => {}
+      Ok(ahead) => {
+        return Err(json!({
+          "success": false,
+          "code": "LOCAL_DIVERGED",
+          "error": format!(
+            "Local branch {} has {} commit(s) not in the pull request",
+            safe_branch, ahead
+          ),
+          "branchName": safe_branch,
+          "ahead": ahead
+        }));
+      }
+      Err(err) => {
+        if is_rate_limit_error(&err) {
+          return Err(rate_limited_response(&err));
+        }
+        return Err(json!({
+          "success": false,
+          "code": "COMPARE_FAILED",
+          "error": format!(
+            "Could not compare local branch {} with the pull request: {}",
+            safe_branch,
+            err.trim()
+          ),
+          "branchName": safe_branch
+        }));
+      }
+    }
+  }
+
   run_command(
     "gh",
     &[
@@ -146,7 +319,8 @@ fn ensure_pull_request_branch(
       "--force",
     ],
     Some(project_path),
-  )?;
+  )
+  .map_err(gh_error_response)?;
 
   if let Some(prev) = previous {
     if prev != safe_branch {
@@ -249,7 +423,8 @@ fn request_device_code() -> Result<DeviceCodeResponse, String> {
     urlencoding::encode(&client_id),
     urlencoding::encode(SCOPES)
   );
-  let response = ureq::post("https://github.com/login/device/code")
+  let response = http_agent()
+    .post("https://github.com/login/device/code")
     .set("Accept", "application/json")
     .set("Content-Type", "application/x-www-form-urlencoded")
     .send_string(&body)
@@ -266,7 +441,8 @@ fn poll_device_token(device_code: &str) -> Result<TokenResponse, String> {
     urlencoding::encode(&client_id),
     urlencoding::encode(device_code)
   );
-  let response = ureq::post("https://github.com/login/oauth/access_token")
+  let response = http_agent()
+    .post("https://github.com/login/oauth/access_token")
     .set("Accept", "application/json")
     .set("Content-Type", "application/x-www-form-urlencoded")
     .send_string(&body)
@@ -429,13 +605,15 @@ pub async fn github_auth(app: AppHandle) -> Value {
 
           if let Some(access_token) = token.access_token.clone() {
             let _ = gh_auth_login(&access_token);
+            let keyring_error = store_token(&access_token).err();
             let user = gh_api_user().ok();
             emit(
               &app_handle,
               "github:auth:success",
               json!({
                 "token": access_token,
-                "user": user
+                "user": user,
+                "keyringError": keyring_error
               }),
             );
             emit(
@@ -570,9 +748,8 @@ pub async fn github_get_user() -> Value {
 
 #[tauri::command]
 pub async fn github_get_repositories() -> Value {
-  run_blocking(json!([]), || {
-    let stdout = match run_command(
-      "gh",
+  run_blocking(json!({ "success": false, "error": "Task cancelled", "repositories": [] }), || {
+    let stdout = match run_gh_read(
       &[
         "repo",
         "list",
@@ -584,7 +761,11 @@ pub async fn github_get_repositories() -> Value {
       None,
     ) {
       Ok(out) => out,
-      Err(_) => return json!([]),
+      Err(err) => {
+        let mut response = gh_error_response(err);
+        response["repositories"] = json!([]);
+        return response;
+      }
     };
 
     let parsed: Value = serde_json::from_str(&stdout).unwrap_or_else(|_| json!([]));
@@ -622,7 +803,7 @@ pub async fn github_get_repositories() -> Value {
       })
       .collect();
 
-    Value::Array(mapped)
+    json!({ "success": true, "repositories": mapped })
   })
   .await
 }
@@ -667,8 +848,87 @@ pub async fn github_connect(project_path: String) -> Value {
   .await
 }
 
+fn resolve_clone_url(repo_url: &str, use_ssh: bool) -> String {
+  let trimmed = repo_url.trim();
+  if !use_ssh || !trimmed.to_lowercase().starts_with("https://") {
+    return trimmed.to_string();
+  }
+  match git::parse_github_repo(trimmed) {
+    Some(repo) => format!("git@github.com:{}.git", repo),
+    None => trimmed.to_string(),
+  }
+}
+
+fn run_clone_with_progress(
+  app: &AppHandle,
+  clone_url: &str,
+  local: &Path,
+) -> Result<(), String> {
+  let local_str = local.to_string_lossy().to_string();
+  let mut child = Command::new("git")
+    .args(["clone", "--progress", clone_url, &local_str])
+    .stdout(Stdio::null())
+    .stderr(Stdio::piped())
+    .spawn()
+    .map_err(|err| err.to_string())?;
+
+  let mut last_lines: Vec<String> = Vec::new();
+  if let Some(mut stderr) = child.stderr.take() {
+    let mut pending: Vec<u8> = Vec::new();
+    let mut chunk = [0u8; 4096];
+    loop {
+      let read = match stderr.read(&mut chunk) {
+        Ok(0) | Err(_) => break,
+        Ok(n) => n,
+      };
+      for byte in &chunk[..read] {
+        if *byte == b'\r' || *byte == b'\n' {
+          let line = String::from_utf8_lossy(&pending).trim().to_string();
+          pending.clear();
+          if line.is_empty() {
+            continue;
+          }
+          emit(
+            app,
+            "github:clone:progress",
+            json!({ "url": clone_url, "localPath": local_str, "line": line }),
+          );
+          last_lines.push(line);
+          if last_lines.len() > 20 {
+            last_lines.remove(0);
+          }
+        } else {
+          pending.push(*byte);
+        }
+      }
+    }
+    let rest = String::from_utf8_lossy(&pending).trim().to_string();
+    if !rest.is_empty() {
+      last_lines.push(rest);
+    }
+  }
+
+  let status = child.wait().map_err(|err| err.to_string())?;
+  if status.success() {
+    return Ok(());
+  }
+  let detail = last_lines
+    .iter()
+    .rev()
+    .find(|line| line.starts_with("fatal:") || line.starts_with("error:"))
+    .or_else(|| last_lines.last())
+    .cloned()
+    .unwrap_or_else(|| "git clone failed".to_string());
+  Err(detail)
+}
+
 #[tauri::command]
-pub async fn github_clone_repository(repo_url: String, local_path: String) -> Value {
+pub async fn github_clone_repository(
+  app: AppHandle,
+  repo_url: String,
+  local_path: String,
+  use_ssh: Option<bool>,
+) -> Value {
   run_blocking(
     json!({ "success": false, "error": "Task cancelled" }),
     move || {
@@ -677,8 +937,16 @@ pub async fn github_clone_repository(repo_url: String, local_path: String) -> Va
         let _ = fs::create_dir_all(parent);
       }
 
-      match run_command("git", &["clone", &repo_url, local.to_str().unwrap_or("")], None) {
-        Ok(_) => json!({ "success": true }),
+      let clone_url = resolve_clone_url(&repo_url, use_ssh.unwrap_or(false));
+      if clone_url.to_lowercase().starts_with("https://") && gh_auth_status() {
+        let _ = run_command("gh", &["auth", "setup-git"], None);
+      }
+
+      match run_clone_with_progress(&app, &clone_url, &local) {
+        Ok(()) => {
+          let resolved = fs::canonicalize(&local).unwrap_or(local);
+          json!({ "success": true, "path": resolved.to_string_lossy() })
+        }
         Err(err) => json!({ "success": false, "error": err }),
       }
     },
@@ -697,8 +965,7 @@ pub async fn github_issues_list(project_path: String, limit: Option<u64>) -> Val
         return json!({ "success": true, "issues": [] });
       }
 
-      let stdout = match run_command(
-        "gh",
+      let stdout = match run_gh_read(
         &[
           "issue",
           "list",
@@ -712,7 +979,7 @@ pub async fn github_issues_list(project_path: String, limit: Option<u64>) -> Val
         Some(path),
       ) {
         Ok(out) => out,
-        Err(err) => return json!({ "success": false, "error": err }),
+        Err(err) => return gh_error_response(err),
       };
 
       let issues: Value = serde_json::from_str(&stdout).unwrap_or_else(|_| json!([]));
@@ -741,8 +1008,7 @@ pub async fn github_issues_search(
         return json!({ "success": true, "issues": [] });
       }
 
-      let stdout = match run_command(
-        "gh",
+      let stdout = match run_gh_read(
         &[
           "issue",
           "list",
@@ -758,7 +1024,7 @@ pub async fn github_issues_search(
         Some(path),
       ) {
         Ok(out) => out,
-        Err(err) => return json!({ "success": false, "error": err }),
+        Err(err) => return gh_error_response(err),
       };
 
       let issues: Value = serde_json::from_str(&stdout).unwrap_or_else(|_| json!([]));
@@ -777,8 +1043,7 @@ pub async fn github_issue_get(project_path: String, number: u64) -> Value {
         return json!({ "success": false, "error": "Issue number is required" });
       }
       let path = Path::new(&project_path);
-      let stdout = match run_command(
-        "gh",
+      let stdout = match run_gh_read(
         &[
           "issue",
           "view",
@@ -789,7 +1054,7 @@ pub async fn github_issue_get(project_path: String, number: u64) -> Value {
         Some(path),
       ) {
         Ok(out) => out,
-        Err(err) => return json!({ "success": false, "error": err }),
+        Err(err) => return gh_error_response(err),
       };
       let issue: Value = serde_json::from_str(&stdout).unwrap_or(Value::Null);
       json!({ "success": !issue.is_null(), "issue": issue })
@@ -798,14 +1063,73 @@ pub async fn github_issue_get(project_path: String, number: u64) -> Value {
   .await
 }
 
+#[tauri::command]
+pub async fn github_get_issue_comments(project_path: String, number: u64) -> Value {
+  run_blocking(
+    json!({ "success": false, "error": "Task cancelled" }),
+    move || {
+      if number == 0 {
+        return json!({ "success": false, "error": "Issue number is required" });
+      }
+      let path = Path::new(&project_path);
+      if !has_github_remote(path) {
+        return json!({ "success": true, "comments": [] });
+      }
+
+      let stdout = match run_gh_read(
+        &["issue", "view", &number.to_string(), "--json", "comments"],
+        Some(path),
+      ) {
+        Ok(out) => out,
+        Err(err) if is_rate_limit_error(&err) => return rate_limited_response(&err),
+        Err(err) => {
+          let lowered = err.to_lowercase();
+          if lowered.contains("could not resolve to an issue") || lowered.contains("not found") {
+            return json!({ "success": true, "comments": [] });
+          }
+          return json!({ "success": false, "error": err });
+        }
+      };
+
+      let data: Value = serde_json::from_str(stdout.trim()).unwrap_or(Value::Null);
+      let mut comments: Vec<Value> = data
+        .get("comments")
+        .and_then(|v| v.as_array())
+        .map(|list| {
+          list
+            .iter()
+            .map(|comment| {
+              json!({
+                "id": comment.get("id").cloned().unwrap_or(Value::Null),
+                "author": comment.get("author").cloned().unwrap_or(Value::Null),
+                "body": comment.get("body").cloned().unwrap_or(Value::Null),
+                "createdAt": comment.get("createdAt").cloned().unwrap_or(Value::Null),
+                "url": comment.get("url").cloned().unwrap_or(Value::Null)
+              })
+            })
+            .collect()
+        })
+        .unwrap_or_default();
+
+      comments.sort_by(|a, b| {
+        let a_ts = a.get("createdAt").and_then(|v| v.as_str()).unwrap_or("");
+        let b_ts = b.get("createdAt").and_then(|v| v.as_str()).unwrap_or("");
+        a_ts.cmp(b_ts)
+      });
+
+      json!({ "success": true, "comments": comments })
+    },
+  )
+  .await
+}
+
 #[tauri::command]
 pub async fn github_list_pull_requests(project_path: String) -> Value {
   run_blocking(
     json!({ "success": false, "error": "Task cancelled" }),
     move || {
       let path = Path::new(&project_path);
-      let stdout = match run_command(
-        "gh",
+      let stdout = match run_gh_read(
         &[
           "pr",
           "list",
@@ -817,7 +1141,7 @@ pub async fn github_list_pull_requests(project_path: String) -> Value {
         Some(path),
       ) {
         Ok(out) => out,
-        Err(err) => return json!({ "success": false, "error": err }),
+        Err(err) => return gh_error_response(err),
       };
       let prs: Value = serde_json::from_str(&stdout).unwrap_or_else(|_| json!([]));
       json!({ "success": true, "prs": prs })
@@ -835,8 +1159,12 @@ pub struct GithubCreatePullRequestWorktreeArgs {
   pr_title: Option<String>,
   task_name: Option<String>,
   branch_name: Option<String>,
+  force: Option<bool>,
 }
 
+/// Checks a PR out into its own worktree, reusing an existing local branch for it.
+/// Returns `LOCAL_DIVERGED` instead of resetting a branch with unpushed commits
+/// unless `force` is set, and `COMPARE_FAILED` when that can't be determined.
 #[tauri::command]
 pub async fn github_create_pull_request_worktree(
   app: AppHandle,
@@ -882,8 +1210,18 @@ pub async fn github_create_pull_request_worktree(
       }
 
       let project_path_buf = Path::new(project_path);
-      if let Err(err) = ensure_pull_request_branch(project_path_buf, args.pr_number, &branch_name) {
-        return json!({ "success": false, "error": err });
+      let db_state: tauri::State<DbState> = app.state();
+      let remote = git::normalize_remote_name(
+        db::project_git_remote_for_path(&db_state, project_path).as_deref(),
+      );
+      if let Err(err) = ensure_pull_request_branch(
+        project_path_buf,
+        args.pr_number,
+        &branch_name,
+        &remote,
+        args.force.unwrap_or(false),
+      ) {
+        return err;
       }
 
       let worktrees_dir = Path::new(project_path).join("..").join("worktrees");
@@ -922,7 +1260,40 @@ pub async fn github_logout() -> Value {
     json!({ "success": false, "error": "Task cancelled" }),
     || {
       let _ = run_command("gh", &["auth", "logout", "--hostname", "github.com", "--yes"], None);
-      json!({ "success": true })
+      match clear_stored_token() {
+        Ok(()) => json!({ "success": true }),
+        Err(err) => json!({ "success": true, "keyringError": err }),
+      }
+    },
+  )
+  .await
+}
+
+#[tauri::command]
+pub async fn github_store_token(token: String) -> Value {
+  run_blocking(
+    json!({ "success": false, "error": "Task cancelled" }),
+    move || {
+      let trimmed = token.trim();
+      if trimmed.is_empty() {
+        return json!({ "success": false, "error": "Token is required" });
+      }
+      match store_token(trimmed) {
+        Ok(()) => json!({ "success": true }),
+        Err(err) => json!({ "success": false, "error": err }),
+      }
+    },
+  )
+  .await
+}
+
+#[tauri::command]
+pub async fn github_get_token() -> Value {
+  run_blocking(
+    json!({ "success": false, "error": "Task cancelled" }),
+    || match get_stored_token() {
+      Ok(token) => json!({ "success": true, "token": token }),
+      Err(err) => json!({ "success": false, "error": err }),
     },
   )
   .await
@@ -1044,7 +1415,7 @@ pub async fn github_create_new_project(
 
       let arg_refs: Vec<&str> = args.iter().map(String::as_str).collect();
       if let Err(err) = run_command("gh", &arg_refs, Some(&project_root)) {
-        return json!({ "success": false, "error": err });
+        return gh_error_response(err);
       }
 
       let local_path = project_root.join(&name);
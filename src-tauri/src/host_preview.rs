@@ -1,29 +1,41 @@
 use crate::runtime::run_blocking;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use serde::Deserialize;
 use serde_json::{json, Value};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::fs;
 use std::io::{BufRead, BufReader};
 use std::net::{TcpListener, TcpStream};
 use std::path::{Path, PathBuf};
 use std::process::{Child, Command, Stdio};
 use std::sync::{
-  atomic::{AtomicBool, Ordering},
-  Arc, Mutex,
+  atomic::{AtomicBool, AtomicU64, Ordering},
+  mpsc, Arc, Mutex,
 };
 use std::thread;
 use std::time::Duration;
 use tauri::{AppHandle, Emitter, Manager};
 
+const LOG_BUFFER_LINES: usize = 2000;
+const RESTART_DEBOUNCE_MS: u64 = 500;
+
+static RESTART_GENERATION: AtomicU64 = AtomicU64::new(0);
+
 #[derive(Default)]
 pub struct HostPreviewState {
   procs: Arc<Mutex<HashMap<String, Child>>>,
+  logs: Arc<Mutex<HashMap<String, VecDeque<String>>>>,
+  // Each watcher is tagged with a generation so its restart thread can tell whether it was
+  // stopped or replaced while it was waiting.
+  watchers: Arc<Mutex<HashMap<String, (u64, RecommendedWatcher)>>>,
 }
 
 impl HostPreviewState {
   pub fn new() -> Self {
     Self {
       procs: Arc::new(Mutex::new(HashMap::new())),
+      logs: Arc::new(Mutex::new(HashMap::new())),
+      watchers: Arc::new(Mutex::new(HashMap::new())),
     }
   }
 }
@@ -40,6 +52,19 @@ fn emit_event(app: &AppHandle, payload: Value) {
   let _ = app.emit("preview:host:event", payload);
 }
 
+fn push_log_line(
+  logs: &Mutex<HashMap<String, VecDeque<String>>>,
+  task_id: &str,
+  line: &str,
+) {
+  let mut map = logs.lock().unwrap();
+  let buf = map.entry(task_id.to_string()).or_default();
+  if buf.len() >= LOG_BUFFER_LINES {
+    buf.pop_front();
+  }
+  buf.push_back(line.to_string());
+}
+
 fn detect_package_manager(dir: &Path) -> &'static str {
   if dir.join("bun.lockb").exists() || dir.join("bun.lock").exists() {
     return "bun";
@@ -231,6 +256,217 @@ fn run_command_streaming(
   }
 }
 
+#[derive(Clone)]
+struct PreviewLaunch {
+  program: String,
+  args: Vec<String>,
+  envs: Vec<(String, String)>,
+  cwd: PathBuf,
+  port: u16,
+}
+
+fn spawn_preview(app: &AppHandle, task_id: &str, launch: &PreviewLaunch) -> Result<(), String> {
+  let state: tauri::State<HostPreviewState> = app.state();
+  let port = launch.port;
+  let mut cmd = Command::new(&launch.program);
+  cmd.args(&launch.args)
+    .current_dir(&launch.cwd)
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped());
+  for (key, value) in &launch.envs {
+    cmd.env(key, value);
+  }
+
+  let mut child = cmd.spawn().map_err(|err| err.to_string())?;
+
+  let url_emitted = Arc::new(AtomicBool::new(false));
+  let make_line_handler = |stream: &'static str| {
+    let task_id_clone = task_id.to_string();
+    let app_clone = app.clone();
+    let url_emitted_clone = url_emitted.clone();
+    let logs = state.logs.clone();
+    Arc::new(move |line: String| {
+      push_log_line(&logs, &task_id_clone, &line);
+      let _ = app_clone.emit(
+        "host_preview:log",
+        json!({ "taskId": task_id_clone, "stream": stream, "line": line }),
+      );
+      emit_event(
+        &app_clone,
+        json!({
+          "type": "setup",
+          "taskId": task_id_clone,
+          "status": "line",
+          "line": line
+        }),
+      );
+      if !url_emitted_clone.load(Ordering::SeqCst) {
+        if let Some(url) = normalize_url(&line) {
+          if !url_emitted_clone.swap(true, Ordering::SeqCst) {
+            emit_event(
+              &app_clone,
+              json!({ "type": "url", "taskId": task_id_clone, "url": url }),
+            );
+          }
+        }
+      }
+    })
+  };
+
+  if let Some(stdout) = child.stdout.take() {
+    spawn_line_reader(stdout, make_line_handler("stdout"));
+  }
+  if let Some(stderr) = child.stderr.take() {
+    spawn_line_reader(stderr, make_line_handler("stderr"));
+  }
+
+  let pid = child.id();
+  {
+    let mut map = state.procs.lock().unwrap();
+    map.insert(task_id.to_string(), child);
+  }
+
+  // Probe for server readiness and emit URL if needed.
+  let app_probe = app.clone();
+  let task_probe = task_id.to_string();
+  let url_emitted_probe = url_emitted.clone();
+  thread::spawn(move || {
+    for _ in 0..40 {
+      if url_emitted_probe.load(Ordering::SeqCst) {
+        return;
+      }
+      if probe_port("127.0.0.1", port) {
+        if !url_emitted_probe.swap(true, Ordering::SeqCst) {
+          emit_event(
+            &app_probe,
+            json!({
+              "type": "url",
+              "taskId": task_probe,
+              "url": format!("http://localhost:{port}")
+            }),
+          );
+        }
+        return;
+      }
+      thread::sleep(Duration::from_millis(800));
+    }
+  });
+
+  // Monitor exit. A process removed from the map (stop/restart) exited on purpose;
+  // anything the monitor reaps itself died unexpectedly.
+  let procs = state.procs.clone();
+  let app_exit = app.clone();
+  let task_exit = task_id.to_string();
+  thread::spawn(move || loop {
+    let status = {
+      let mut map = procs.lock().unwrap();
+      match map.get_mut(&task_exit) {
+        Some(child) if child.id() == pid => child.try_wait().ok().flatten(),
+        _ => return,
+      }
+    };
+    if let Some(status) = status {
+      let mut map = procs.lock().unwrap();
+      map.remove(&task_exit);
+      drop(map);
+      emit_event(&app_exit, json!({ "type": "exit", "taskId": task_exit }));
+      let _ = app_exit.emit(
+        "host_preview:exited",
+        json!({ "taskId": task_exit, "exitCode": status.code() }),
+      );
+      return;
+    }
+    thread::sleep(Duration::from_millis(500));
+  });
+
+  Ok(())
+}
+
+fn is_ignored_restart_path(rel: &Path) -> bool {
+  rel.components().any(|c| {
+    let name = c.as_os_str().to_string_lossy();
+    crate::fs::DEFAULT_IGNORES.contains(&name.as_ref())
+  })
+}
+
+// Relaunches the preview once file changes go quiet. Registers the watcher in the state and
+// exits once it is dropped.
+fn spawn_restart_watcher(
+  app: &AppHandle,
+  task_id: &str,
+  launch: PreviewLaunch,
+) -> Result<(), String> {
+  let root = fs::canonicalize(&launch.cwd).unwrap_or_else(|_| launch.cwd.clone());
+  let (tx, rx) = mpsc::channel();
+  let mut watcher = notify::recommended_watcher(tx).map_err(|err| err.to_string())?;
+  watcher
+    .watch(&root, RecursiveMode::Recursive)
+    .map_err(|err| err.to_string())?;
+
+  let generation = RESTART_GENERATION.fetch_add(1, Ordering::SeqCst);
+  {
+    let state: tauri::State<HostPreviewState> = app.state();
+    let mut watchers = state.watchers.lock().unwrap();
+    watchers.insert(task_id.to_string(), (generation, watcher));
+  }
+
+  let app = app.clone();
+  let task_id = task_id.to_string();
+  thread::spawn(move || {
+    let mut pending: Vec<String> = Vec::new();
+    loop {
+      match rx.recv_timeout(Duration::from_millis(RESTART_DEBOUNCE_MS)) {
+        Ok(Ok(event)) => {
+          for path in event.paths {
+            let rel = match path.strip_prefix(&root) {
+              Ok(rel) => rel,
+              Err(_) => continue,
+            };
+            if rel.as_os_str().is_empty() || is_ignored_restart_path(rel) {
+              continue;
+            }
+            let rel = rel.to_string_lossy().replace('\\', "/");
+            if !pending.contains(&rel) {
+              pending.push(rel);
+            }
+          }
+        }
+        Ok(Err(_)) => {}
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+          if pending.is_empty() {
+            continue;
+          }
+          let paths = std::mem::take(&mut pending);
+          // Hold the watchers lock across the restart: a stop that lands meanwhile waits
+          // and then kills the new process, and one that already ran is seen here.
+          let state: tauri::State<HostPreviewState> = app.state();
+          let watchers = state.watchers.lock().unwrap();
+          if watchers.get(&task_id).map(|(current, _)| *current) != Some(generation) {
+            break;
+          }
+          let _ = app.emit(
+            "host_preview:restarting",
+            json!({ "taskId": task_id, "paths": paths }),
+          );
+          {
+            let mut map = state.procs.lock().unwrap();
+            if let Some(mut child) = map.remove(&task_id) {
+              let _ = child.kill();
+              let _ = child.wait();
+            }
+          }
+          if let Err(err) = spawn_preview(&app, &task_id, &launch) {
+            emit_event(&app, json!({ "type": "exit", "taskId": task_id, "error": err }));
+          }
+          drop(watchers);
+        }
+        Err(mpsc::RecvTimeoutError::Disconnected) => break,
+      }
+    }
+  });
+  Ok(())
+}
+
 #[tauri::command]
 pub async fn host_preview_setup(app: AppHandle, task_id: String, task_path: String) -> Value {
   run_blocking(
@@ -258,6 +494,7 @@ pub async fn host_preview_start(
   task_path: String,
   script: Option<String>,
   parent_project_path: Option<String>,
+  restart_on_change: Option<bool>,
 ) -> Value {
   run_blocking(
     json!({ "ok": false, "error": "Task cancelled" }),
@@ -268,7 +505,8 @@ pub async fn host_preview_start(
         return json!({ "ok": false, "error": "task path not found" });
       }
 
-      // Stop existing process for this task.
+      // Stop existing process (and restart watcher) for this task.
+      state.watchers.lock().unwrap().remove(&task_id);
       {
         let mut map = state.procs.lock().unwrap();
         if let Some(mut child) = map.remove(&task_id) {
@@ -356,106 +594,23 @@ pub async fn host_preview_start(
         }
       }
 
-      let mut cmd = Command::new(pm);
-      cmd.args(&args)
-        .current_dir(&cwd)
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped());
-      for (key, value) in envs {
-        cmd.env(key, value);
-      }
-
-      let mut child = match cmd.spawn() {
-        Ok(child) => child,
-        Err(err) => return json!({ "ok": false, "error": err.to_string() }),
+      state.logs.lock().unwrap().remove(&task_id);
+      let launch = PreviewLaunch {
+        program: pm.to_string(),
+        args,
+        envs,
+        cwd,
+        port,
       };
-
-      let url_emitted = Arc::new(AtomicBool::new(false));
-      let task_id_clone = task_id.clone();
-      let app_clone = app.clone();
-      let url_emitted_clone = url_emitted.clone();
-
-      let on_line = Arc::new(move |line: String| {
-        emit_event(
-          &app_clone,
-          json!({
-            "type": "setup",
-            "taskId": task_id_clone,
-            "status": "line",
-            "line": line
-          }),
-        );
-        if !url_emitted_clone.load(Ordering::SeqCst) {
-          if let Some(url) = normalize_url(&line) {
-            if !url_emitted_clone.swap(true, Ordering::SeqCst) {
-              emit_event(
-                &app_clone,
-                json!({ "type": "url", "taskId": task_id_clone, "url": url }),
-              );
-            }
-          }
-        }
-      });
-
-      if let Some(stdout) = child.stdout.take() {
-        spawn_line_reader(stdout, on_line.clone());
-      }
-      if let Some(stderr) = child.stderr.take() {
-        spawn_line_reader(stderr, on_line);
-      }
-
-      {
-        let mut map = state.procs.lock().unwrap();
-        map.insert(task_id.clone(), child);
+      if let Err(err) = spawn_preview(&app, &task_id, &launch) {
+        return json!({ "ok": false, "error": err });
       }
 
-      // Probe for server readiness and emit URL if needed.
-      let app_probe = app.clone();
-      let task_probe = task_id.clone();
-      let url_emitted_probe = url_emitted.clone();
-      thread::spawn(move || {
-        for _ in 0..40 {
-          if url_emitted_probe.load(Ordering::SeqCst) {
-            return;
-          }
-          if probe_port("127.0.0.1", port) {
-            if !url_emitted_probe.swap(true, Ordering::SeqCst) {
-              emit_event(
-                &app_probe,
-                json!({
-                  "type": "url",
-                  "taskId": task_probe,
-                  "url": format!("http://localhost:{port}")
-                }),
-              );
-            }
-            return;
-          }
-          thread::sleep(Duration::from_millis(800));
-        }
-      });
-
-      // Monitor exit.
-      let procs = state.procs.clone();
-      let app_exit = app.clone();
-      let task_exit = task_id.clone();
-      thread::spawn(move || loop {
-        let status = {
-          let mut map = procs.lock().unwrap();
-          if let Some(child) = map.get_mut(&task_exit) {
-            child.try_wait().ok().flatten()
-          } else {
-            return;
-          }
-        };
-        if status.is_some() {
-          let mut map = procs.lock().unwrap();
-          map.remove(&task_exit);
-          emit_event(&app_exit, json!({ "type": "exit", "taskId": task_exit }));
-          return;
+      if restart_on_change.unwrap_or(false) {
+        if let Err(err) = spawn_restart_watcher(&app, &task_id, launch) {
+          return json!({ "ok": true, "restartOnChange": false, "watchError": err });
         }
-        thread::sleep(Duration::from_millis(500));
-      });
+      }
 
       json!({ "ok": true })
     },
@@ -469,6 +624,7 @@ pub async fn host_preview_stop(app: AppHandle, task_id: String) -> Value {
     json!({ "ok": false, "error": "Task cancelled" }),
     move || {
       let state: tauri::State<HostPreviewState> = app.state();
+      state.watchers.lock().unwrap().remove(&task_id);
       let mut map = state.procs.lock().unwrap();
       if let Some(mut child) = map.remove(&task_id) {
         let _ = child.kill();
@@ -486,6 +642,12 @@ pub async fn host_preview_stop_all(app: AppHandle, except_id: Option<String>) ->
     move || {
       let state: tauri::State<HostPreviewState> = app.state();
       let except = except_id.unwrap_or_default();
+      // Watchers before procs, in the same order as the restart thread takes the locks.
+      state
+        .watchers
+        .lock()
+        .unwrap()
+        .retain(|key, _| !except.is_empty() && *key == except);
       let mut map = state.procs.lock().unwrap();
       let mut stopped: Vec<String> = Vec::new();
       let keys: Vec<String> = map.keys().cloned().collect();
@@ -503,3 +665,23 @@ pub async fn host_preview_stop_all(app: AppHandle, except_id: Option<String>) ->
   )
   .await
 }
+
+#[tauri::command]
+pub async fn host_preview_logs(app: AppHandle, task_id: String, tail: Option<usize>) -> Value {
+  run_blocking(
+    json!({ "ok": false, "error": "Task cancelled" }),
+    move || {
+      let state: tauri::State<HostPreviewState> = app.state();
+      let map = state.logs.lock().unwrap();
+      let lines: Vec<String> = match map.get(&task_id) {
+        Some(buf) => {
+          let tail = tail.unwrap_or(200).min(buf.len());
+          buf.iter().skip(buf.len() - tail).cloned().collect()
+        }
+        None => Vec::new(),
+      };
+      json!({ "ok": true, "lines": lines })
+    },
+  )
+  .await
+}
@@ -0,0 +1,40 @@
+use std::sync::OnceLock;
+use std::time::Duration;
+
+const DEFAULT_CONNECT_TIMEOUT_MS: u64 = 10_000;
+const DEFAULT_READ_TIMEOUT_MS: u64 = 30_000;
+const USER_AGENT: &str = concat!("emdash/", env!("CARGO_PKG_VERSION"));
+
+fn timeout_from_env(key: &str, fallback: u64) -> Duration {
+  let ms = std::env::var(key)
+    .ok()
+    .and_then(|v| v.trim().parse::<u64>().ok())
+    .filter(|v| *v > 0)
+    .unwrap_or(fallback);
+  Duration::from_millis(ms)
+}
+
+fn build_agent(redirects: u32) -> ureq::Agent {
+  let connect = timeout_from_env("EMDASH_HTTP_CONNECT_TIMEOUT_MS", DEFAULT_CONNECT_TIMEOUT_MS);
+  let read = timeout_from_env("EMDASH_HTTP_READ_TIMEOUT_MS", DEFAULT_READ_TIMEOUT_MS);
+  ureq::AgentBuilder::new()
+    .timeout_connect(connect)
+    .timeout_read(read)
+    .timeout_write(read)
+    .redirects(redirects)
+    .user_agent(USER_AGENT)
+    .build()
+}
+
+/// Shared agent for outbound HTTP with bounded connect/read/write timeouts.
+/// Override with EMDASH_HTTP_CONNECT_TIMEOUT_MS / EMDASH_HTTP_READ_TIMEOUT_MS.
+pub fn http_agent() -> ureq::Agent {
+  static AGENT: OnceLock<ureq::Agent> = OnceLock::new();
+  AGENT.get_or_init(|| build_agent(5)).clone()
+}
+
+/// Same as `http_agent`, but hands 3xx responses back so callers can vet each hop.
+pub fn http_agent_no_redirects() -> ureq::Agent {
+  static AGENT: OnceLock<ureq::Agent> = OnceLock::new();
+  AGENT.get_or_init(|| build_agent(0)).clone()
+}
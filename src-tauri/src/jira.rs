@@ -1,14 +1,18 @@
+use crate::http::http_agent;
 use crate::storage;
 use crate::runtime::run_blocking;
 use base64::{engine::general_purpose::STANDARD, Engine as _};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::fs;
+use std::io::Read;
 use std::path::PathBuf;
 
 const SERVICE_NAME: &str = "emdash-jira";
 const ACCOUNT_NAME: &str = "api-token";
 const CONFIG_FILE: &str = "jira.json";
+const MAX_SEARCH_RESULTS: u32 = 1000;
+const MAX_ATTACHMENT_BYTES: u64 = 100 * 1024 * 1024;
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 struct JiraCreds {
@@ -100,16 +104,40 @@ fn build_url(base: &str, path: &str) -> String {
   format!("{}{}", base.trim_end_matches('/'), path)
 }
 
-fn do_request(
+/// Pulls the human-readable parts out of a Jira error body (`errorMessages` plus per-field
+/// `errors`), e.g. JQL parse errors on a 400.
+fn api_error_messages(body: &str) -> Option<String> {
+  let data: Value = serde_json::from_str(body).ok()?;
+  let mut messages: Vec<String> = data
+    .get("errorMessages")
+    .and_then(|v| v.as_array())
+    .map(|arr| arr.iter().filter_map(|m| m.as_str().map(|s| s.to_string())).collect())
+    .unwrap_or_default();
+  if let Some(errors) = data.get("errors").and_then(|v| v.as_object()) {
+    for (field, msg) in errors {
+      if let Some(msg) = msg.as_str() {
+        messages.push(format!("{}: {}", field, msg));
+      }
+    }
+  }
+  if messages.is_empty() {
+    None
+  } else {
+    Some(messages.join("; "))
+  }
+}
+
+fn send_request(
   url: &str,
   email: &str,
   token: &str,
   method: &str,
   payload: Option<&str>,
   extra_headers: Option<Vec<(&str, &str)>>,
-) -> Result<String, String> {
+) -> Result<ureq::Response, String> {
   let auth = encode_basic(email, token);
-  let mut req = ureq::request(method, url)
+  let mut req = http_agent()
+    .request(method, url)
     .set("Authorization", &format!("Basic {}", auth))
     .set("Accept", "application/json");
 
@@ -126,10 +154,11 @@ fn do_request(
   };
 
   match response {
-    Ok(resp) => resp.into_string().map_err(|err| err.to_string()),
+    Ok(resp) => Ok(resp),
     Err(ureq::Error::Status(code, resp)) => {
-      let snippet = resp.into_string().unwrap_or_default();
-      let snippet = snippet.chars().take(200).collect::<String>();
+      let body = resp.into_string().unwrap_or_default();
+      let snippet = api_error_messages(&body)
+        .unwrap_or_else(|| body.chars().take(200).collect::<String>());
       let suffix = if snippet.is_empty() { "" } else { ": " };
       Err(format!("Jira API error {}{}{}", code, suffix, snippet))
     }
@@ -137,6 +166,33 @@ fn do_request(
   }
 }
 
+fn do_request(
+  url: &str,
+  email: &str,
+  token: &str,
+  method: &str,
+  payload: Option<&str>,
+  extra_headers: Option<Vec<(&str, &str)>>,
+) -> Result<String, String> {
+  send_request(url, email, token, method, payload, extra_headers)?
+    .into_string()
+    .map_err(|err| err.to_string())
+}
+
+fn do_request_bytes(url: &str, email: &str, token: &str, limit: u64) -> Result<Vec<u8>, String> {
+  let resp = send_request(url, email, token, "GET", None, Some(vec![("Accept", "*/*")]))?;
+  let mut buf = Vec::new();
+  resp
+    .into_reader()
+    .take(limit + 1)
+    .read_to_end(&mut buf)
+    .map_err(|err| err.to_string())?;
+  if buf.len() as u64 > limit {
+    return Err(format!("Attachment exceeds {} bytes", limit));
+  }
+  Ok(buf)
+}
+
 fn get_myself(site_url: &str, email: &str, token: &str) -> Result<Value, String> {
   let url = build_url(site_url, "/rest/api/3/myself");
   let body = do_request(&url, email, token, "GET", None, None)?;
@@ -147,10 +203,19 @@ fn get_myself(site_url: &str, email: &str, token: &str) -> Result<Value, String>
   Ok(data)
 }
 
-fn search_raw(site_url: &str, email: &str, token: &str, jql: &str, limit: u32) -> Result<Vec<Value>, String> {
+/// Fetches one page of results along with Jira's reported total.
+fn search_raw(
+  site_url: &str,
+  email: &str,
+  token: &str,
+  jql: &str,
+  start_at: u32,
+  limit: u32,
+) -> Result<(Vec<Value>, Option<u64>), String> {
   let url = build_url(site_url, "/rest/api/3/search");
   let payload = json!({
     "jql": jql,
+    "startAt": start_at,
     "maxResults": limit.clamp(1, 100),
     "fields": ["summary", "updated", "project", "status", "assignee"]
   })
@@ -165,11 +230,31 @@ fn search_raw(site_url: &str, email: &str, token: &str, jql: &str, limit: u32) -
     Some(vec![("Content-Type", "application/json")]),
   )?;
   let data: Value = serde_json::from_str(&body).map_err(|err| err.to_string())?;
-  Ok(data
+  let issues = data
     .get("issues")
     .and_then(|v| v.as_array())
     .cloned()
-    .unwrap_or_default())
+    .unwrap_or_default();
+  Ok((issues, data.get("total").and_then(|v| v.as_u64())))
+}
+
+/// Walks pages of at most 100 issues until `limit` is reached or Jira runs out of results.
+/// Jira Cloud may cap a page below what was asked for, so a short page isn't the end.
+fn search_paged(site_url: &str, email: &str, token: &str, jql: &str, limit: u32) -> Result<Vec<Value>, String> {
+  let mut issues: Vec<Value> = Vec::new();
+  while issues.len() < limit as usize {
+    let start_at = issues.len() as u32;
+    let want = (limit - start_at).min(100);
+    let (page, total) = search_raw(site_url, email, token, jql, start_at, want)?;
+    let received = page.len();
+    issues.extend(page);
+    let exhausted = total.map(|t| issues.len() as u64 >= t).unwrap_or(false);
+    if received == 0 || exhausted {
+      break;
+    }
+  }
+  issues.truncate(limit as usize);
+  Ok(issues)
 }
 
 fn get_issue_by_key(site_url: &str, email: &str, token: &str, key: &str) -> Result<Option<Value>, String> {
@@ -182,6 +267,28 @@ fn get_issue_by_key(site_url: &str, email: &str, token: &str, key: &str) -> Resu
   Ok(Some(data))
 }
 
+fn get_issue_attachments(site_url: &str, email: &str, token: &str, key: &str) -> Result<Vec<Value>, String> {
+  let url = build_url(site_url, &format!("/rest/api/3/issue/{}?fields=attachment", key));
+  let body = do_request(&url, email, token, "GET", None, None)?;
+  let data: Value = serde_json::from_str(&body).map_err(|err| err.to_string())?;
+  Ok(data
+    .get("fields")
+    .and_then(|f| f.get("attachment"))
+    .and_then(|v| v.as_array())
+    .cloned()
+    .unwrap_or_default())
+}
+
+fn normalize_attachment(raw: &Value) -> Value {
+  json!({
+    "id": raw.get("id").and_then(|v| v.as_str()).unwrap_or(""),
+    "filename": raw.get("filename").and_then(|v| v.as_str()).unwrap_or(""),
+    "mimeType": raw.get("mimeType").and_then(|v| v.as_str()).unwrap_or(""),
+    "size": raw.get("size").and_then(|v| v.as_u64()).unwrap_or(0),
+    "contentUrl": raw.get("content").and_then(|v| v.as_str()).unwrap_or(""),
+  })
+}
+
 fn get_recent_issue_keys(
   site_url: &str,
   email: &str,
@@ -343,7 +450,7 @@ pub async fn jira_initial_fetch(app: tauri::AppHandle, limit: Option<u32>) -> Va
         Ok(res) => res,
         Err(err) => return json!({ "success": false, "error": err }),
       };
-      let limit = limit.unwrap_or(50).clamp(1, 100);
+      let limit = limit.unwrap_or(50).clamp(1, MAX_SEARCH_RESULTS);
       let jql_candidates = vec![
         "assignee = currentUser() ORDER BY updated DESC",
         "reporter = currentUser() ORDER BY updated DESC",
@@ -351,7 +458,7 @@ pub async fn jira_initial_fetch(app: tauri::AppHandle, limit: Option<u32>) -> Va
       ];
 
       for jql in jql_candidates {
-        if let Ok(issues) = search_raw(&creds.site_url, &creds.email, &token, jql, limit) {
+        if let Ok(issues) = search_paged(&creds.site_url, &creds.email, &token, jql, limit) {
           if !issues.is_empty() {
             return json!({ "success": true, "issues": normalize_issues(&creds.site_url, issues) });
           }
@@ -392,7 +499,7 @@ pub async fn jira_search_issues(app: tauri::AppHandle, args: JiraSearchArgs) ->
         Ok(res) => res,
         Err(err) => return json!({ "success": false, "error": err }),
       };
-      let limit = args.limit.unwrap_or(20).clamp(1, 100);
+      let limit = args.limit.unwrap_or(20).clamp(1, MAX_SEARCH_RESULTS);
 
       if looks_like_key(term) {
         let key_upper = term.to_uppercase();
@@ -408,7 +515,7 @@ pub async fn jira_search_issues(app: tauri::AppHandle, args: JiraSearchArgs) ->
         String::new()
       };
       let jql = format!("text ~ \"{}\"{}", sanitized, extra_key);
-      match search_raw(&creds.site_url, &creds.email, &token, &jql, limit) {
+      match search_paged(&creds.site_url, &creds.email, &token, &jql, limit) {
         Ok(issues) => json!({ "success": true, "issues": normalize_issues(&creds.site_url, issues) }),
         Err(err) => json!({ "success": false, "error": err }),
       }
@@ -416,3 +523,119 @@ pub async fn jira_search_issues(app: tauri::AppHandle, args: JiraSearchArgs) ->
   )
   .await
 }
+
+#[tauri::command]
+pub async fn jira_get_attachments(app: tauri::AppHandle, issue_key: String) -> Value {
+  run_blocking(
+    json!({ "success": false, "error": "Task cancelled" }),
+    move || {
+      let key = issue_key.trim().to_uppercase();
+      if !looks_like_key(&key) {
+        return json!({ "success": false, "error": "Invalid issue key" });
+      }
+      let (creds, token) = match require_auth(&app) {
+        Ok(res) => res,
+        Err(err) => return json!({ "success": false, "error": err }),
+      };
+      match get_issue_attachments(&creds.site_url, &creds.email, &token, &key) {
+        Ok(raw) => json!({
+          "success": true,
+          "attachments": raw.iter().map(normalize_attachment).collect::<Vec<_>>()
+        }),
+        Err(err) => json!({ "success": false, "error": err }),
+      }
+    },
+  )
+  .await
+}
+
+#[tauri::command]
+pub async fn jira_download_attachment(
+  app: tauri::AppHandle,
+  issue_key: String,
+  attachment_id: String,
+  dest_path: String,
+) -> Value {
+  run_blocking(
+    json!({ "success": false, "error": "Task cancelled" }),
+    move || {
+      let key = issue_key.trim().to_uppercase();
+      if !looks_like_key(&key) {
+        return json!({ "success": false, "error": "Invalid issue key" });
+      }
+      let dest = PathBuf::from(dest_path.trim());
+      if dest.as_os_str().is_empty() || dest.is_dir() {
+        return json!({ "success": false, "error": "Invalid destination path" });
+      }
+      let (creds, token) = match require_auth(&app) {
+        Ok(res) => res,
+        Err(err) => return json!({ "success": false, "error": err }),
+      };
+
+      // Only download attachments that actually belong to the issue.
+      let attachments = match get_issue_attachments(&creds.site_url, &creds.email, &token, &key) {
+        Ok(list) => list,
+        Err(err) => return json!({ "success": false, "error": err }),
+      };
+      let attachment = match attachments
+        .iter()
+        .find(|a| a.get("id").and_then(|v| v.as_str()) == Some(attachment_id.as_str()))
+      {
+        Some(a) => normalize_attachment(a),
+        None => return json!({ "success": false, "error": "Attachment not found on issue" }),
+      };
+      let content_url = attachment
+        .get("contentUrl")
+        .and_then(|v| v.as_str())
+        .filter(|u| !u.is_empty())
+        .map(|u| u.to_string())
+        .unwrap_or_else(|| {
+          build_url(&creds.site_url, &format!("/rest/api/3/attachment/content/{}", attachment_id))
+        });
+
+      let bytes = match do_request_bytes(&content_url, &creds.email, &token, MAX_ATTACHMENT_BYTES) {
+        Ok(bytes) => bytes,
+        Err(err) => return json!({ "success": false, "error": err }),
+      };
+      if let Some(parent) = dest.parent() {
+        if let Err(err) = fs::create_dir_all(parent) {
+          return json!({ "success": false, "error": err.to_string() });
+        }
+      }
+      if let Err(err) = fs::write(&dest, &bytes) {
+        return json!({ "success": false, "error": err.to_string() });
+      }
+
+      json!({
+        "success": true,
+        "path": dest.to_string_lossy(),
+        "bytes": bytes.len(),
+        "attachment": attachment
+      })
+    },
+  )
+  .await
+}
+
+#[tauri::command]
+pub async fn jira_search_jql(app: tauri::AppHandle, jql: String, limit: Option<u32>) -> Value {
+  run_blocking(
+    json!({ "success": false, "error": "Task cancelled" }),
+    move || {
+      let jql = jql.trim();
+      if jql.is_empty() {
+        return json!({ "success": false, "error": "JQL query is required" });
+      }
+      let (creds, token) = match require_auth(&app) {
+        Ok(res) => res,
+        Err(err) => return json!({ "success": false, "error": err }),
+      };
+      let limit = limit.unwrap_or(50).clamp(1, 100);
+      match search_raw(&creds.site_url, &creds.email, &token, jql, 0, limit) {
+        Ok((issues, _)) => json!({ "success": true, "issues": normalize_issues(&creds.site_url, issues) }),
+        Err(err) => json!({ "success": false, "error": err }),
+      }
+    },
+  )
+  .await
+}
@@ -1,3 +1,4 @@
+use crate::http::http_agent;
 use crate::runtime::run_blocking;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
@@ -85,12 +86,25 @@ fn graphql<T: for<'de> Deserialize<'de>>(
   })
   .to_string();
 
-  let response = ureq::post(LINEAR_API_URL)
+  let response = http_agent()
+    .post(LINEAR_API_URL)
     .set("Content-Type", "application/json")
     .set("Authorization", token)
     .send_string(&body);
 
-  let response = response.map_err(|err| err.to_string())?;
+  // Linear reports validation problems as 4xx with a GraphQL error body; keep its message.
+  let response = match response {
+    Ok(response) => response,
+    Err(ureq::Error::Status(code, response)) => {
+      let text = response.into_string().unwrap_or_default();
+      let message = serde_json::from_str::<GraphQLResponse<Value>>(&text)
+        .ok()
+        .and_then(|parsed| parsed.errors)
+        .and_then(|errors| errors.into_iter().filter_map(|e| e.message).next());
+      return Err(message.unwrap_or_else(|| format!("Linear API returned status {code}")));
+    }
+    Err(err) => return Err(err.to_string()),
+  };
   let text = response.into_string().map_err(|err| err.to_string())?;
   let parsed: GraphQLResponse<T> = serde_json::from_str(&text).map_err(|err| err.to_string())?;
 
@@ -258,8 +272,8 @@ pub async fn linear_initial_fetch(limit: Option<u32>) -> Value {
           title
           description
           url
-          state { name type }
-          team { name key }
+          state { id name type }
+          team { id name key }
           project { name }
           assignee { displayName name }
           updatedAt
@@ -313,8 +327,8 @@ pub async fn linear_search_issues(args: LinearSearchArgs) -> Value {
           title
           description
           url
-          state { name type }
-          team { name key }
+          state { id name type }
+          team { id name key }
           project { name }
           assignee { displayName name }
           updatedAt
@@ -364,3 +378,121 @@ pub async fn linear_search_issues(args: LinearSearchArgs) -> Value {
   )
   .await
 }
+
+#[tauri::command]
+pub async fn linear_list_workflow_states(team_id: String) -> Value {
+  run_blocking(
+    json!({ "success": false, "error": "Task cancelled" }),
+    move || {
+      let team_id = team_id.trim().to_string();
+      if team_id.is_empty() {
+        return json!({ "success": false, "error": "Team id is required." });
+      }
+      let token = match get_token() {
+        Ok(Some(token)) => token,
+        Ok(None) => return json!({ "success": false, "error": "Linear token not set." }),
+        Err(err) => return json!({ "success": false, "error": err }),
+      };
+
+      let query = r#"
+    query WorkflowStates($teamId: String!) {
+      team(id: $teamId) {
+        states {
+          nodes { id name type position }
+        }
+      }
+    }
+      "#;
+
+      #[derive(Debug, Deserialize)]
+      struct TeamStatesResponse {
+        team: Option<Value>,
+      }
+      let data: Result<TeamStatesResponse, String> =
+        graphql(&token, query, Some(json!({ "teamId": team_id })));
+
+      match data {
+        Ok(resp) => {
+          let mut nodes = resp
+            .team
+            .and_then(|team| team.pointer("/states/nodes").cloned())
+            .and_then(|nodes| nodes.as_array().cloned())
+            .unwrap_or_default();
+          nodes.sort_by(|a, b| {
+            let pa = a.get("position").and_then(|v| v.as_f64()).unwrap_or(0.0);
+            let pb = b.get("position").and_then(|v| v.as_f64()).unwrap_or(0.0);
+            pa.total_cmp(&pb)
+          });
+          let states: Vec<Value> = nodes
+            .into_iter()
+            .map(|node| {
+              json!({
+                "id": node.get("id").cloned().unwrap_or(Value::Null),
+                "name": node.get("name").cloned().unwrap_or(Value::Null),
+                "type": node.get("type").cloned().unwrap_or(Value::Null)
+              })
+            })
+            .collect();
+          json!({ "success": true, "states": states })
+        }
+        Err(err) => json!({ "success": false, "error": err }),
+      }
+    },
+  )
+  .await
+}
+
+#[tauri::command]
+pub async fn linear_update_issue_state(issue_id: String, state_id: String) -> Value {
+  run_blocking(
+    json!({ "success": false, "error": "Task cancelled" }),
+    move || {
+      let issue_id = issue_id.trim().to_string();
+      let state_id = state_id.trim().to_string();
+      if issue_id.is_empty() || state_id.is_empty() {
+        return json!({ "success": false, "error": "Issue id and state id are required." });
+      }
+      let token = match get_token() {
+        Ok(Some(token)) => token,
+        Ok(None) => return json!({ "success": false, "error": "Linear token not set." }),
+        Err(err) => return json!({ "success": false, "error": err }),
+      };
+
+      let query = r#"
+    mutation UpdateIssueState($id: String!, $stateId: String!) {
+      issueUpdate(id: $id, input: { stateId: $stateId }) {
+        success
+        issue {
+          id
+          identifier
+          state { id name type }
+        }
+      }
+    }
+      "#;
+
+      #[derive(Debug, Deserialize)]
+      #[serde(rename_all = "camelCase")]
+      struct IssueUpdateResponse {
+        issue_update: Option<Value>,
+      }
+      let data: Result<IssueUpdateResponse, String> = graphql(
+        &token,
+        query,
+        Some(json!({ "id": issue_id, "stateId": state_id })),
+      );
+
+      match data {
+        Ok(resp) => {
+          let payload = resp.issue_update.unwrap_or(Value::Null);
+          if payload.get("success").and_then(|v| v.as_bool()) != Some(true) {
+            return json!({ "success": false, "error": "Linear did not update the issue." });
+          }
+          json!({ "success": true, "issue": payload.get("issue").cloned().unwrap_or(Value::Null) })
+        }
+        Err(err) => json!({ "success": false, "error": err }),
+      }
+    },
+  )
+  .await
+}
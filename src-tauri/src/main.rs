@@ -8,6 +8,7 @@ mod fs;
 mod github;
 mod git;
 mod host_preview;
+mod http;
 mod jira;
 mod linear;
 mod net;
@@ -19,6 +20,8 @@ mod settings;
 mod system_env;
 mod storage;
 mod terminal_snapshots;
+#[cfg(test)]
+mod test_support;
 mod worktree;
 
 use tauri::{Emitter, Manager};
@@ -26,7 +29,7 @@ use tauri::{Emitter, Manager};
 use serde_json::{json, Value};
 use std::fs::OpenOptions;
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -78,6 +81,33 @@ fn app_open_in(app_handle: tauri::AppHandle, app: String, path: String) -> Value
   }
 }
 
+#[tauri::command]
+async fn preflight_check() -> Value {
+  runtime::run_blocking(
+    json!({ "success": false, "error": "preflight_check failed" }),
+    || {
+      let git_bin = git::resolve_git_bin();
+      let tools: Vec<Value> = std::thread::scope(|scope| {
+        let mut handles = vec![
+          scope.spawn(|| providers::probe_tool("git", &git_bin, &["--version"])),
+          scope.spawn(|| providers::probe_tool("gh", "gh", &["--version"])),
+          scope.spawn(|| providers::probe_tool("docker", "docker", &["--version"])),
+        ];
+        for id in providers::provider_ids() {
+          handles.push(scope.spawn(move || providers::probe_provider(id).unwrap_or(Value::Null)));
+        }
+        handles
+          .into_iter()
+          .filter_map(|handle| handle.join().ok())
+          .filter(|tool| !tool.is_null())
+          .collect()
+      });
+      json!({ "success": true, "tools": tools })
+    },
+  )
+  .await
+}
+
 #[tauri::command]
 fn project_open() -> Value {
   let picked = rfd::FileDialog::new().set_title("Open Project").pick_folder();
@@ -90,7 +120,8 @@ fn project_open() -> Value {
 #[tauri::command]
 fn settings_get(app: tauri::AppHandle) -> Result<Value, String> {
   let settings = settings::load_settings(&app);
-  Ok(json!({ "success": true, "settings": settings }))
+  let warnings = settings::take_load_warnings();
+  Ok(json!({ "success": true, "settings": settings, "warnings": warnings }))
 }
 
 #[tauri::command]
@@ -122,14 +153,17 @@ fn main() {
           db::DbState::disabled()
         }
       };
+      db_state.set_app_handle(app.handle().clone());
       app.manage(db_state);
       app.manage(github::GitHubState::new());
       app.manage(host_preview::HostPreviewState::new());
       app.manage(providers::ProviderState::new(&app.handle()));
+      app.manage(git::CancelRegistry::new());
       app.manage(pty::PtyState::default());
       app.manage(worktree::WorktreeState::new());
       app.manage(container::ContainerState::new());
       app.manage(browser::BrowserViewState::new());
+      app.manage(fs::FsWatchState::new());
       Ok(())
     })
     .invoke_handler(tauri::generate_handler![
@@ -139,14 +173,21 @@ fn main() {
       app_open_external,
       app_open_in,
       project_open,
+      preflight_check,
       pty::pty_start,
       pty::pty_input,
+      pty::pty_write_file,
       pty::pty_resize,
       pty::pty_kill,
       pty::pty_snapshot_get,
       pty::pty_snapshot_save,
+      pty::pty_snapshot_trim,
       pty::pty_snapshot_clear,
+      pty::pty_save_session,
+      pty::pty_restore_session,
+      pty::task_set_env,
       pty::terminal_get_theme,
+      pty::terminal_set_theme,
       github::github_check_cli_installed,
       github::github_install_cli,
       github::github_auth,
@@ -160,24 +201,48 @@ fn main() {
       github::github_issues_list,
       github::github_issues_search,
       github::github_issue_get,
+      github::github_get_issue_comments,
       github::github_list_pull_requests,
       github::github_logout,
+      github::github_store_token,
+      github::github_get_token,
       github::github_get_owners,
       github::github_validate_repo_name,
       github::github_create_new_project,
       github::github_create_pull_request_worktree,
       git::git_get_info,
       git::git_get_status,
+      git::git_get_change_summary,
       git::git_get_file_diff,
+      git::git_blame,
+      git::git_get_diffs,
+      git::git_get_commit_diff,
       git::git_stage_file,
+      git::git_stage_all,
+      git::git_unstage_all,
       git::git_revert_file,
+      git::git_discard_all,
       git::git_commit_and_push,
       git::git_get_branch_status,
+      git::git_pull,
       git::git_get_pr_status,
+      git::github_pr_mark_ready,
+      git::github_rerun_checks,
+      git::github_pr_mark_draft,
       git::git_get_pr_comments,
       git::git_get_pr_changes,
       git::git_list_remote_branches,
+      git::git_delete_remote_branch,
+      git::git_get_log,
+      git::git_export_patch,
+      git::git_apply_patch,
+      git::git_benchmark,
       git::git_generate_pr_content,
+      git::git_generate_commit_message,
+      git::provider_run,
+      git::provider_capabilities,
+      git::provider_cancel,
+      git::git_cancel,
       git::git_create_pr,
       git::git_merge_pr,
       providers::providers_get_statuses,
@@ -185,21 +250,30 @@ fn main() {
       host_preview::host_preview_start,
       host_preview::host_preview_stop,
       host_preview::host_preview_stop_all,
+      host_preview::host_preview_logs,
       worktree::worktree_create,
       worktree::worktree_list,
       worktree::worktree_remove,
       worktree::worktree_status,
       worktree::worktree_merge,
       worktree::worktree_get,
+      worktree::worktree_branch_diff,
+      worktree::worktree_repair,
       worktree::worktree_get_all,
       db::db_get_projects,
       db::db_save_project,
       db::db_get_tasks,
       db::db_save_task,
       db::db_delete_project,
+      db::db_cleanup_orphans,
+      db::db_get_stats,
+      db::db_global_search,
       db::db_delete_task,
       db::db_save_conversation,
       db::db_get_conversations,
+      db::db_rename_conversation,
+      db::db_get_task_tags,
+      db::db_set_task_tags,
       db::db_get_or_create_default_conversation,
       db::db_save_message,
       db::db_get_messages,
@@ -208,34 +282,50 @@ fn main() {
       db::project_settings_update,
       db::db_get_init_error,
       db::db_retry_init,
+      db::db_backup,
+      db::db_vacuum,
+      db::db_check_integrity,
       db::db_backup_and_reset,
       worktree::project_settings_fetch_base_ref,
+      worktree::task_set_base_ref,
       settings_get,
       settings_update,
       fs::fs_list,
+      fs::fs_list_tree,
+      fs::fs_watch_start,
+      fs::fs_watch_stop,
       fs::fs_read,
       fs::fs_write,
       fs::fs_remove,
       fs::fs_save_attachment,
       net::net_probe_ports,
+      net::net_wait_for_port,
       plan_lock::plan_lock,
       plan_lock::plan_unlock,
+      plan_lock::plan_lock_status,
       debug::debug_append_log,
       linear::linear_save_token,
       linear::linear_check_connection,
       linear::linear_clear_token,
       linear::linear_initial_fetch,
       linear::linear_search_issues,
+      linear::linear_list_workflow_states,
+      linear::linear_update_issue_state,
       jira::jira_save_credentials,
       jira::jira_clear_credentials,
       jira::jira_check_connection,
       jira::jira_initial_fetch,
       jira::jira_search_issues,
+      jira::jira_search_jql,
+      jira::jira_get_attachments,
+      jira::jira_download_attachment,
       container::container_load_config,
       container::container_start_run,
       container::container_stop_run,
       container::container_inspect_run,
+      container::container_prune,
       container::icons_resolve_service,
+      container::icons_resolve_services,
       browser::browser_view_show,
       browser::browser_view_hide,
       browser::browser_view_set_bounds,
@@ -243,12 +333,20 @@ fn main() {
       browser::browser_view_go_back,
       browser::browser_view_go_forward,
       browser::browser_view_reload,
+      browser::browser_view_current_url,
       browser::browser_view_open_devtools,
-      browser::browser_view_clear
+      browser::browser_view_capture,
+      browser::browser_view_clear,
+      browser::browser_view_clear_storage
     ])
-    .run(tauri::generate_context!());
-  if let Err(err) = result {
-    eprintln!("error while running tauri application: {}", err);
+    .build(tauri::generate_context!());
+  match result {
+    Ok(app) => app.run(|app_handle, event| {
+      if let tauri::RunEvent::Exit = event {
+        app_handle.state::<fs::FsWatchState>().stop_all();
+      }
+    }),
+    Err(err) => eprintln!("error while running tauri application: {}", err),
   }
 }
 
@@ -292,6 +390,17 @@ fn try_command(command: &str, args: &[&str]) -> bool {
     .unwrap_or(false)
 }
 
+/// Launches a long-lived GUI app rooted at `cwd` without waiting for it to exit.
+fn try_spawn_in(command: &str, cwd: &str) -> bool {
+  Command::new(command)
+    .current_dir(cwd)
+    .stdin(Stdio::null())
+    .stdout(Stdio::null())
+    .stderr(Stdio::null())
+    .spawn()
+    .is_ok()
+}
+
 fn run_shell_command(command: &str) -> bool {
   let mut cmd = if cfg!(target_os = "windows") {
     let mut cmd = Command::new("cmd");
@@ -369,8 +478,8 @@ fn spawn_background_install(target: &Path, cmds: &[String]) {
   let _ = cmd.spawn();
 }
 
-fn should_auto_install(app: &tauri::AppHandle) -> bool {
-  let settings = settings::load_settings(app);
+fn should_auto_install(app: &tauri::AppHandle, project_id: Option<&str>) -> bool {
+  let settings = settings::load_settings_for_project(app, project_id);
   settings
     .get("projectPrep")
     .and_then(|v| v.get("autoInstallOnOpenInEditor"))
@@ -378,8 +487,34 @@ fn should_auto_install(app: &tauri::AppHandle) -> bool {
     .unwrap_or(true)
 }
 
+fn is_workspace_root(dir: &Path) -> bool {
+  if dir.join("pnpm-workspace.yaml").exists() {
+    return true;
+  }
+  std::fs::read_to_string(dir.join("package.json"))
+    .ok()
+    .and_then(|raw| serde_json::from_str::<Value>(&raw).ok())
+    .map(|pkg| pkg.get("workspaces").is_some())
+    .unwrap_or(false)
+}
+
+/// Walks up from `target` to the nearest pnpm/yarn/npm workspace root, stopping at the repo root.
+fn find_workspace_root(target: &Path) -> Option<PathBuf> {
+  for dir in target.ancestors() {
+    if is_workspace_root(dir) {
+      return Some(dir.to_path_buf());
+    }
+    if dir.join(".git").exists() {
+      break;
+    }
+  }
+  None
+}
+
 fn maybe_prepare_project(app: &tauri::AppHandle, target_path: &str) {
-  if !should_auto_install(app) {
+  let db_state: tauri::State<db::DbState> = app.state();
+  let project_id = db::project_id_for_path(&db_state, target_path);
+  if !should_auto_install(app, project_id.as_deref()) {
     return;
   }
   let target = Path::new(target_path);
@@ -389,11 +524,12 @@ fn maybe_prepare_project(app: &tauri::AppHandle, target_path: &str) {
   if !target.join("package.json").exists() {
     return;
   }
-  if target.join("node_modules").exists() {
+  let install_root = find_workspace_root(target).unwrap_or_else(|| target.to_path_buf());
+  if install_root.join("node_modules").exists() {
     return;
   }
-  let cmds = pick_node_install_cmds(target);
-  spawn_background_install(target, &cmds);
+  let cmds = pick_node_install_cmds(&install_root);
+  spawn_background_install(&install_root, &cmds);
 }
 
 fn open_in(app: &str, path: &str) -> Result<(), String> {
@@ -402,7 +538,7 @@ fn open_in(app: &str, path: &str) -> Result<(), String> {
   }
   let supported = matches!(
     app,
-    "finder" | "cursor" | "vscode" | "terminal" | "ghostty" | "zed" | "iterm2" | "warp"
+    "finder" | "cursor" | "vscode" | "terminal" | "ghostty" | "zed" | "iterm2" | "warp" | "wave"
   );
   if !supported {
     return Err("Unsupported platform or app".to_string());
@@ -416,6 +552,9 @@ fn open_in(app: &str, path: &str) -> Result<(), String> {
   }
 
   if app == "warp" {
+    if cfg!(target_os = "linux") && try_spawn_in("warp-terminal", path) {
+      return Ok(());
+    }
     let urls = [
       format!("warp://action/new_window?path={}", urlencoding::encode(path)),
       format!(
@@ -428,7 +567,17 @@ fn open_in(app: &str, path: &str) -> Result<(), String> {
         return Ok(());
       }
     }
-    return Err("Warp is not installed or its URI scheme is not registered on this platform.".to_string());
+    return Err("Warp is not installed or not available on this platform.".to_string());
+  }
+
+  if app == "wave" {
+    let opened = (cfg!(target_os = "macos") && try_command("open", &["-a", "Wave", path]))
+      || try_spawn_in("wave", path)
+      || (cfg!(target_os = "linux") && try_spawn_in("waveterm", path));
+    if opened {
+      return Ok(());
+    }
+    return Err("Wave is not installed or not available on this platform.".to_string());
   }
 
   let opened = if cfg!(target_os = "macos") {
@@ -2,7 +2,7 @@ use serde::Deserialize;
 use serde_json::json;
 use crate::runtime::run_blocking;
 use std::net::{TcpStream, ToSocketAddrs};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 fn probe_port(host: &str, port: u16, timeout_ms: u64) -> bool {
   let addr = format!("{}:{}", host, port);
@@ -50,3 +50,38 @@ pub async fn net_probe_ports(args: NetProbeArgs) -> serde_json::Value {
   })
   .await
 }
+
+#[tauri::command]
+pub async fn net_wait_for_port(
+  host: Option<String>,
+  port: i64,
+  timeout_ms: Option<u64>,
+  interval_ms: Option<u64>,
+) -> serde_json::Value {
+  run_blocking(json!({ "ready": false, "waitedMs": 0 }), move || {
+    if port <= 0 || port > 65535 {
+      return json!({ "ready": false, "waitedMs": 0 });
+    }
+    let h = host.as_deref().unwrap_or("").trim().to_string();
+    let host = if h.is_empty() { "localhost".to_string() } else { h };
+    let timeout = Duration::from_millis(timeout_ms.unwrap_or(30_000).min(300_000));
+    let interval = Duration::from_millis(interval_ms.unwrap_or(250).max(50));
+    let started = Instant::now();
+
+    loop {
+      let remaining = timeout.saturating_sub(started.elapsed());
+      let attempt_ms = remaining.as_millis().clamp(1, 1_000) as u64;
+      if probe_port(&host, port as u16, attempt_ms) {
+        return json!({ "ready": true, "waitedMs": started.elapsed().as_millis() as u64 });
+      }
+      let remaining = timeout.saturating_sub(started.elapsed());
+      if remaining.is_zero() {
+        break;
+      }
+      std::thread::sleep(interval.min(remaining));
+    }
+
+    json!({ "ready": false, "waitedMs": started.elapsed().as_millis() as u64 })
+  })
+  .await
+}
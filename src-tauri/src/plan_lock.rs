@@ -3,6 +3,9 @@ use serde_json::json;
 use crate::runtime::run_blocking;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const DEFAULT_LOCK_TTL_MS: u64 = 2 * 60 * 60 * 1000;
 
 #[derive(Serialize, Deserialize, Clone)]
 struct Entry {
@@ -10,6 +13,74 @@ struct Entry {
   m: u32,
 }
 
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+struct LockMeta {
+  holder: Option<String>,
+  acquired_at: u64,
+}
+
+struct LockInfo {
+  holder: Option<String>,
+  acquired_at: u64,
+  age_ms: u64,
+}
+
+fn now_ms() -> u64 {
+  SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|d| d.as_millis() as u64)
+    .unwrap_or(0)
+}
+
+fn state_path(root: &Path) -> PathBuf {
+  root.join(".emdash").join(".planlock.json")
+}
+
+fn meta_path(root: &Path) -> PathBuf {
+  root.join(".emdash").join(".planlock.meta.json")
+}
+
+fn write_meta(root: &Path, holder: Option<String>) {
+  let meta = LockMeta {
+    holder,
+    acquired_at: now_ms(),
+  };
+  let _ = fs::write(
+    meta_path(root),
+    serde_json::to_string(&meta).unwrap_or_else(|_| "{}".into()),
+  );
+}
+
+fn clear_meta(root: &Path) {
+  let _ = fs::remove_file(meta_path(root));
+}
+
+fn read_lock_info(root: &Path) -> Option<LockInfo> {
+  let state = state_path(root);
+  if !state.exists() {
+    return None;
+  }
+  let meta: Option<LockMeta> = fs::read_to_string(meta_path(root))
+    .ok()
+    .and_then(|raw| serde_json::from_str(&raw).ok());
+  // Locks taken before metadata existed fall back to the state file's mtime.
+  let acquired_at = match &meta {
+    Some(m) => m.acquired_at,
+    None => fs::metadata(&state)
+      .and_then(|m| m.modified())
+      .ok()
+      .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+      .map(|d| d.as_millis() as u64)
+      .unwrap_or(0),
+  };
+  Some(LockInfo {
+    holder: meta.and_then(|m| m.holder),
+    acquired_at,
+    age_ms: now_ms().saturating_sub(acquired_at),
+  })
+}
+
 fn is_symlink(path: &Path) -> bool {
   fs::symlink_metadata(path)
     .map(|meta| meta.file_type().is_symlink())
@@ -92,7 +163,7 @@ fn apply_lock(root: &Path) -> Result<usize, String> {
     }
   }
 
-  let state_path = root.join(".emdash").join(".planlock.json");
+  let state_path = state_path(root);
   if let Some(parent) = state_path.parent() {
     let _ = fs::create_dir_all(parent);
   }
@@ -130,7 +201,7 @@ fn apply_lock(root: &Path) -> Result<usize, String> {
     }
   }
 
-  let state_path = root.join(".emdash").join(".planlock.json");
+  let state_path = state_path(root);
   if let Some(parent) = state_path.parent() {
     let _ = fs::create_dir_all(parent);
   }
@@ -142,7 +213,7 @@ fn apply_lock(root: &Path) -> Result<usize, String> {
 #[cfg(unix)]
 fn release_lock(root: &Path) -> Result<usize, String> {
   use std::os::unix::fs::PermissionsExt;
-  let state_path = root.join(".emdash").join(".planlock.json");
+  let state_path = state_path(root);
   if !state_path.exists() {
     return Ok(0);
   }
@@ -161,7 +232,7 @@ fn release_lock(root: &Path) -> Result<usize, String> {
 
 #[cfg(windows)]
 fn release_lock(root: &Path) -> Result<usize, String> {
-  let state_path = root.join(".emdash").join(".planlock.json");
+  let state_path = state_path(root);
   if !state_path.exists() {
     return Ok(0);
   }
@@ -192,40 +263,189 @@ fn release_lock(root: &Path) -> Result<usize, String> {
 #[serde(rename_all = "camelCase")]
 pub struct PlanLockArgs {
   task_path: String,
+  holder: Option<String>,
+  force: Option<bool>,
+  ttl_ms: Option<u64>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanUnlockArgs {
+  task_path: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanLockStatusArgs {
+  task_path: String,
+  ttl_ms: Option<u64>,
+}
+
+fn plan_lock_sync(args: PlanLockArgs) -> serde_json::Value {
+  let root = Path::new(args.task_path.trim());
+  if args.task_path.trim().is_empty() {
+    return json!({ "success": false, "changed": 0, "error": "taskPath is required" });
+  }
+  let ttl_ms = args.ttl_ms.unwrap_or(DEFAULT_LOCK_TTL_MS);
+  let holder = args
+    .holder
+    .map(|h| h.trim().to_string())
+    .filter(|h| !h.is_empty());
+  if let Some(info) = read_lock_info(root) {
+    let expired = ttl_ms > 0 && info.age_ms >= ttl_ms;
+    // An unattributed lock (or one taken by the same holder) is simply re-applied, so
+    // re-enabling plan mode on a task that is already locked keeps it locked.
+    let same_holder = info.holder.is_none() || info.holder == holder;
+    if !args.force.unwrap_or(false) && !expired && !same_holder {
+      return json!({
+        "success": false,
+        "changed": 0,
+        "error": "Plan lock is already held",
+        "heldBy": info.holder,
+        "ageMs": info.age_ms
+      });
+    }
+    // Restore the previous holder's permissions before re-applying so the saved
+    // modes stay accurate.
+    if let Err(err) = release_lock(root) {
+      return json!({ "success": false, "changed": 0, "error": err });
+    }
+    clear_meta(root);
+  }
+  match apply_lock(root) {
+    Ok(changed) => {
+      write_meta(root, holder);
+      json!({ "success": true, "changed": changed })
+    }
+    Err(err) => json!({ "success": false, "changed": 0, "error": err }),
+  }
 }
 
 #[tauri::command]
 pub async fn plan_lock(args: PlanLockArgs) -> serde_json::Value {
   run_blocking(
     json!({ "success": false, "changed": 0, "error": "Task cancelled" }),
-    move || {
-      let root = Path::new(args.task_path.trim());
-      if args.task_path.trim().is_empty() {
-        return json!({ "success": false, "changed": 0, "error": "taskPath is required" });
-      }
-      match apply_lock(root) {
-        Ok(changed) => json!({ "success": true, "changed": changed }),
-        Err(err) => json!({ "success": false, "changed": 0, "error": err }),
-      }
-    },
+    move || plan_lock_sync(args),
   )
   .await
 }
 
+fn plan_lock_status_sync(args: PlanLockStatusArgs) -> serde_json::Value {
+  if args.task_path.trim().is_empty() {
+    return json!({ "success": false, "error": "taskPath is required" });
+  }
+  let root = Path::new(args.task_path.trim());
+  let ttl_ms = args.ttl_ms.unwrap_or(DEFAULT_LOCK_TTL_MS);
+  match read_lock_info(root) {
+    Some(info) => json!({
+      "success": true,
+      "locked": true,
+      "heldBy": info.holder,
+      "acquiredAt": info.acquired_at,
+      "ageMs": info.age_ms,
+      "expired": ttl_ms > 0 && info.age_ms >= ttl_ms
+    }),
+    None => json!({ "success": true, "locked": false }),
+  }
+}
+
 #[tauri::command]
-pub async fn plan_unlock(args: PlanLockArgs) -> serde_json::Value {
+pub async fn plan_lock_status(args: PlanLockStatusArgs) -> serde_json::Value {
+  run_blocking(
+    json!({ "success": false, "error": "Task cancelled" }),
+    move || plan_lock_status_sync(args),
+  )
+  .await
+}
+
+fn plan_unlock_sync(args: PlanUnlockArgs) -> serde_json::Value {
+  if args.task_path.trim().is_empty() {
+    return json!({ "success": false, "restored": 0, "error": "taskPath is required" });
+  }
+  let root = Path::new(args.task_path.trim());
+  match release_lock(root) {
+    Ok(restored) => {
+      clear_meta(root);
+      json!({ "success": true, "restored": restored })
+    }
+    Err(err) => json!({ "success": false, "restored": 0, "error": err }),
+  }
+}
+
+#[tauri::command]
+pub async fn plan_unlock(args: PlanUnlockArgs) -> serde_json::Value {
   run_blocking(
     json!({ "success": false, "restored": 0, "error": "Task cancelled" }),
-    move || {
-      if args.task_path.trim().is_empty() {
-        return json!({ "success": false, "restored": 0, "error": "taskPath is required" });
-      }
-      let root = Path::new(args.task_path.trim());
-      match release_lock(root) {
-        Ok(restored) => json!({ "success": true, "restored": restored }),
-        Err(err) => json!({ "success": false, "restored": 0, "error": err }),
-      }
-    },
+    move || plan_unlock_sync(args),
   )
   .await
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn temp_task(label: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("emdash-{}-{}", label, uuid::Uuid::new_v4()));
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(root.join("src").join("main.rs"), "fn main() {}\n").unwrap();
+    root
+  }
+
+  fn lock(root: &Path, holder: Option<&str>) -> serde_json::Value {
+    plan_lock_sync(PlanLockArgs {
+      task_path: root.to_string_lossy().to_string(),
+      holder: holder.map(str::to_string),
+      force: None,
+      ttl_ms: None,
+    })
+  }
+
+  fn status(root: &Path, ttl_ms: Option<u64>) -> serde_json::Value {
+    plan_lock_status_sync(PlanLockStatusArgs {
+      task_path: root.to_string_lossy().to_string(),
+      ttl_ms,
+    })
+  }
+
+  fn unlock(root: &Path) -> serde_json::Value {
+    plan_unlock_sync(PlanUnlockArgs { task_path: root.to_string_lossy().to_string() })
+  }
+
+  #[test]
+  fn status_reports_holder_and_expiry() {
+    let root = temp_task("plan-status");
+    assert_eq!(status(&root, None), json!({ "success": true, "locked": false }));
+
+    assert_eq!(lock(&root, Some("agent-a"))["success"], json!(true));
+    let held = status(&root, None);
+    assert_eq!(held["locked"], json!(true));
+    assert_eq!(held["heldBy"], json!("agent-a"));
+    assert_eq!(held["expired"], json!(false));
+
+    std::thread::sleep(std::time::Duration::from_millis(5));
+    assert_eq!(status(&root, Some(1))["expired"], json!(true));
+    assert_eq!(status(&root, Some(0))["expired"], json!(false));
+
+    assert_eq!(unlock(&root)["success"], json!(true));
+    assert_eq!(status(&root, None)["locked"], json!(false));
+    let _ = fs::remove_dir_all(&root);
+  }
+
+  #[test]
+  fn relocking_is_allowed_only_for_the_same_or_no_holder() {
+    let root = temp_task("plan-relock");
+    assert_eq!(lock(&root, None)["success"], json!(true));
+    assert_eq!(lock(&root, None)["success"], json!(true));
+    assert_eq!(status(&root, None)["locked"], json!(true));
+
+    assert_eq!(lock(&root, Some("agent-a"))["success"], json!(true));
+    assert_eq!(lock(&root, Some("agent-a"))["success"], json!(true));
+    let taken = lock(&root, Some("agent-b"));
+    assert_eq!(taken["success"], json!(false));
+    assert_eq!(taken["heldBy"], json!("agent-a"));
+
+    assert_eq!(unlock(&root)["success"], json!(true));
+    let _ = fs::remove_dir_all(&root);
+  }
+}
@@ -15,6 +15,14 @@ pub struct ProviderStatus {
   pub installed: bool,
   pub path: Option<String>,
   pub version: Option<String>,
+  #[serde(default)]
+  pub version_output: Option<String>,
+  #[serde(default)]
+  pub min_version: Option<String>,
+  #[serde(default)]
+  pub satisfied: Option<bool>,
+  #[serde(default)]
+  pub install_hint: Option<String>,
   pub last_checked: i64,
 }
 
@@ -77,6 +85,8 @@ struct ProviderDef {
   id: &'static str,
   commands: &'static [&'static str],
   args: &'static [&'static str],
+  min_version: Option<&'static str>,
+  install_hint: &'static str,
 }
 
 const PROVIDERS: &[ProviderDef] = &[
@@ -84,96 +94,134 @@ const PROVIDERS: &[ProviderDef] = &[
     id: "codex",
     commands: &["codex"],
     args: &["--version"],
+    min_version: None,
+    install_hint: "npm install -g @openai/codex",
   },
   ProviderDef {
     id: "claude",
     commands: &["claude"],
     args: &["--version"],
+    min_version: Some("1.0.0"),
+    install_hint: "npm install -g @anthropic-ai/claude-code",
   },
   ProviderDef {
     id: "cursor",
     commands: &["cursor-agent", "cursor"],
     args: &["--version"],
+    min_version: None,
+    install_hint: "curl https://cursor.com/install -fsS | bash",
   },
   ProviderDef {
     id: "gemini",
     commands: &["gemini"],
     args: &["--version"],
+    min_version: None,
+    install_hint: "npm install -g @google/gemini-cli",
   },
   ProviderDef {
     id: "qwen",
     commands: &["qwen"],
     args: &["--version"],
+    min_version: None,
+    install_hint: "npm install -g @qwen-code/qwen-code",
   },
   ProviderDef {
     id: "droid",
     commands: &["droid"],
     args: &["--version"],
+    min_version: None,
+    install_hint: "curl -fsSL https://app.factory.ai/cli | sh",
   },
   ProviderDef {
     id: "amp",
     commands: &["amp"],
     args: &["--version"],
+    min_version: None,
+    install_hint: "npm install -g @sourcegraph/amp@latest",
   },
   ProviderDef {
     id: "opencode",
     commands: &["opencode"],
     args: &["--version"],
+    min_version: None,
+    install_hint: "npm install -g opencode-ai",
   },
   ProviderDef {
     id: "copilot",
     commands: &["copilot"],
     args: &["--version"],
+    min_version: None,
+    install_hint: "npm install -g @github/copilot",
   },
   ProviderDef {
     id: "charm",
     commands: &["crush"],
     args: &["--version"],
+    min_version: None,
+    install_hint: "npm install -g @charmland/crush",
   },
   ProviderDef {
     id: "auggie",
     commands: &["auggie"],
     args: &["--version"],
+    min_version: None,
+    install_hint: "npm install -g @augmentcode/auggie",
   },
   ProviderDef {
     id: "goose",
     commands: &["goose"],
     args: &["--version"],
+    min_version: None,
+    install_hint: "curl -fsSL https://github.com/block/goose/releases/download/stable/download_cli.sh | bash",
   },
   ProviderDef {
     id: "kimi",
     commands: &["kimi"],
     args: &["--version"],
+    min_version: None,
+    install_hint: "uv tool install kimi-cli",
   },
   ProviderDef {
     id: "kilocode",
     commands: &["kilocode"],
     args: &["--version"],
+    min_version: None,
+    install_hint: "npm install -g @kilocode/cli",
   },
   ProviderDef {
     id: "kiro",
     commands: &["kiro-cli", "kiro"],
     args: &["--version"],
+    min_version: None,
+    install_hint: "curl -fsSL https://cli.kiro.dev/install | bash",
   },
   ProviderDef {
     id: "rovo",
     commands: &["rovodev", "acli"],
     args: &["--version"],
+    min_version: None,
+    install_hint: "acli rovodev auth login",
   },
   ProviderDef {
     id: "cline",
     commands: &["cline"],
     args: &["help"],
+    min_version: None,
+    install_hint: "npm install -g cline",
   },
   ProviderDef {
     id: "codebuff",
     commands: &["codebuff"],
     args: &["--version"],
+    min_version: None,
+    install_hint: "npm install -g codebuff",
   },
   ProviderDef {
     id: "mistral",
     commands: &["vibe"],
     args: &["-h"],
+    min_version: None,
+    install_hint: "curl -LsSf https://mistral.ai/vibe/install.sh | bash",
   },
 ];
 
@@ -181,6 +229,34 @@ pub fn is_valid_provider_id(id: &str) -> bool {
   PROVIDERS.iter().any(|provider| provider.id == id)
 }
 
+pub fn version_args(id: &str) -> Option<&'static [&'static str]> {
+  PROVIDERS.iter().find(|provider| provider.id == id).map(|provider| provider.args)
+}
+
+fn parse_loose_version(raw: &str) -> Option<semver::Version> {
+  let mut parts: Vec<&str> = raw.trim().split('.').filter(|p| !p.is_empty()).take(3).collect();
+  while parts.len() < 3 {
+    parts.push("0");
+  }
+  semver::Version::parse(&parts.join(".")).ok()
+}
+
+fn version_satisfies(version: Option<&str>, min_version: Option<&str>) -> Option<bool> {
+  let min = parse_loose_version(min_version?)?;
+  let current = parse_loose_version(version?)?;
+  Some(current >= min)
+}
+
+fn first_output_line(result: &CommandResult) -> Option<String> {
+  result
+    .stdout
+    .lines()
+    .chain(result.stderr.lines())
+    .map(|line| line.trim())
+    .find(|line| !line.is_empty())
+    .map(|line| line.to_string())
+}
+
 #[derive(Default, Clone)]
 struct CommandResult {
   command: String,
@@ -358,6 +434,31 @@ fn compute_status(result: &CommandResult) -> bool {
   result.success
 }
 
+const PREFLIGHT_TIMEOUT_MS: u64 = 3000;
+
+fn tool_probe(tool: &str, result: &CommandResult) -> Value {
+  json!({
+    "tool": tool,
+    "present": compute_status(result),
+    "version": result.version,
+    "path": result.resolved_path
+  })
+}
+
+/// Probes a non-provider CLI with the same detection used for provider statuses.
+pub(crate) fn probe_tool(tool: &str, command: &str, args: &[&str]) -> Value {
+  tool_probe(tool, &run_command(command, args, PREFLIGHT_TIMEOUT_MS))
+}
+
+pub(crate) fn provider_ids() -> Vec<&'static str> {
+  PROVIDERS.iter().map(|provider| provider.id).collect()
+}
+
+pub(crate) fn probe_provider(id: &str) -> Option<Value> {
+  let def = PROVIDERS.iter().find(|provider| provider.id == id)?;
+  Some(tool_probe(def.id, &check_provider(def, PREFLIGHT_TIMEOUT_MS)))
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ProviderStatusOptions {
@@ -410,16 +511,31 @@ pub async fn providers_get_statuses(
       let def_copy = *def;
       handles.push(tauri::async_runtime::spawn_blocking(move || {
         let res = check_provider(&def_copy, TIMEOUT_MS);
-        (def_copy.id, res)
+        (def_copy, res)
       }));
     }
 
     for handle in handles {
-      if let Ok((id, res)) = handle.await {
+      if let Ok((def, res)) = handle.await {
+        let id = def.id;
+        let installed = compute_status(&res);
+        let satisfied = if installed {
+          version_satisfies(res.version.as_deref(), def.min_version)
+        } else {
+          None
+        };
         let status = ProviderStatus {
-          installed: compute_status(&res),
-          path: res.resolved_path,
-          version: res.version,
+          installed,
+          path: res.resolved_path.clone(),
+          version: res.version.clone(),
+          version_output: first_output_line(&res),
+          min_version: def.min_version.map(|v| v.to_string()),
+          satisfied,
+          install_hint: if installed {
+            None
+          } else {
+            Some(def.install_hint.to_string())
+          },
           last_checked: chrono::Utc::now().timestamp_millis(),
         };
         state.set(id, status.clone());
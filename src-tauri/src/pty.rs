@@ -1,4 +1,7 @@
-use crate::terminal_snapshots::{self, TerminalSnapshotPayload};
+use crate::db::{self, DbState};
+use crate::runtime::run_blocking;
+use crate::settings;
+use crate::terminal_snapshots::{self, PtySessionRecord, TerminalSnapshotPayload};
 use portable_pty::{native_pty_system, ChildKiller, CommandBuilder, MasterPty, PtySize};
 use serde::Deserialize;
 use serde_json::{json, Value};
@@ -6,13 +9,27 @@ use std::collections::HashMap;
 use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
-use tauri::{AppHandle, Emitter, State, Window};
+use tauri::{AppHandle, Emitter, Manager, State, Window};
+
+const SCROLLBACK_MAX_BYTES: usize = 2 * 1024 * 1024;
+const WRITE_FILE_MAX_BYTES: u64 = 16 * 1024 * 1024;
+const WRITE_FILE_CHUNK_BYTES: usize = 4096;
+
+#[derive(Clone)]
+struct PtyLaunch {
+  cwd: Option<String>,
+  shell: Option<String>,
+  command: Option<String>,
+  env: HashMap<String, String>,
+}
 
 #[derive(Clone)]
 struct PtyHandle {
   writer: Arc<Mutex<Box<dyn Write + Send>>>,
   master: Arc<Mutex<Box<dyn MasterPty + Send>>>,
   killer: Arc<Mutex<Box<dyn ChildKiller + Send + Sync>>>,
+  launch: PtyLaunch,
+  scrollback: Arc<Mutex<String>>,
 }
 
 #[derive(Default, Clone)]
@@ -58,6 +75,7 @@ fn resolve_cwd(cwd: &Option<String>) -> PathBuf {
 
 fn build_env(
   default_shell: &str,
+  task_env: HashMap<String, String>,
   overrides: Option<HashMap<String, String>>,
 ) -> HashMap<String, String> {
   let mut env = HashMap::new();
@@ -83,12 +101,37 @@ fn build_env(
     env.insert("SSH_AUTH_SOCK".to_string(), sock);
   }
 
+  env.extend(task_env);
   if let Some(extra) = overrides {
     env.extend(extra);
   }
   env
 }
 
+fn is_valid_env_name(name: &str) -> bool {
+  let mut chars = name.chars();
+  matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+    && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// The `env` map stored in the metadata of the task whose worktree is `cwd`.
+fn task_env(app: &AppHandle, cwd: Option<&str>) -> HashMap<String, String> {
+  let Some(cwd) = cwd.map(str::trim).filter(|v| !v.is_empty()) else {
+    return HashMap::new();
+  };
+  let db_state: State<DbState> = app.state();
+  db::task_metadata_for_path(&db_state, cwd)
+    .and_then(|meta| meta.get("env").and_then(|v| v.as_object()).cloned())
+    .map(|env| {
+      env
+        .into_iter()
+        .filter_map(|(key, value)| Some((key, value.as_str()?.to_string())))
+        .filter(|(key, _)| is_valid_env_name(key))
+        .collect()
+    })
+    .unwrap_or_default()
+}
+
 fn shell_basename(shell: &str) -> String {
   Path::new(shell)
     .file_name()
@@ -125,6 +168,18 @@ fn build_shell_args(shell: &str, command: Option<&str>) -> Vec<String> {
   }
 }
 
+fn push_scrollback(scrollback: &Mutex<String>, chunk: &str) {
+  let mut buf = scrollback.lock().unwrap();
+  buf.push_str(chunk);
+  if buf.len() > SCROLLBACK_MAX_BYTES {
+    let mut cut = buf.len() - SCROLLBACK_MAX_BYTES;
+    while !buf.is_char_boundary(cut) {
+      cut += 1;
+    }
+    buf.drain(..cut);
+  }
+}
+
 fn build_command_chain(command: Option<&str>, shell_path: &str) -> Option<String> {
   let cmd = command?;
   if cfg!(target_os = "windows") {
@@ -140,6 +195,16 @@ pub fn pty_start(
   app: AppHandle,
   state: State<PtyState>,
   args: PtyStartArgs,
+) -> Result<Value, String> {
+  start_pty(&window, &app, &state, args, String::new())
+}
+
+fn start_pty(
+  window: &Window,
+  app: &AppHandle,
+  state: &PtyState,
+  args: PtyStartArgs,
+  scrollback: String,
 ) -> Result<Value, String> {
   if std::env::var("EMDASH_DISABLE_PTY").map(|v| v == "1").unwrap_or(false) {
     return Ok(json!({ "ok": false, "error": "PTY disabled via EMDASH_DISABLE_PTY=1" }));
@@ -175,7 +240,13 @@ pub fn pty_start(
     shell_path.clone()
   };
   let shell_args = build_shell_args(&launch_shell, command_chain.as_deref());
-  let env = build_env(&default_shell, args.env);
+  let launch = PtyLaunch {
+    cwd: args.cwd.clone(),
+    shell: args.shell.clone(),
+    command: args.command.clone(),
+    env: args.env.clone().unwrap_or_default(),
+  };
+  let env = build_env(&default_shell, task_env(app, args.cwd.as_deref()), args.env);
 
   let pty_system = native_pty_system();
   let pair = pty_system
@@ -215,7 +286,10 @@ pub fn pty_start(
     writer: Arc::new(Mutex::new(writer)),
     master: Arc::new(Mutex::new(pair.master)),
     killer: Arc::new(Mutex::new(child.clone_killer())),
+    launch,
+    scrollback: Arc::new(Mutex::new(scrollback)),
   };
+  let scrollback = handle.scrollback.clone();
 
   state.inner.lock().unwrap().insert(id.clone(), handle);
 
@@ -230,6 +304,7 @@ pub fn pty_start(
         Ok(0) => break,
         Ok(n) => {
           let chunk = String::from_utf8_lossy(&buf[..n]).to_string();
+          push_scrollback(&scrollback, &chunk);
           let _ = app_handle.emit_to(&label, &data_event, chunk);
         }
         Err(_) => break,
@@ -275,6 +350,78 @@ pub fn pty_input(state: State<PtyState>, id: String, data: String) -> Result<(),
   Ok(())
 }
 
+fn write_file_to_pty(
+  writer: &Mutex<Box<dyn Write + Send>>,
+  file_path: &str,
+  terminator: Option<&str>,
+) -> Result<usize, String> {
+  let path = Path::new(file_path);
+  let size = std::fs::metadata(path).map_err(|err| err.to_string())?.len();
+  if size > WRITE_FILE_MAX_BYTES {
+    return Err(format!("File exceeds {} bytes", WRITE_FILE_MAX_BYTES));
+  }
+  let contents = std::fs::read(path).map_err(|err| err.to_string())?;
+  let mut writer = writer.lock().unwrap();
+  for chunk in contents.chunks(WRITE_FILE_CHUNK_BYTES) {
+    writer.write_all(chunk).map_err(|err| err.to_string())?;
+    writer.flush().map_err(|err| err.to_string())?;
+  }
+  let mut written = contents.len();
+  if let Some(terminator) = terminator.filter(|t| !t.is_empty()) {
+    writer.write_all(terminator.as_bytes()).map_err(|err| err.to_string())?;
+    let _ = writer.flush();
+    written += terminator.len();
+  }
+  Ok(written)
+}
+
+/// Streams a file into the PTY off the main thread, since a busy child can block large writes.
+#[tauri::command]
+pub async fn pty_write_file(
+  state: State<'_, PtyState>,
+  id: String,
+  file_path: String,
+  terminator: Option<String>,
+) -> Result<Value, String> {
+  let Some(handle) = state.inner.lock().unwrap().get(&id).cloned() else {
+    return Ok(json!({ "ok": false, "error": "No running terminal with that id" }));
+  };
+  let result = run_blocking(Err("pty_write_file failed".to_string()), move || {
+    write_file_to_pty(&handle.writer, &file_path, terminator.as_deref())
+  })
+  .await;
+  match result {
+    Ok(bytes) => Ok(json!({ "ok": true, "bytes": bytes })),
+    Err(err) => Ok(json!({ "ok": false, "error": err })),
+  }
+}
+
+/// Replaces the env vars applied to every terminal opened in the task's worktree.
+/// The caller's own `env` passed to `pty_start` still takes precedence.
+#[tauri::command]
+pub async fn task_set_env(app: AppHandle, task_id: String, env: HashMap<String, String>) -> Value {
+  run_blocking(json!({ "ok": false, "error": "Task cancelled" }), move || {
+    let task_id = task_id.trim();
+    if task_id.is_empty() {
+      return json!({ "ok": false, "error": "taskId is required" });
+    }
+    if let Some(key) = env.keys().find(|key| !is_valid_env_name(key)) {
+      return json!({ "ok": false, "error": format!("Invalid environment variable name: {}", key) });
+    }
+    if let Some(key) = env.iter().find(|(_, value)| value.contains('\0')).map(|(key, _)| key) {
+      return json!({ "ok": false, "error": format!("Value for {} contains a NUL byte", key) });
+    }
+
+    let db_state: State<DbState> = app.state();
+    let stored = if env.is_empty() { None } else { Some(json!(env)) };
+    match db::update_task_metadata_key(&db_state, task_id, "env", stored) {
+      Ok(()) => json!({ "ok": true, "env": env }),
+      Err(err) => json!({ "ok": false, "error": err }),
+    }
+  })
+  .await
+}
+
 #[tauri::command]
 pub fn pty_resize(state: State<PtyState>, id: String, cols: u16, rows: u16) -> Result<(), String> {
   let handle = state.inner.lock().unwrap().get(&id).cloned();
@@ -322,6 +469,16 @@ pub fn pty_snapshot_save(
   }
 }
 
+#[tauri::command]
+pub fn pty_snapshot_trim(app: AppHandle, id: String, max_bytes: Option<usize>) -> Result<Value, String> {
+  let max_bytes = max_bytes.unwrap_or_else(|| terminal_snapshots::snapshot_data_cap(&app));
+  match terminal_snapshots::trim_snapshot(&app, &id, max_bytes) {
+    Ok(Some(bytes)) => Ok(json!({ "ok": true, "bytes": bytes })),
+    Ok(None) => Ok(json!({ "ok": false, "error": "Snapshot not found" })),
+    Err(err) => Ok(json!({ "ok": false, "error": err })),
+  }
+}
+
 #[tauri::command]
 pub fn pty_snapshot_clear(app: AppHandle, id: String) -> Result<Value, String> {
   match terminal_snapshots::delete_snapshot(&app, &id) {
@@ -331,7 +488,114 @@ pub fn pty_snapshot_clear(app: AppHandle, id: String) -> Result<Value, String> {
 }
 
 #[tauri::command]
-pub fn terminal_get_theme() -> Result<Value, String> {
+pub fn pty_save_session(
+  app: AppHandle,
+  state: State<PtyState>,
+  id: String,
+  cols: Option<u16>,
+  rows: Option<u16>,
+) -> Result<Value, String> {
+  let Some(handle) = state.inner.lock().unwrap().get(&id).cloned() else {
+    return Ok(json!({ "ok": false, "error": "No running terminal with that id" }));
+  };
+  let size = handle.master.lock().unwrap().get_size().ok();
+  let record = PtySessionRecord {
+    version: terminal_snapshots::PTY_SESSION_VERSION,
+    saved_at: chrono::Utc::now().to_rfc3339(),
+    cwd: handle.launch.cwd.clone(),
+    shell: handle.launch.shell.clone(),
+    command: handle.launch.command.clone(),
+    env_keys: {
+      let mut keys: Vec<String> = handle.launch.env.keys().cloned().collect();
+      keys.sort();
+      keys
+    },
+    cols: cols.or(size.map(|s| s.cols)).unwrap_or(80),
+    rows: rows.or(size.map(|s| s.rows)).unwrap_or(24),
+    scrollback: handle.scrollback.lock().unwrap().clone(),
+  };
+  match terminal_snapshots::save_session(&app, &id, &record) {
+    Ok(_) => Ok(json!({ "ok": true })),
+    Err(err) => Ok(json!({ "ok": false, "error": err })),
+  }
+}
+
+/// Relaunches a saved session's command in its original cwd. The child process itself cannot be
+/// reattached, so the saved scrollback is returned for the renderer to replay before live output.
+/// Env override values aren't persisted; the caller passes them again in `env`, and `envKeys`
+/// lists the names the session was launched with.
+#[tauri::command]
+pub fn pty_restore_session(
+  window: Window,
+  app: AppHandle,
+  state: State<PtyState>,
+  id: String,
+  env: Option<HashMap<String, String>>,
+) -> Result<Value, String> {
+  let record = match terminal_snapshots::get_session(&app, &id) {
+    Ok(Some(record)) => record,
+    Ok(None) => return Ok(json!({ "ok": false, "error": "No saved session" })),
+    Err(err) => return Ok(json!({ "ok": false, "error": err })),
+  };
+  if state.inner.lock().unwrap().contains_key(&id) {
+    return Ok(json!({ "ok": false, "error": "Terminal is already running" }));
+  }
+  let args = PtyStartArgs {
+    id: id.clone(),
+    cwd: record.cwd,
+    shell: record.shell,
+    command: record.command,
+    env,
+    cols: Some(record.cols),
+    rows: Some(record.rows),
+    auto_approve: None,
+    initial_prompt: None,
+    skip_resume: None,
+  };
+  let started = start_pty(&window, &app, &state, args, record.scrollback.clone())?;
+  if started.get("ok").and_then(|v| v.as_bool()) != Some(true) {
+    return Ok(started);
+  }
+  Ok(json!({
+    "ok": true,
+    "scrollback": record.scrollback,
+    "savedAt": record.saved_at,
+    "envKeys": record.env_keys
+  }))
+}
+
+#[tauri::command]
+pub fn terminal_set_theme(app: AppHandle, theme: Value) -> Result<Value, String> {
+  if theme.is_null() {
+    settings::replace_terminal_theme(&app, Value::Null);
+    return Ok(json!({ "ok": true, "theme": null }));
+  }
+  match settings::validate_terminal_theme(&theme) {
+    Ok(theme) => {
+      settings::replace_terminal_theme(&app, theme.clone());
+      Ok(json!({ "ok": true, "theme": theme }))
+    }
+    Err(err) => Ok(json!({ "ok": false, "error": err })),
+  }
+}
+
+#[tauri::command]
+pub fn terminal_get_theme(app: AppHandle) -> Result<Value, String> {
+  let custom = settings::load_settings(&app)
+    .get("terminal")
+    .and_then(|terminal| terminal.get("customTheme"))
+    .filter(|theme| theme.is_object())
+    .cloned();
+  if let Some(theme) = custom {
+    return Ok(json!({
+      "ok": true,
+      "config": {
+        "terminal": "Custom",
+        "theme": theme
+      }
+    }));
+  }
+
   if !(cfg!(target_os = "macos") || cfg!(target_os = "linux")) {
     return Ok(json!({ "ok": false, "error": "No terminal configuration found" }));
   }
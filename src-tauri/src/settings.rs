@@ -1,8 +1,12 @@
 use serde_json::{json, Map, Value};
-use std::path::PathBuf;
+use std::fs;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
 use tauri::Manager;
 
-use crate::{providers, storage};
+use crate::{db, providers, storage};
 
 const SETTINGS_FILE: &str = "settings.json";
 
@@ -17,7 +21,8 @@ fn default_settings(app: &tauri::AppHandle) -> Value {
   json!({
     "repository": {
       "branchTemplate": "agent/{slug}-{timestamp}",
-      "pushOnCreate": true
+      "pushOnCreate": true,
+      "signCommits": false
     },
     "projectPrep": {
       "autoInstallOnOpenInEditor": true
@@ -43,6 +48,13 @@ fn default_settings(app: &tauri::AppHandle) -> Value {
     },
     "projects": {
       "defaultDirectory": default_projects_dir(app)
+    },
+    "serviceIcons": {},
+    "providerModels": {},
+    "prGenerationProviders": ["claude", "codex"],
+    "terminal": {
+      "customTheme": null,
+      "snapshotMaxBytes": 2 * 1024 * 1024
     }
   })
 }
@@ -69,6 +81,99 @@ fn merge_value(base: &mut Value, patch: &Value) {
   }
 }
 
+// Like merge_value, but a stored value only replaces a default of the same JSON type,
+// so a hand-edited `"repository": "x"` can't knock out a whole section. Rejected key
+// paths are collected into `invalid`.
+fn merge_over_defaults(
+  base: &mut Value,
+  stored: &Value,
+  key_path: &str,
+  invalid: &mut Vec<String>,
+) {
+  match (base, stored) {
+    (Value::Object(base_map), Value::Object(stored_map)) => {
+      for (key, value) in stored_map {
+        let child_path = if key_path.is_empty() {
+          key.clone()
+        } else {
+          format!("{key_path}.{key}")
+        };
+        match base_map.get_mut(key) {
+          Some(existing) => merge_over_defaults(existing, value, &child_path, invalid),
+          None => {
+            base_map.insert(key.clone(), value.clone());
+          }
+        }
+      }
+    }
+    (base_value, stored_value) => {
+      if base_value.is_null()
+        || std::mem::discriminant(base_value) == std::mem::discriminant(stored_value)
+      {
+        *base_value = stored_value.clone();
+      } else {
+        invalid.push(key_path.to_string());
+      }
+    }
+  }
+}
+
+#[derive(Default)]
+struct LoadWarnings {
+  reported_keys: HashSet<String>,
+  pending: Vec<String>,
+}
+
+fn load_warnings() -> MutexGuard<'static, LoadWarnings> {
+  static WARNINGS: OnceLock<Mutex<LoadWarnings>> = OnceLock::new();
+  match WARNINGS.get_or_init(|| Mutex::new(LoadWarnings::default())).lock() {
+    Ok(guard) => guard,
+    Err(poisoned) => poisoned.into_inner(),
+  }
+}
+
+/// Problems found while loading settings since the last call, for settings_get to return.
+pub fn take_load_warnings() -> Vec<String> {
+  std::mem::take(&mut load_warnings().pending)
+}
+
+// Settings are loaded on hot paths, so each invalid key is only reported the first time.
+fn warn_invalid_once(invalid: Vec<String>) {
+  let mut warnings = load_warnings();
+  for key_path in invalid {
+    if warnings.reported_keys.insert(key_path.clone()) {
+      warnings
+        .pending
+        .push(format!("Ignoring invalid value for {key_path}, using default"));
+    }
+  }
+}
+
+// Moves an unreadable settings file aside so the next write doesn't clobber it.
+fn quarantine_settings_file(path: &Path) {
+  let stamp = SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|d| d.as_millis())
+    .unwrap_or(0);
+  let backup = path.with_extension(format!("json.corrupt-{stamp}"));
+  let warning = match fs::rename(path, &backup) {
+    Ok(()) => format!("Settings file is not valid JSON, moved to {}", backup.display()),
+    Err(err) => format!("Settings file is not valid JSON and could not be backed up: {err}"),
+  };
+  load_warnings().pending.push(warning);
+}
+
+fn read_stored_settings(path: &Path) -> Option<Value> {
+  let raw = fs::read_to_string(path).ok()?;
+  match serde_json::from_str::<Value>(&raw) {
+    Ok(value) if value.is_object() => Some(value),
+    _ => {
+      quarantine_settings_file(path);
+      None
+    }
+  }
+}
+
 fn coerce_bool(value: Option<&Value>, fallback: bool) -> bool {
   value.and_then(|v| v.as_bool()).unwrap_or(fallback)
 }
@@ -80,6 +185,78 @@ fn coerce_string(value: Option<&Value>, fallback: &str) -> String {
   }
 }
 
+// Accepts "example.com", "https://example.com/" etc. and returns a bare lowercase hostname.
+fn normalize_icon_domain(raw: &str) -> Option<String> {
+  let trimmed = raw.trim().to_lowercase();
+  let without_scheme = trimmed
+    .strip_prefix("https://")
+    .or_else(|| trimmed.strip_prefix("http://"))
+    .unwrap_or(&trimmed);
+  let host = without_scheme.split('/').next().unwrap_or("").trim_end_matches('.');
+  let valid = host.contains('.')
+    && !host.starts_with('.')
+    && !host.contains("..")
+    && host
+      .chars()
+      .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-');
+  if valid {
+    Some(host.to_string())
+  } else {
+    None
+  }
+}
+
+const TERMINAL_THEME_KEYS: &[&str] = &[
+  "foreground",
+  "background",
+  "cursor",
+  "black",
+  "red",
+  "green",
+  "yellow",
+  "blue",
+  "magenta",
+  "cyan",
+  "white",
+  "brightBlack",
+  "brightRed",
+  "brightGreen",
+  "brightYellow",
+  "brightBlue",
+  "brightMagenta",
+  "brightCyan",
+  "brightWhite",
+];
+
+fn is_hex_color(value: &str) -> bool {
+  let Some(hex) = value.strip_prefix('#') else {
+    return false;
+  };
+  matches!(hex.len(), 3 | 4 | 6 | 8) && hex.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Validates an xterm-style theme: known color keys only, each a hex string.
+pub fn validate_terminal_theme(theme: &Value) -> Result<Value, String> {
+  let map = theme.as_object().ok_or("Theme must be an object")?;
+  let mut out = Map::new();
+  for (key, value) in map {
+    if !TERMINAL_THEME_KEYS.contains(&key.as_str()) {
+      return Err(format!("Unknown theme color '{}'", key));
+    }
+    let color = value.as_str().map(str::trim).unwrap_or("");
+    if !is_hex_color(color) {
+      return Err(format!("Theme color '{}' must be a hex string like #1e1e1e", key));
+    }
+    out.insert(key.clone(), Value::String(color.to_lowercase()));
+  }
+  for required in ["foreground", "background"] {
+    if !out.contains_key(required) {
+      return Err(format!("Theme is missing '{}'", required));
+    }
+  }
+  Ok(Value::Object(out))
+}
+
 fn normalize_settings(value: Value, app: &tauri::AppHandle) -> Value {
   let mut defaults = default_settings(app);
   merge_value(&mut defaults, &value);
@@ -113,6 +290,23 @@ fn normalize_settings(value: Value, app: &tauri::AppHandle) -> Value {
       "pushOnCreate".to_string(),
       Value::Bool(coerce_bool(repo.get("pushOnCreate"), fallback_push)),
     );
+    repo.insert(
+      "signCommits".to_string(),
+      Value::Bool(coerce_bool(repo.get("signCommits"), false)),
+    );
+    let signing_format = repo
+      .get("signingFormat")
+      .and_then(Value::as_str)
+      .map(|v| v.trim().to_ascii_lowercase())
+      .filter(|v| v == "gpg" || v == "ssh");
+    match signing_format {
+      Some(format) => {
+        repo.insert("signingFormat".to_string(), Value::String(format));
+      }
+      None => {
+        repo.remove("signingFormat");
+      }
+    }
   }
 
   if let Some(project_prep) = obj.get_mut("projectPrep").and_then(Value::as_object_mut) {
@@ -180,6 +374,62 @@ fn normalize_settings(value: Value, app: &tauri::AppHandle) -> Value {
     projects.insert("defaultDirectory".to_string(), Value::String(dir));
   }
 
+  let service_icons: Map<String, Value> = obj
+    .get("serviceIcons")
+    .and_then(Value::as_object)
+    .map(|map| {
+      map
+        .iter()
+        .filter_map(|(service, domain)| {
+          let service = service.trim().to_lowercase();
+          let domain = normalize_icon_domain(domain.as_str()?)?;
+          if service.is_empty() {
+            return None;
+          }
+          Some((service, Value::String(domain)))
+        })
+        .collect()
+    })
+    .unwrap_or_default();
+  obj.insert("serviceIcons".to_string(), Value::Object(service_icons));
+
+  let provider_models: Map<String, Value> = obj
+    .get("providerModels")
+    .and_then(Value::as_object)
+    .map(|map| {
+      map
+        .iter()
+        .filter_map(|(provider, model)| {
+          let model = model.as_str()?.trim();
+          if model.is_empty() || !providers::is_valid_provider_id(provider) {
+            return None;
+          }
+          Some((provider.clone(), Value::String(model.to_string())))
+        })
+        .collect()
+    })
+    .unwrap_or_default();
+  obj.insert("providerModels".to_string(), Value::Object(provider_models));
+
+  let mut pr_providers: Vec<Value> = Vec::new();
+  if let Some(list) = obj.get("prGenerationProviders").and_then(Value::as_array) {
+    for id in list.iter().filter_map(Value::as_str).map(str::trim) {
+      if providers::is_valid_provider_id(id) && !pr_providers.iter().any(|v| v == id) {
+        pr_providers.push(Value::String(id.to_string()));
+      }
+    }
+  }
+  obj.insert("prGenerationProviders".to_string(), Value::Array(pr_providers));
+
+  if let Some(terminal) = obj.get_mut("terminal").and_then(Value::as_object_mut) {
+    let theme = terminal
+      .get("customTheme")
+      .filter(|theme| !theme.is_null())
+      .and_then(|theme| validate_terminal_theme(theme).ok())
+      .unwrap_or(Value::Null);
+    terminal.insert("customTheme".to_string(), theme);
+  }
+
   let normalized_provider = obj
     .get("defaultProvider")
     .and_then(Value::as_str)
@@ -198,16 +448,91 @@ fn normalize_settings(value: Value, app: &tauri::AppHandle) -> Value {
 pub fn load_settings(app: &tauri::AppHandle) -> Value {
   let path = settings_path(app);
   let mut base = default_settings(app);
-  if let Some(existing) = storage::read_json(&path) {
-    merge_value(&mut base, &existing);
+  if let Some(existing) = read_stored_settings(&path) {
+    let mut invalid = Vec::new();
+    merge_over_defaults(&mut base, &existing, "", &mut invalid);
+    warn_invalid_once(invalid);
   }
   normalize_settings(base, app)
 }
 
+/// Trims a project's override object down to the keys that may shadow global settings.
+pub fn normalize_project_overrides(value: &Value) -> Value {
+  let mut out = Map::new();
+
+  let mut repository = Map::new();
+  if let Some(repo) = value.get("repository") {
+    let template = repo
+      .get("branchTemplate")
+      .and_then(Value::as_str)
+      .map(str::trim)
+      .filter(|t| !t.is_empty());
+    if let Some(template) = template {
+      let limited: String = template.chars().take(200).collect();
+      repository.insert("branchTemplate".to_string(), Value::String(limited));
+    }
+    if let Some(push) = repo.get("pushOnCreate").and_then(Value::as_bool) {
+      repository.insert("pushOnCreate".to_string(), Value::Bool(push));
+    }
+  }
+  if !repository.is_empty() {
+    out.insert("repository".to_string(), Value::Object(repository));
+  }
+
+  let auto_install = value
+    .get("projectPrep")
+    .and_then(|v| v.get("autoInstallOnOpenInEditor"))
+    .and_then(Value::as_bool);
+  if let Some(auto_install) = auto_install {
+    out.insert(
+      "projectPrep".to_string(),
+      json!({ "autoInstallOnOpenInEditor": auto_install }),
+    );
+  }
+
+  Value::Object(out)
+}
+
+/// Global settings with the project's overrides (if any) layered on top.
+pub fn load_settings_for_project(app: &tauri::AppHandle, project_id: Option<&str>) -> Value {
+  let mut settings = load_settings(app);
+  let overrides = project_id
+    .filter(|id| !id.trim().is_empty())
+    .and_then(|id| {
+      let state = app.try_state::<db::DbState>()?;
+      db::project_settings_overrides(&state, id)
+    });
+  if let Some(overrides) = overrides {
+    let mut invalid = Vec::new();
+    let overrides = normalize_project_overrides(&overrides);
+    merge_over_defaults(&mut settings, &overrides, "", &mut invalid);
+    warn_invalid_once(invalid);
+  }
+  settings
+}
+
 pub fn update_settings(app: &tauri::AppHandle, patch: Value) -> Value {
   let mut current = load_settings(app);
   merge_value(&mut current, &patch);
-  let normalized = normalize_settings(current, app);
+  save_settings(app, current)
+}
+
+/// Stores `theme` as the terminal's custom theme as-is; unlike `update_settings`, colors
+/// missing from the new theme don't survive from the previous one.
+pub fn replace_terminal_theme(app: &tauri::AppHandle, theme: Value) -> Value {
+  let mut current = load_settings(app);
+  if let Some(obj) = current.as_object_mut() {
+    let terminal = obj.entry("terminal").or_insert_with(|| json!({}));
+    if !terminal.is_object() {
+      *terminal = json!({});
+    }
+    terminal["customTheme"] = theme;
+  }
+  save_settings(app, current)
+}
+
+fn save_settings(app: &tauri::AppHandle, settings: Value) -> Value {
+  let normalized = normalize_settings(settings, app);
   let path = settings_path(app);
   let _ = storage::write_json(&path, &normalized);
   normalized
@@ -16,11 +16,6 @@ pub fn config_file(app: &tauri::AppHandle, name: &str) -> PathBuf {
   config_dir(app).join(name)
 }
 
-pub fn read_json(path: &Path) -> Option<Value> {
-  let raw = fs::read_to_string(path).ok()?;
-  serde_json::from_str(&raw).ok()
-}
-
 pub fn write_json(path: &Path, value: &Value) -> Result<(), String> {
   if let Some(parent) = path.parent() {
     if !parent.exists() {
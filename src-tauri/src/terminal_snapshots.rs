@@ -5,9 +5,14 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use tauri::Manager;
 
+use crate::settings;
+
 const MAX_SNAPSHOT_BYTES: usize = 8 * 1024 * 1024;
 const MAX_TOTAL_BYTES: usize = 64 * 1024 * 1024;
+const DEFAULT_SNAPSHOT_DATA_BYTES: usize = 2 * 1024 * 1024;
 pub const TERMINAL_SNAPSHOT_VERSION: u32 = 1;
+// Version 1 records stored env override values in plaintext.
+pub const PTY_SESSION_VERSION: u32 = 2;
 
 #[derive(Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -21,6 +26,22 @@ pub struct TerminalSnapshotPayload {
   pub stats: Option<Value>,
 }
 
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PtySessionRecord {
+  pub version: u32,
+  pub saved_at: String,
+  pub cwd: Option<String>,
+  pub shell: Option<String>,
+  pub command: Option<String>,
+  // Only the names of the launch env overrides; their values often hold tokens.
+  #[serde(default)]
+  pub env_keys: Vec<String>,
+  pub cols: u16,
+  pub rows: u16,
+  pub scrollback: String,
+}
+
 #[derive(Clone)]
 struct StoredSnapshot {
   payload: TerminalSnapshotPayload,
@@ -43,6 +64,41 @@ fn base_dir(app: &tauri::AppHandle) -> PathBuf {
     .join("terminal-snapshots")
 }
 
+/// Cap on a snapshot's `data`, applied on every save. Configured by
+/// `terminal.snapshotMaxBytes` in settings (bounded by MAX_SNAPSHOT_BYTES).
+pub fn snapshot_data_cap(app: &tauri::AppHandle) -> usize {
+  snapshot_cap_from_settings(&settings::load_settings(app))
+}
+
+fn snapshot_cap_from_settings(settings: &Value) -> usize {
+  settings
+    .get("terminal")
+    .and_then(|terminal| terminal.get("snapshotMaxBytes"))
+    .and_then(Value::as_u64)
+    .map(|v| v as usize)
+    .filter(|v| *v > 0)
+    .unwrap_or(DEFAULT_SNAPSHOT_DATA_BYTES)
+    .min(MAX_SNAPSHOT_BYTES)
+}
+
+/// Keeps the last `max_bytes` of `data`, starting at a line boundary when one is available.
+/// Returns `None` if nothing needs trimming.
+fn trim_to_tail(data: &str, max_bytes: usize) -> Option<String> {
+  if data.len() <= max_bytes {
+    return None;
+  }
+  let mut start = data.len() - max_bytes;
+  while !data.is_char_boundary(start) {
+    start += 1;
+  }
+  let tail = &data[start..];
+  let tail = match tail.find('\n') {
+    Some(idx) if idx + 1 < tail.len() => &tail[idx + 1..],
+    _ => tail,
+  };
+  Some(tail.to_string())
+}
+
 fn sanitize_id(id: &str) -> String {
   id.chars()
     .map(|ch| {
@@ -59,6 +115,12 @@ fn snapshot_path(app: &tauri::AppHandle, id: &str) -> PathBuf {
   base_dir(app).join(format!("{}.json", sanitize_id(id)))
 }
 
+fn session_path(app: &tauri::AppHandle, id: &str) -> PathBuf {
+  base_dir(app)
+    .join("sessions")
+    .join(format!("{}.json", sanitize_id(id)))
+}
+
 fn ensure_dir(path: &Path) -> Result<(), String> {
   if let Some(parent) = path.parent() {
     if !parent.exists() {
@@ -129,11 +191,14 @@ pub fn get_snapshot(
 pub fn save_snapshot(
   app: &tauri::AppHandle,
   id: &str,
-  payload: TerminalSnapshotPayload,
+  mut payload: TerminalSnapshotPayload,
 ) -> Result<(), String> {
   if payload.version != TERMINAL_SNAPSHOT_VERSION {
     return Err("Unsupported snapshot version".to_string());
   }
+  if let Some(trimmed) = trim_to_tail(&payload.data, snapshot_data_cap(app)) {
+    payload.data = trimmed;
+  }
 
   let json = serde_json::to_string(&payload).map_err(|err| err.to_string())?;
   let bytes = json.len();
@@ -148,6 +213,24 @@ pub fn save_snapshot(
   Ok(())
 }
 
+/// Truncates a stored snapshot's data to its last `max_bytes`. Returns the resulting data
+/// size, or `None` if there is no snapshot.
+pub fn trim_snapshot(app: &tauri::AppHandle, id: &str, max_bytes: usize) -> Result<Option<usize>, String> {
+  let path = snapshot_path(app, id);
+  let Some(stored) = read_snapshot_file(&path) else {
+    return Ok(None);
+  };
+  let mut payload = stored.payload;
+  if let Some(trimmed) = trim_to_tail(&payload.data, max_bytes) {
+    payload.data = trimmed;
+    let json = serde_json::to_string(&payload).map_err(|err| err.to_string())?;
+    fs::write(&path, json).map_err(|err| err.to_string())?;
+    // Re-run the total-size accounting against the trimmed file, as a save would.
+    prune_if_needed(app, id)?;
+  }
+  Ok(Some(payload.data.len()))
+}
+
 pub fn delete_snapshot(app: &tauri::AppHandle, id: &str) -> Result<(), String> {
   let path = snapshot_path(app, id);
   match fs::remove_file(&path) {
@@ -191,3 +274,71 @@ fn prune_if_needed(app: &tauri::AppHandle, recent_id: &str) -> Result<(), String
 
   Ok(())
 }
+
+pub fn get_session(app: &tauri::AppHandle, id: &str) -> Result<Option<PtySessionRecord>, String> {
+  let raw = match fs::read_to_string(session_path(app, id)) {
+    Ok(raw) => raw,
+    Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+    Err(err) => return Err(err.to_string()),
+  };
+  let record: PtySessionRecord = serde_json::from_str(&raw).map_err(|err| err.to_string())?;
+  if record.version != PTY_SESSION_VERSION {
+    let _ = fs::remove_file(session_path(app, id));
+    return Ok(None);
+  }
+  Ok(Some(record))
+}
+
+pub fn save_session(app: &tauri::AppHandle, id: &str, record: &PtySessionRecord) -> Result<(), String> {
+  let json = serde_json::to_string(record).map_err(|err| err.to_string())?;
+  if json.len() > MAX_SNAPSHOT_BYTES {
+    return Err("Session size exceeds per-task limit".to_string());
+  }
+  let path = session_path(app, id);
+  ensure_dir(&path)?;
+  fs::write(&path, json).map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  #[test]
+  fn trim_to_tail_keeps_whole_lines_and_utf8() {
+    let cases: &[(&str, usize, Option<&str>)] = &[
+      ("short", 10, None),
+      ("exact", 5, None),
+      ("aaa\nbbb\nccc", 6, Some("ccc")),
+      ("abcdef", 3, Some("def")),
+      // A newline that ends the tail isn't used as a cut point.
+      ("abc\ndef\n", 2, Some("f\n")),
+      // Cuts inside a multi-byte character move forward to the next boundary.
+      ("\u{e9}\u{e9}\u{e9}", 3, Some("\u{e9}")),
+      ("\u{65e5}\u{672c}\u{8a9e}", 4, Some("\u{8a9e}")),
+      ("\u{1f600}\n\u{1f600}x", 6, Some("\u{1f600}x")),
+      ("anything", 0, Some("")),
+    ];
+    for (data, max_bytes, expected) in cases {
+      let trimmed = trim_to_tail(data, *max_bytes);
+      assert_eq!(trimmed.as_deref(), *expected, "{:?} at {}", data, max_bytes);
+      if let Some(trimmed) = trimmed {
+        assert!(trimmed.len() <= *max_bytes);
+        assert!(data.ends_with(&trimmed));
+      }
+    }
+  }
+
+  #[test]
+  fn snapshot_cap_comes_from_settings() {
+    let cap = |settings: Value| snapshot_cap_from_settings(&settings);
+    assert_eq!(cap(json!({})), DEFAULT_SNAPSHOT_DATA_BYTES);
+    assert_eq!(cap(json!({ "terminal": { "snapshotMaxBytes": 4096 } })), 4096);
+    assert_eq!(cap(json!({ "terminal": { "snapshotMaxBytes": 0 } })), DEFAULT_SNAPSHOT_DATA_BYTES);
+    assert_eq!(cap(json!({ "terminal": { "snapshotMaxBytes": "big" } })), DEFAULT_SNAPSHOT_DATA_BYTES);
+    assert_eq!(
+      cap(json!({ "terminal": { "snapshotMaxBytes": MAX_SNAPSHOT_BYTES * 4 } })),
+      MAX_SNAPSHOT_BYTES
+    );
+  }
+}
@@ -0,0 +1,36 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+pub(crate) fn git(cwd: &Path, args: &[&str]) -> String {
+  let output = Command::new("git").args(args).current_dir(cwd).output().unwrap();
+  assert!(
+    output.status.success(),
+    "git {:?}: {}",
+    args,
+    String::from_utf8_lossy(&output.stderr)
+  );
+  String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+// The repo sits one level down in its own temp dir, so tests can put worktrees next to it;
+// remove_temp_repo cleans up both.
+pub(crate) fn temp_repo(label: &str) -> PathBuf {
+  let root = std::env::temp_dir().join(format!(
+    "emdash-{}-{}-{}",
+    label,
+    std::process::id(),
+    uuid::Uuid::new_v4()
+  ));
+  let repo = root.join("repo");
+  fs::create_dir_all(&repo).unwrap();
+  git(&repo, &["init", "-q"]);
+  git(&repo, &["config", "user.name", "t"]);
+  git(&repo, &["config", "user.email", "t@t"]);
+  git(&repo, &["config", "core.autocrlf", "false"]);
+  repo
+}
+
+pub(crate) fn remove_temp_repo(repo: &Path) {
+  let _ = fs::remove_dir_all(repo.parent().unwrap_or(repo));
+}
@@ -1,7 +1,9 @@
 use crate::db::{self, DbState, ProjectSettingsRow};
+use crate::git;
 use crate::runtime::run_blocking;
 use crate::settings;
 use chrono::Utc;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use sha1::{Digest, Sha1};
@@ -9,8 +11,9 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Output};
+use std::sync::atomic::AtomicBool;
 use std::sync::{Arc, Mutex};
-use tauri::{AppHandle, Manager, State};
+use tauri::{AppHandle, Emitter, Manager, State};
 
 #[derive(Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -29,14 +32,24 @@ pub struct WorktreeInfo {
 #[derive(Default, Clone)]
 pub struct WorktreeState {
   inner: Arc<Mutex<HashMap<String, WorktreeInfo>>>,
+  create_locks: Arc<Mutex<HashMap<String, Arc<Mutex<()>>>>>,
 }
 
 impl WorktreeState {
   pub fn new() -> Self {
     Self {
       inner: Arc::new(Mutex::new(HashMap::new())),
+      create_locks: Arc::new(Mutex::new(HashMap::new())),
     }
   }
+
+  fn create_lock(&self, project_path: &str) -> Arc<Mutex<()>> {
+    let mut guard = self.create_locks.lock().unwrap_or_else(|p| p.into_inner());
+    guard
+      .entry(project_path.to_string())
+      .or_insert_with(|| Arc::new(Mutex::new(())))
+      .clone()
+  }
 }
 
 #[derive(Debug, Clone)]
@@ -53,6 +66,12 @@ pub struct WorktreeCreateArgs {
   task_name: String,
   project_id: String,
   auto_approve: Option<bool>,
+  request_id: Option<String>,
+  task_id: Option<String>,
+  /// Base ref for a task that isn't saved yet; takes precedence over the task's stored override.
+  base_ref: Option<String>,
+  /// Overrides `repository.pushOnCreate` for this worktree only.
+  push: Option<bool>,
 }
 
 #[derive(Deserialize)]
@@ -74,6 +93,7 @@ pub struct WorktreeRemoveArgs {
 #[serde(rename_all = "camelCase")]
 pub struct WorktreeStatusArgs {
   worktree_path: String,
+  base_ref: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -81,6 +101,7 @@ pub struct WorktreeStatusArgs {
 pub struct WorktreeMergeArgs {
   project_path: String,
   worktree_id: String,
+  strategy: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -89,6 +110,19 @@ pub struct WorktreeGetArgs {
   worktree_id: String,
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorktreeBranchDiffArgs {
+  worktree_id: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorktreeRepairArgs {
+  worktree_id: String,
+  branch_name: Option<String>,
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WorktreeCreateFromBranchArgs {
@@ -104,6 +138,14 @@ pub struct WorktreeCreateFromBranchArgs {
 pub struct FetchBaseRefArgs {
   project_id: String,
   project_path: String,
+  task_id: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskSetBaseRefArgs {
+  task_id: String,
+  base_ref: Option<String>,
 }
 
 fn run_command(cmd: &str, args: &[&str], cwd: Option<&Path>) -> Result<Output, String> {
@@ -239,8 +281,12 @@ fn extract_template_prefix(template: &str) -> Option<String> {
   }
 }
 
-fn get_default_branch(project_path: &Path) -> String {
-  if let Ok(output) = run_command("git", &["remote", "show", "origin"], Some(project_path)) {
+fn project_remote(db_state: &DbState, project_path: &str) -> String {
+  git::normalize_remote_name(db::project_git_remote_for_path(db_state, project_path).as_deref())
+}
+
+fn get_default_branch(project_path: &Path, remote: &str) -> String {
+  if let Ok(output) = run_command("git", &["remote", "show", remote], Some(project_path)) {
     let stdout = String::from_utf8_lossy(&output.stdout);
     for line in stdout.lines() {
       if let Some(idx) = line.find("HEAD branch:") {
@@ -290,14 +336,10 @@ fn parse_base_ref(raw: &str, project_path: Option<&Path>) -> Option<BaseRefInfo>
 fn resolve_project_base_ref(
   project_path: &Path,
   row: &ProjectSettingsRow,
+  task_base_ref: Option<&str>,
 ) -> Result<BaseRefInfo, String> {
-  let default_remote = row
-    .git_remote
-    .as_deref()
-    .map(|r| r.trim())
-    .filter(|r| !r.is_empty())
-    .unwrap_or("origin");
-  if let Some(base_ref) = row.base_ref.as_deref() {
+  let default_remote = git::normalize_remote_name(row.git_remote.as_deref());
+  if let Some(base_ref) = task_base_ref.or(row.base_ref.as_deref()) {
     if let Some(info) = parse_base_ref(base_ref, Some(project_path)) {
       return Ok(info);
     }
@@ -330,7 +372,7 @@ fn resolve_project_base_ref(
     .map(|b| b.trim())
     .filter(|b| !b.is_empty() && !b.contains(' '))
     .map(|b| b.to_string())
-    .unwrap_or_else(|| get_default_branch(project_path));
+    .unwrap_or_else(|| get_default_branch(project_path, &default_remote));
 
   Ok(BaseRefInfo {
     remote: default_remote.to_string(),
@@ -348,70 +390,178 @@ fn is_missing_remote_ref_error(message: &str) -> bool {
     || msg.contains("no such ref was fetched")
 }
 
+// Auth and network failures would fail the default-branch fallback the same way.
+fn remote_unreachable_message(remote: &str, err: &str) -> Option<String> {
+  match git::classify_git_error(err) {
+    git::GitErrorCode::AuthFailed => Some(git_auth_failure_message(remote, err)),
+    git::GitErrorCode::Network => Some(format!(
+      "Could not reach remote '{}'. Check your network connection and the remote URL. Git said: {}",
+      remote,
+      err.trim()
+    )),
+    _ => None,
+  }
+}
+
+fn git_auth_failure_message(remote: &str, err: &str) -> String {
+  format!(
+    "Could not authenticate with remote '{}'. If this repo uses an SSH host alias or a non-default key, \
+     make sure it is configured in ~/.ssh/config and loaded in your agent (try `git fetch {}` in a terminal). \
+     For HTTPS remotes, check your credential helper. Git said: {}",
+    remote,
+    remote,
+    err.trim()
+  )
+}
+
+fn fetch_error_response(err: String) -> Value {
+  if err == git::GIT_CANCELLED {
+    return json!({ "success": false, "cancelled": true, "error": err });
+  }
+  match git::classify_git_error(&err) {
+    git::GitErrorCode::Unknown => json!({ "success": false, "error": err }),
+    code => json!({ "success": false, "code": code.as_str(), "error": err }),
+  }
+}
+
 fn fetch_base_ref_with_fallback(
   project_path: &Path,
   project_id: &str,
   base_ref: &BaseRefInfo,
+  default_remote: &str,
   db_state: &DbState,
+  persist_fallback: bool,
+  cancel: Option<&AtomicBool>,
 ) -> Result<BaseRefInfo, String> {
-  let fetch_res = run_command(
-    "git",
+  let fetch_res = git::run_git_cancellable(
+    project_path,
     &["fetch", &base_ref.remote, &base_ref.branch],
-    Some(project_path),
+    cancel,
   );
   if fetch_res.is_ok() {
     return Ok(base_ref.clone());
   }
 
   let err = fetch_res.err().unwrap_or_else(|| "Failed to fetch base ref".to_string());
+  if err == git::GIT_CANCELLED {
+    return Err(err);
+  }
+  if let Some(message) = remote_unreachable_message(&base_ref.remote, &err) {
+    return Err(message);
+  }
   if !is_missing_remote_ref_error(&err) {
     return Err(format!("Failed to fetch {}: {}", base_ref.full_ref, err));
   }
 
-  let fallback_branch = get_default_branch(project_path);
+  let fallback_branch = get_default_branch(project_path, default_remote);
   let fallback = BaseRefInfo {
-    remote: "origin".to_string(),
+    remote: default_remote.to_string(),
     branch: fallback_branch.clone(),
-    full_ref: format!("origin/{}", fallback_branch),
+    full_ref: format!("{}/{}", default_remote, fallback_branch),
   };
 
   if fallback.full_ref == base_ref.full_ref {
     return Err(format!("Failed to fetch {}: {}", base_ref.full_ref, err));
   }
 
-  run_command(
-    "git",
+  git::run_git_cancellable(
+    project_path,
     &["fetch", &fallback.remote, &fallback.branch],
-    Some(project_path),
+    cancel,
   )
   .map_err(|err| {
+    if err == git::GIT_CANCELLED {
+      return err;
+    }
+    if let Some(message) = remote_unreachable_message(&fallback.remote, &err) {
+      return message;
+    }
     format!(
       "Failed to fetch base branch. Tried {} and {}. {} Please verify the branch exists on the remote.",
       base_ref.full_ref, fallback.full_ref, err
     )
   })?;
 
-  let _ = db::update_project_base_ref(db_state, project_id, &fallback.full_ref);
+  // A task-level override shouldn't rewrite the project's base ref.
+  if persist_fallback {
+    let _ = db::update_project_base_ref(db_state, project_id, &fallback.full_ref);
+  }
   Ok(fallback)
 }
 
-fn ensure_codex_log_ignored(worktree_path: &Path) {
-  let git_meta = worktree_path.join(".git");
-  let mut git_dir = git_meta.clone();
-  if git_meta.exists() && git_meta.is_file() {
-    if let Ok(content) = fs::read_to_string(&git_meta) {
-      for line in content.lines() {
-        if let Some(rest) = line.strip_prefix("gitdir:") {
-          let dir = rest.trim();
-          if !dir.is_empty() {
-            git_dir = worktree_path.join(dir);
-          }
-        }
-      }
+fn list_conflicted_files(repo_path: &Path) -> Vec<String> {
+  run_command(
+    "git",
+    &["diff", "--name-only", "--diff-filter=U"],
+    Some(repo_path),
+  )
+  .map(|output| {
+    String::from_utf8_lossy(&output.stdout)
+      .lines()
+      .map(|line| line.trim().to_string())
+      .filter(|line| !line.is_empty())
+      .collect()
+  })
+  .unwrap_or_default()
+}
+
+fn resolve_tracking_ref(worktree_path: &Path, base_ref: Option<&str>) -> Option<String> {
+  if let Ok(output) = run_command(
+    "git",
+    &["rev-parse", "--abbrev-ref", "--symbolic-full-name", "@{u}"],
+    Some(worktree_path),
+  ) {
+    let upstream = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if !upstream.is_empty() {
+      return Some(upstream);
     }
   }
+  let base = base_ref.map(|b| b.trim()).filter(|b| !b.is_empty())?;
+  run_command("git", &["rev-parse", "--verify", "--quiet", base], Some(worktree_path))
+    .ok()
+    .map(|_| base.to_string())
+}
+
+fn ahead_behind(worktree_path: &Path, tracking_ref: &str) -> Option<(i64, i64)> {
+  let output = run_command(
+    "git",
+    &[
+      "rev-list",
+      "--left-right",
+      "--count",
+      &format!("{}...HEAD", tracking_ref),
+    ],
+    Some(worktree_path),
+  )
+  .ok()?;
+  let stdout = String::from_utf8_lossy(&output.stdout);
+  let mut parts = stdout.split_whitespace();
+  let behind = parts.next()?.parse::<i64>().ok()?;
+  let ahead = parts.next()?.parse::<i64>().ok()?;
+  Some((ahead, behind))
+}
 
-  let exclude_path = git_dir.join("info").join("exclude");
+fn ensure_codex_log_ignored(worktree_path: &Path) {
+  ensure_git_excluded(worktree_path, "codex-stream.log");
+}
+
+/// Adds `pattern` to the worktree's `info/exclude` so generated files stay out of git status.
+// Linked worktrees only honour the common dir's `info/exclude`, so let git resolve the path
+// rather than following the worktree's `gitdir:` pointer.
+pub(crate) fn ensure_git_excluded(worktree_path: &Path, pattern: &str) {
+  let Ok(output) = run_command(
+    "git",
+    &["rev-parse", "--git-path", "info/exclude"],
+    Some(worktree_path),
+  ) else {
+    return;
+  };
+  let resolved = String::from_utf8_lossy(&output.stdout).trim().to_string();
+  if !output.status.success() || resolved.is_empty() {
+    return;
+  }
+
+  let exclude_path = worktree_path.join(resolved);
   if let Some(parent) = exclude_path.parent() {
     let _ = fs::create_dir_all(parent);
   }
@@ -419,12 +569,13 @@ fn ensure_codex_log_ignored(worktree_path: &Path) {
   if let Ok(text) = fs::read_to_string(&exclude_path) {
     current = text;
   }
-  if !current.contains("codex-stream.log") {
+  if !current.lines().any(|line| line.trim() == pattern) {
     let mut next = current;
     if !next.ends_with('\n') && !next.is_empty() {
       next.push('\n');
     }
-    next.push_str("codex-stream.log\n");
+    next.push_str(pattern);
+    next.push('\n');
     let _ = fs::write(&exclude_path, next);
   }
 }
@@ -452,8 +603,8 @@ fn ensure_claude_auto_approve(worktree_path: &Path) {
   );
 }
 
-fn should_push_on_create(app: &AppHandle) -> bool {
-  let settings = settings::load_settings(app);
+fn should_push_on_create(app: &AppHandle, project_id: Option<&str>) -> bool {
+  let settings = settings::load_settings_for_project(app, project_id);
   settings
     .get("repository")
     .and_then(|v| v.get("pushOnCreate"))
@@ -461,8 +612,8 @@ fn should_push_on_create(app: &AppHandle) -> bool {
     .unwrap_or(true)
 }
 
-fn branch_template(app: &AppHandle) -> String {
-  settings::load_settings(app)
+fn branch_template(app: &AppHandle, project_id: Option<&str>) -> String {
+  settings::load_settings_for_project(app, project_id)
     .get("repository")
     .and_then(|v| v.get("branchTemplate"))
     .and_then(|v| v.as_str())
@@ -478,7 +629,9 @@ pub fn list_worktrees_internal(
   let output = run_command("git", &["worktree", "list"], Some(Path::new(project_path)))?;
   let stdout = String::from_utf8_lossy(&output.stdout);
   let mut managed_prefixes = vec!["agent".to_string(), "pr".to_string(), "orch".to_string()];
-  if let Some(prefix) = extract_template_prefix(&branch_template(app)) {
+  let db_state: State<DbState> = app.state();
+  let project_id = db::project_id_for_path(&db_state, project_path);
+  if let Some(prefix) = extract_template_prefix(&branch_template(app, project_id.as_deref())) {
     if !managed_prefixes.contains(&prefix) {
       managed_prefixes.push(prefix);
     }
@@ -543,6 +696,88 @@ pub fn list_worktrees_internal(
   Ok(worktrees)
 }
 
+fn local_branch_exists(project_path: &Path, branch: &str) -> bool {
+  run_command(
+    "git",
+    &["rev-parse", "--verify", "--quiet", &format!("refs/heads/{}", branch)],
+    Some(project_path),
+  )
+  .is_ok()
+}
+
+fn random_suffix() -> String {
+  const ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789";
+  let mut rng = rand::thread_rng();
+  (0..4)
+    .map(|_| ALPHABET[rng.gen_range(0..ALPHABET.len())] as char)
+    .collect()
+}
+
+/// Picks an unused branch and worktree directory and runs `git worktree add` from the ref
+/// `start_ref` resolves, all under the project's create lock so concurrent creates can't
+/// claim the same name between the existence check and the add.
+fn add_worktree_locked<F>(
+  state: &WorktreeState,
+  project_path: &Path,
+  slugged: &str,
+  template: &str,
+  start_ref: F,
+) -> Result<(String, PathBuf), Value>
+where
+  F: FnOnce(&Path) -> Result<String, Value>,
+{
+  let create_lock = state.create_lock(&project_path.to_string_lossy());
+  let _create_guard = create_lock.lock().unwrap_or_else(|p| p.into_inner());
+
+  let timestamp = Utc::now().timestamp_millis().to_string();
+  let mut branch_name = render_branch_template(template, slugged, &timestamp);
+  let worktrees_dir = project_path.join("..").join("worktrees");
+  let mut worktree_path = worktrees_dir.join(format!("{}-{}", slugged, timestamp));
+
+  if worktree_path.exists() || local_branch_exists(project_path, &branch_name) {
+    let suffix = random_suffix();
+    worktree_path = worktrees_dir.join(format!("{}-{}-{}", slugged, timestamp, suffix));
+    branch_name = format!("{}-{}", branch_name, suffix);
+  }
+
+  if worktree_path.exists() {
+    return Err(json!({
+      "success": false,
+      "error": format!("Worktree directory already exists: {}", worktree_path.display())
+    }));
+  }
+
+  if let Some(parent) = worktree_path.parent() {
+    fs::create_dir_all(parent)
+      .map_err(|err| json!({ "success": false, "error": err.to_string() }))?;
+  }
+
+  let full_ref = start_ref(&worktree_path)?;
+  let args_vec = vec![
+    "worktree".to_string(),
+    "add".to_string(),
+    "-b".to_string(),
+    branch_name.clone(),
+    worktree_path.to_string_lossy().to_string(),
+    full_ref,
+  ];
+  run_command_vec("git", &args_vec, Some(project_path))
+    .map_err(|err| json!({ "success": false, "error": err }))?;
+
+  Ok((branch_name, worktree_path))
+}
+
+fn emit_create_progress(app: &AppHandle, project_id: &str, worktree_path: &Path, stage: &str) {
+  let _ = app.emit(
+    "worktree:create:progress",
+    json!({
+      "projectId": project_id,
+      "worktreePath": worktree_path.to_string_lossy(),
+      "stage": stage,
+    }),
+  );
+}
+
 #[tauri::command]
 pub async fn worktree_create(app: AppHandle, args: WorktreeCreateArgs) -> Value {
   run_blocking(
@@ -559,58 +794,60 @@ pub async fn worktree_create(app: AppHandle, args: WorktreeCreateArgs) -> Value
       }
 
       let slugged = slugify(task_name);
-      let timestamp = Utc::now().timestamp_millis().to_string();
-      let template = branch_template(&app);
-      let branch_name = render_branch_template(&template, &slugged, &timestamp);
-
-      let worktree_path = Path::new(project_path)
-        .join("..")
-        .join("worktrees")
-        .join(format!("{}-{}", slugged, timestamp));
-
-      if worktree_path.exists() {
-        return json!({
-          "success": false,
-          "error": format!("Worktree directory already exists: {}", worktree_path.display())
-        });
-      }
-
-      if let Some(parent) = worktree_path.parent() {
-        if let Err(err) = fs::create_dir_all(parent) {
-          return json!({ "success": false, "error": err.to_string() });
-        }
-      }
-
+      let template = branch_template(&app, Some(project_id));
       let project_path_buf = PathBuf::from(project_path);
       let row = match db::project_settings_row(&db_state, project_id) {
         Ok(row) => row,
         Err(err) => return json!({ "success": false, "error": err }),
       };
+      let remote = git::normalize_remote_name(row.git_remote.as_deref());
+      let task_base_ref = args
+        .base_ref
+        .as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+        .or_else(|| {
+          args
+            .task_id
+            .as_deref()
+            .and_then(|id| db::task_base_ref(&db_state, id.trim()))
+        });
 
-      let base_ref = match resolve_project_base_ref(&project_path_buf, &row) {
-        Ok(info) => info,
-        Err(err) => return json!({ "success": false, "error": err }),
+      let created = add_worktree_locked(
+        &state,
+        &project_path_buf,
+        &slugged,
+        &template,
+        |worktree_path| {
+          emit_create_progress(&app, project_id, worktree_path, "resolving");
+          let base_ref =
+            resolve_project_base_ref(&project_path_buf, &row, task_base_ref.as_deref())
+              .map_err(|err| json!({ "success": false, "error": err }))?;
+
+          emit_create_progress(&app, project_id, worktree_path, "fetching");
+          let fetched = git::with_cancel_flag(&app, args.request_id.as_deref(), |cancel| {
+            fetch_base_ref_with_fallback(
+              &project_path_buf,
+              project_id,
+              &base_ref,
+              &remote,
+              &db_state,
+              task_base_ref.is_none(),
+              cancel.map(|flag| flag.as_ref()),
+            )
+          })
+          .map_err(fetch_error_response)?;
+
+          emit_create_progress(&app, project_id, worktree_path, "creating");
+          Ok(fetched.full_ref)
+        },
+      );
+      let (branch_name, worktree_path) = match created {
+        Ok(created) => created,
+        Err(response) => return response,
       };
 
-      let fetched =
-        match fetch_base_ref_with_fallback(&project_path_buf, project_id, &base_ref, &db_state) {
-          Ok(info) => info,
-          Err(err) => return json!({ "success": false, "error": err }),
-        };
-
-      let args_vec = vec![
-        "worktree".to_string(),
-        "add".to_string(),
-        "-b".to_string(),
-        branch_name.clone(),
-        worktree_path.to_string_lossy().to_string(),
-        fetched.full_ref.clone(),
-      ];
-
-      if let Err(err) = run_command_vec("git", &args_vec, Some(&project_path_buf)) {
-        return json!({ "success": false, "error": err });
-      }
-
       if !worktree_path.exists() {
         return json!({
           "success": false,
@@ -640,15 +877,22 @@ pub async fn worktree_create(app: AppHandle, args: WorktreeCreateArgs) -> Value
         .unwrap()
         .insert(worktree_info.id.clone(), worktree_info.clone());
 
-      if should_push_on_create(&app) {
-        let _ = run_command(
+      let push = args
+        .push
+        .unwrap_or_else(|| should_push_on_create(&app, Some(project_id)));
+      let mut pushed = false;
+      if push {
+        emit_create_progress(&app, project_id, &worktree_path, "pushing");
+        pushed = run_command(
           "git",
-          &["push", "--set-upstream", "origin", &branch_name],
+          &["push", "--set-upstream", &remote, &branch_name],
           Some(&worktree_path),
-        );
+        )
+        .is_ok();
       }
 
-      json!({ "success": true, "worktree": worktree_info })
+      emit_create_progress(&app, project_id, &worktree_path, "done");
+      json!({ "success": true, "worktree": worktree_info, "pushed": pushed })
     },
   )
   .await
@@ -673,7 +917,11 @@ pub async fn worktree_list(app: AppHandle, args: WorktreeListArgs) -> Value {
   .await
 }
 
-fn worktree_remove_internal(state: &WorktreeState, args: WorktreeRemoveArgs) -> Value {
+fn worktree_remove_internal(
+  state: &WorktreeState,
+  db_state: &DbState,
+  args: WorktreeRemoveArgs,
+) -> Value {
   let project_path = args.project_path.trim();
   if project_path.is_empty() {
     return json!({ "success": false, "error": "projectPath is required" });
@@ -742,13 +990,14 @@ fn worktree_remove_internal(state: &WorktreeState, args: WorktreeRemoveArgs) ->
       }
     }
 
+    let remote = project_remote(db_state, project_path);
     let mut remote_branch = branch.clone();
-    if let Some(stripped) = branch.strip_prefix("origin/") {
+    if let Some(stripped) = branch.strip_prefix(&format!("{}/", remote)) {
       remote_branch = stripped.to_string();
     }
     let _ = run_command(
       "git",
-      &["push", "origin", "--delete", &remote_branch],
+      &["push", &remote, "--delete", &remote_branch],
       Some(&project_path_buf),
     );
   }
@@ -767,7 +1016,8 @@ pub async fn worktree_remove(app: AppHandle, args: WorktreeRemoveArgs) -> Value
     json!({ "success": false, "error": "Task cancelled" }),
     move || {
       let state: State<WorktreeState> = app.state();
-      worktree_remove_internal(&state, args)
+      let db_state: State<DbState> = app.state();
+      worktree_remove_internal(&state, &db_state, args)
     },
   )
   .await
@@ -818,6 +1068,11 @@ pub async fn worktree_status(args: WorktreeStatusArgs) -> Value {
       let has_changes =
         !staged_files.is_empty() || !unstaged_files.is_empty() || !untracked_files.is_empty();
 
+      let upstream = resolve_tracking_ref(Path::new(worktree_path), args.base_ref.as_deref());
+      let counts = upstream
+        .as_deref()
+        .and_then(|tracking| ahead_behind(Path::new(worktree_path), tracking));
+
       json!({
         "success": true,
         "status": {
@@ -825,6 +1080,9 @@ pub async fn worktree_status(args: WorktreeStatusArgs) -> Value {
           "stagedFiles": staged_files,
           "unstagedFiles": unstaged_files,
           "untrackedFiles": untracked_files,
+          "ahead": counts.map(|(ahead, _)| ahead),
+          "behind": counts.map(|(_, behind)| behind),
+          "upstream": upstream,
         }
       })
     },
@@ -850,17 +1108,82 @@ pub async fn worktree_merge(app: AppHandle, args: WorktreeMergeArgs) -> Value {
       };
       drop(guard);
 
+      let strategy = args
+        .strategy
+        .as_deref()
+        .map(|s| s.trim().to_ascii_lowercase())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| "merge".to_string());
+      if !matches!(strategy.as_str(), "merge" | "squash" | "ff-only") {
+        return json!({ "success": false, "error": format!("Unsupported merge strategy: {}", strategy) });
+      }
+
       let project_path_buf = PathBuf::from(project_path);
-      let default_branch = get_default_branch(&project_path_buf);
+      let db_state: State<DbState> = app.state();
+      let remote = project_remote(&db_state, project_path);
+      let default_branch = get_default_branch(&project_path_buf, &remote);
       if let Err(err) = run_command("git", &["checkout", &default_branch], Some(&project_path_buf)) {
         return json!({ "success": false, "error": err });
       }
-      if let Err(err) = run_command("git", &["merge", &worktree.branch], Some(&project_path_buf)) {
+
+      let merge_args: Vec<&str> = match strategy.as_str() {
+        "squash" => vec!["merge", "--squash", &worktree.branch],
+        "ff-only" => vec!["merge", "--ff-only", &worktree.branch],
+        _ => vec!["merge", &worktree.branch],
+      };
+      if let Err(err) = run_command("git", &merge_args, Some(&project_path_buf)) {
+        if strategy == "ff-only" && err.to_lowercase().contains("not possible to fast-forward") {
+          return json!({
+            "success": false,
+            "code": "FF_NOT_POSSIBLE",
+            "error": format!(
+              "Cannot fast-forward {} to {}; the branches have diverged. Use a merge or squash instead.",
+              default_branch, worktree.branch
+            ),
+            "output": err
+          });
+        }
+        let conflicted = list_conflicted_files(&project_path_buf);
+        if !conflicted.is_empty() {
+          // Leave the worktree in place so the user can resolve it from the task.
+          // A squash merge records no MERGE_HEAD, so `merge --abort` can't undo it.
+          let abort_args: &[&str] = if strategy == "squash" {
+            &["reset", "--merge"]
+          } else {
+            &["merge", "--abort"]
+          };
+          let _ = run_command("git", abort_args, Some(&project_path_buf));
+          return json!({
+            "success": false,
+            "conflicted": true,
+            "files": conflicted,
+            "error": err
+          });
+        }
         return json!({ "success": false, "error": err });
       }
 
+      if strategy == "squash" {
+        let (title, description) =
+          git::generate_squash_message(&project_path_buf, &default_branch, &worktree.branch);
+        let signing = git::commit_signing(&app);
+        if let Err(err) =
+          git::run_git_commit(&project_path_buf, &signing, &["-m", &title, "-m", &description])
+        {
+          if !err.to_lowercase().contains("nothing to commit") {
+            // Don't leave the squashed changes staged on the default branch (e.g. a hook rejected it).
+            let _ = run_command("git", &["reset", "--merge"], Some(&project_path_buf));
+            if git::is_signing_error(&signing, &err) {
+              return git::signing_error_response(err);
+            }
+            return json!({ "success": false, "error": err });
+          }
+        }
+      }
+
       let _ = worktree_remove_internal(
         &state,
+        &db_state,
         WorktreeRemoveArgs {
           project_path: project_path.to_string(),
           worktree_id: worktree.id.clone(),
@@ -875,6 +1198,141 @@ pub async fn worktree_merge(app: AppHandle, args: WorktreeMergeArgs) -> Value {
   .await
 }
 
+#[tauri::command]
+pub async fn worktree_branch_diff(app: AppHandle, args: WorktreeBranchDiffArgs) -> Value {
+  run_blocking(
+    json!({ "success": false, "error": "Task cancelled" }),
+    move || {
+      let state: State<WorktreeState> = app.state();
+      let worktree = match state.inner.lock().unwrap().get(&args.worktree_id) {
+        Some(wt) => wt.clone(),
+        None => return json!({ "success": false, "error": "Worktree not found" }),
+      };
+
+      let db_state: State<DbState> = app.state();
+      let row = match db::project_settings_row(&db_state, &worktree.project_id) {
+        Ok(row) => row,
+        Err(err) => return json!({ "success": false, "error": err }),
+      };
+      let task_base_ref = db::task_metadata_for_path(&db_state, &worktree.path)
+        .and_then(|meta| meta.get("baseRef").and_then(|v| v.as_str()).map(|v| v.to_string()));
+      let worktree_path = PathBuf::from(&worktree.path);
+      let base_ref = match resolve_project_base_ref(&worktree_path, &row, task_base_ref.as_deref()) {
+        Ok(info) => info,
+        Err(err) => return json!({ "success": false, "error": err }),
+      };
+
+      match git::branch_diff(&worktree_path, &base_ref.full_ref) {
+        Ok(Value::Object(mut payload)) => {
+          payload.insert("success".to_string(), json!(true));
+          Value::Object(payload)
+        }
+        Ok(_) => json!({ "success": false, "error": "Unexpected diff payload" }),
+        Err(err) => json!({ "success": false, "error": err, "baseRef": base_ref.full_ref }),
+      }
+    },
+  )
+  .await
+}
+
+fn head_commit(worktree_path: &Path) -> Result<String, String> {
+  let output = run_command("git", &["rev-parse", "HEAD"], Some(worktree_path))?;
+  Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn current_branch(worktree_path: &Path) -> Option<String> {
+  let output = run_command("git", &["symbolic-ref", "-q", "--short", "HEAD"], Some(worktree_path))
+    .ok()?;
+  let branch = String::from_utf8_lossy(&output.stdout).trim().to_string();
+  if branch.is_empty() {
+    None
+  } else {
+    Some(branch)
+  }
+}
+
+#[tauri::command]
+pub async fn worktree_repair(app: AppHandle, args: WorktreeRepairArgs) -> Value {
+  run_blocking(
+    json!({ "success": false, "error": "Task cancelled" }),
+    move || {
+      let state: State<WorktreeState> = app.state();
+      let worktree = match state.inner.lock().unwrap().get(&args.worktree_id) {
+        Some(wt) => wt.clone(),
+        None => return json!({ "success": false, "error": "Worktree not found" }),
+      };
+      let worktree_path = PathBuf::from(&worktree.path);
+      if !worktree_path.exists() {
+        return json!({ "success": false, "error": "Worktree path does not exist" });
+      }
+
+      if let Some(branch) = current_branch(&worktree_path) {
+        return json!({ "success": true, "repaired": false, "branch": branch });
+      }
+
+      let db_state: State<DbState> = app.state();
+      let requested = args
+        .branch_name
+        .as_deref()
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| name.to_string());
+      let branch = match requested
+        .or_else(|| db::task_branch_for_path(&db_state, &worktree.path))
+        .or_else(|| Some(worktree.branch.clone()).filter(|b| !b.trim().is_empty()))
+      {
+        Some(branch) => branch,
+        None => return json!({ "success": false, "error": "No branch name to repair with" }),
+      };
+      if run_command("git", &["check-ref-format", "--branch", &branch], Some(&worktree_path))
+        .is_err()
+      {
+        return json!({ "success": false, "error": format!("Invalid branch name: {}", branch) });
+      }
+
+      let head = match head_commit(&worktree_path) {
+        Ok(head) => head,
+        Err(err) => return json!({ "success": false, "error": err }),
+      };
+
+      // Only move an existing branch when that can't drop commits from it.
+      if local_branch_exists(&worktree_path, &branch) {
+        let contained = run_command(
+          "git",
+          &["merge-base", "--is-ancestor", &format!("refs/heads/{}", branch), &head],
+          Some(&worktree_path),
+        )
+        .is_ok();
+        if !contained {
+          return json!({
+            "success": false,
+            "error": format!(
+              "Branch {} already exists and has commits not in the detached HEAD",
+              branch
+            ),
+            "code": "BRANCH_DIVERGED",
+            "branch": branch
+          });
+        }
+      }
+
+      if let Err(err) = run_command("git", &["switch", "-C", &branch, &head], Some(&worktree_path)) {
+        return json!({ "success": false, "error": err });
+      }
+
+      if let Some(entry) = state.inner.lock().unwrap().get_mut(&args.worktree_id) {
+        entry.branch = branch.clone();
+      }
+      if branch != worktree.branch {
+        let _ = db::update_task_branch_for_path(&db_state, &worktree.path, &branch);
+      }
+
+      json!({ "success": true, "repaired": true, "branch": branch, "head": head })
+    },
+  )
+  .await
+}
+
 #[tauri::command]
 pub async fn worktree_get(app: AppHandle, args: WorktreeGetArgs) -> Value {
   run_blocking(
@@ -993,21 +1451,158 @@ pub async fn project_settings_fetch_base_ref(app: AppHandle, args: FetchBaseRefA
         Err(err) => return json!({ "success": false, "error": err }),
       };
 
-      let base_ref = match resolve_project_base_ref(Path::new(project_path), &row) {
+      let remote = git::normalize_remote_name(row.git_remote.as_deref());
+      let task_base_ref = args
+        .task_id
+        .as_deref()
+        .and_then(|id| db::task_base_ref(&db_state, id.trim()));
+      let base_ref = match resolve_project_base_ref(Path::new(project_path), &row, task_base_ref.as_deref()) {
         Ok(info) => info,
         Err(err) => return json!({ "success": false, "error": err }),
       };
 
-      match fetch_base_ref_with_fallback(Path::new(project_path), project_id, &base_ref, &db_state) {
+      match fetch_base_ref_with_fallback(
+        Path::new(project_path),
+        project_id,
+        &base_ref,
+        &remote,
+        &db_state,
+        task_base_ref.is_none(),
+        None,
+      ) {
         Ok(info) => json!({
           "success": true,
           "baseRef": info.full_ref,
           "remote": info.remote,
           "branch": info.branch,
         }),
+        Err(err) => fetch_error_response(err),
+      }
+    },
+  )
+  .await
+}
+
+#[tauri::command]
+pub async fn task_set_base_ref(app: AppHandle, args: TaskSetBaseRefArgs) -> Value {
+  run_blocking(
+    json!({ "success": false, "error": "Task cancelled" }),
+    move || {
+      let db_state: State<DbState> = app.state();
+      let task_id = args.task_id.trim();
+      if task_id.is_empty() {
+        return json!({ "success": false, "error": "taskId is required" });
+      }
+
+      let requested = args.base_ref.as_deref().map(str::trim).filter(|v| !v.is_empty());
+      let Some(requested) = requested else {
+        return match db::update_task_base_ref(&db_state, task_id, None) {
+          Ok(()) => json!({ "success": true, "baseRef": null }),
+          Err(err) => json!({ "success": false, "error": err }),
+        };
+      };
+
+      let (_, project_path, git_remote) = match db::task_project(&db_state, task_id) {
+        Ok(row) => row,
+        Err(err) => return json!({ "success": false, "error": err }),
+      };
+      let project_path_buf = PathBuf::from(&project_path);
+      let info = parse_base_ref(requested, Some(&project_path_buf)).unwrap_or_else(|| {
+        let remote = git::normalize_remote_name(git_remote.as_deref());
+        BaseRefInfo {
+          full_ref: format!("{}/{}", remote, requested),
+          remote,
+          branch: requested.to_string(),
+        }
+      });
+
+      let listed = git::git_list_remote_branches_sync(project_path, info.remote.clone(), None);
+      let Some(branches) = listed.get("branches").and_then(|v| v.as_array()) else {
+        return listed;
+      };
+      let exists = branches
+        .iter()
+        .any(|b| b.get("label").and_then(|v| v.as_str()) == Some(info.full_ref.as_str()));
+      if !exists {
+        return json!({
+          "success": false,
+          "code": "REMOTE_REF_MISSING",
+          "error": format!("{} does not exist on the remote", info.full_ref)
+        });
+      }
+
+      match db::update_task_base_ref(&db_state, task_id, Some(&info.full_ref)) {
+        Ok(()) => json!({ "success": true, "baseRef": info.full_ref }),
         Err(err) => json!({ "success": false, "error": err }),
       }
     },
   )
   .await
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_support::{git, remove_temp_repo, temp_repo};
+  use std::sync::Barrier;
+  use std::thread;
+
+  fn seeded_repo(label: &str) -> PathBuf {
+    let repo = temp_repo(label);
+    git(&repo, &["commit", "-q", "--allow-empty", "-m", "init"]);
+    repo
+  }
+
+  #[test]
+  fn concurrent_creates_get_distinct_branches_and_paths() {
+    let repo = seeded_repo("worktree-create");
+    let state = WorktreeState::new();
+    let barrier = Arc::new(Barrier::new(2));
+
+    let handles: Vec<_> = (0..2)
+      .map(|_| {
+        let state = state.clone();
+        let repo = repo.clone();
+        let barrier = barrier.clone();
+        thread::spawn(move || {
+          barrier.wait();
+          add_worktree_locked(&state, &repo, "same-task", "emdash/{slug}-{timestamp}", |_| {
+            Ok("HEAD".to_string())
+          })
+        })
+      })
+      .collect();
+    let results: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+
+    let created: Vec<(String, PathBuf)> = results
+      .into_iter()
+      .map(|result| result.unwrap_or_else(|err| panic!("create failed: {}", err)))
+      .collect();
+    assert_ne!(created[0].0, created[1].0);
+    assert_ne!(created[0].1, created[1].1);
+    for (branch, path) in &created {
+      assert!(path.exists());
+      assert!(local_branch_exists(&repo, branch));
+    }
+
+    remove_temp_repo(&repo);
+  }
+
+  #[test]
+  fn excludes_apply_to_linked_worktrees() {
+    let repo = seeded_repo("worktree-exclude");
+    let linked = repo.parent().unwrap().join("linked");
+    git(&repo, &["worktree", "add", "-q", "-b", "linked", &linked.to_string_lossy()]);
+    fs::write(linked.join("artifact.yml"), "x").unwrap();
+
+    ensure_git_excluded(&linked, "/artifact.yml");
+    ensure_git_excluded(&linked, "/artifact.yml");
+
+    let status = run_command("git", &["status", "--porcelain"], Some(&linked)).unwrap();
+    assert!(String::from_utf8_lossy(&status.stdout).trim().is_empty());
+    let exclude = fs::read_to_string(repo.join(".git").join("info").join("exclude")).unwrap();
+    assert_eq!(exclude.lines().filter(|line| *line == "/artifact.yml").count(), 1);
+
+    remove_temp_repo(&repo);
+  }
+}
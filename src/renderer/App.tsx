@@ -191,6 +191,7 @@ const AppContent: React.FC = () => {
     };
 
     let off: (() => void) | null = null;
+    let offRuntime: (() => void) | null = null;
     const attachListener = async () => {
       const api: any = (window as any).desktopAPI;
       if (api?.__runtime === 'tauri' && !api?.__runtimeReady) {
@@ -205,6 +206,16 @@ const AppContent: React.FC = () => {
             dbPath: payload?.dbPath,
           });
         }) ?? null;
+      offRuntime =
+        api?.onDbRuntimeError?.((payload: { message?: string; dbPath?: string }) => {
+          if (cancelled) return;
+          setDbInitError({
+            message: payload?.message
+              ? `The database stopped responding: ${payload.message}`
+              : 'The database stopped responding.',
+            dbPath: payload?.dbPath,
+          });
+        }) ?? null;
     };
 
     void checkDbInitError();
@@ -212,6 +223,7 @@ const AppContent: React.FC = () => {
     return () => {
       cancelled = true;
       off?.();
+      offRuntime?.();
     };
   }, []);
 
@@ -1108,7 +1120,8 @@ const AppContent: React.FC = () => {
     linkedLinearIssue: LinearIssueSummary | null = null,
     linkedGithubIssue: GitHubIssueSummary | null = null,
     linkedJiraIssue: JiraIssueSummary | null = null,
-    autoApprove?: boolean
+    autoApprove?: boolean,
+    baseRef?: string
   ) => {
     if (!selectedProject) return;
 
@@ -1209,13 +1222,19 @@ const AppContent: React.FC = () => {
       }
 
       const taskMetadata: TaskMetadata | null =
-        linkedLinearIssue || linkedJiraIssue || linkedGithubIssue || preparedPrompt || autoApprove
+        linkedLinearIssue ||
+        linkedJiraIssue ||
+        linkedGithubIssue ||
+        preparedPrompt ||
+        autoApprove ||
+        baseRef
           ? {
               linearIssue: linkedLinearIssue ?? null,
               jiraIssue: linkedJiraIssue ?? null,
               githubIssue: linkedGithubIssue ?? null,
               initialPrompt: preparedPrompt ?? null,
               autoApprove: autoApprove ?? null,
+              ...(baseRef ? { baseRef } : {}),
             }
           : null;
 
@@ -1245,6 +1264,7 @@ const AppContent: React.FC = () => {
               taskName: variantName,
               projectId: selectedProject.id,
               autoApprove,
+              baseRef,
             });
             if (!worktreeResult?.success || !worktreeResult.worktree) {
               throw new Error(
@@ -1306,6 +1326,7 @@ const AppContent: React.FC = () => {
           taskName,
           projectId: selectedProject.id,
           autoApprove,
+          baseRef,
         });
 
         if (!worktreeResult.success) {
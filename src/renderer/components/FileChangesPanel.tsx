@@ -565,6 +565,13 @@ const FileChangesPanelComponent: React.FC<FileChangesPanelProps> = ({
           'border-amber-200 bg-amber-50 text-amber-700 dark:border-amber-500/40 dark:bg-amber-500/10 dark:text-amber-200',
       };
     }
+    if (normalized === 'submodule') {
+      return {
+        label: 'Submodule',
+        className:
+          'border-sky-200 bg-sky-50 text-sky-700 dark:border-sky-500/40 dark:bg-sky-500/10 dark:text-sky-200',
+      };
+    }
     if (normalized === 'copied') {
       return {
         label: 'Copied',
@@ -1206,6 +1213,7 @@ const FileChangesPanelComponent: React.FC<FileChangesPanelProps> = ({
                               {file.additions && file.additions > 0 ? (
                                 <span className="rounded bg-green-50 px-1.5 py-0.5 text-[11px] font-medium text-emerald-700 dark:bg-green-900/30 dark:text-emerald-300">
                                   +{file.additions}
+                                  {file.additionsApprox ? '+' : ''}
                                 </span>
                               ) : null}
                               {file.deletions && file.deletions > 0 ? (
@@ -1251,6 +1259,12 @@ const FileChangesPanelComponent: React.FC<FileChangesPanelProps> = ({
                   </div>
                 </div>
                 <div className="ml-3 flex items-center gap-2">
+                  {change.status === 'submodule' && change.newSha && (
+                    <span className="rounded bg-muted px-1.5 py-0.5 font-mono text-[11px] text-muted-foreground">
+                      {change.oldSha ? `${change.oldSha} → ` : ''}
+                      {change.newSha}
+                    </span>
+                  )}
                   {change.additions > 0 && (
                     <span className="rounded bg-green-50 px-1.5 py-0.5 text-[11px] font-medium text-emerald-700 dark:bg-green-900/30 dark:text-emerald-300">
                       +{change.additions}
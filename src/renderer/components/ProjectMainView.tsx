@@ -187,7 +187,7 @@ function TaskRow({
     e.stopPropagation();
     try {
       setIsStoppingContainer(true);
-      const res = await (window as any).desktopAPI.stopContainerRun(ws.id);
+      const res = await (window as any).desktopAPI.stopContainerRun(ws.id, ws.path);
       if (!res?.ok) {
         toast({
           title: 'Failed to stop container',
@@ -1,6 +1,6 @@
 import React from 'react';
 import { AnimatePresence, motion, useReducedMotion } from 'motion/react';
-import { ChevronDown } from 'lucide-react';
+import { ChevronDown, Waves } from 'lucide-react';
 import { Button } from '../ui/button';
 import { useToast } from '@/hooks/use-toast';
 import cursorLogo from '../../../assets/images/cursorlogo.png';
@@ -36,7 +36,16 @@ const OpenInMenu: React.FC<OpenInMenuProps> = ({ path, align = 'right' }) => {
   }, [open]);
 
   const callOpen = async (
-    app: 'finder' | 'cursor' | 'vscode' | 'terminal' | 'ghostty' | 'zed' | 'iterm2' | 'warp'
+    app:
+      | 'finder'
+      | 'cursor'
+      | 'vscode'
+      | 'terminal'
+      | 'ghostty'
+      | 'zed'
+      | 'iterm2'
+      | 'warp'
+      | 'wave'
   ) => {
     void import('../../lib/telemetryClient').then(({ captureTelemetry }) => {
       captureTelemetry('toolbar_open_in_selected', { app });
@@ -55,7 +64,9 @@ const OpenInMenu: React.FC<OpenInMenuProps> = ({ path, align = 'right' }) => {
                   ? 'iTerm2'
                   : app === 'warp'
                     ? 'Warp'
-                    : app;
+                    : app === 'wave'
+                      ? 'Wave'
+                      : app;
         toast({
           title: `Open in ${pretty} failed`,
           description:
@@ -68,7 +79,9 @@ const OpenInMenu: React.FC<OpenInMenuProps> = ({ path, align = 'right' }) => {
                   ? 'iTerm2 is not installed or not available on this platform.'
                   : app === 'warp'
                     ? 'Warp is not installed or not available on this platform.'
-                    : 'Application not available.'),
+                    : app === 'wave'
+                      ? 'Wave is not installed or not available on this platform.'
+                      : 'Application not available.'),
           variant: 'destructive',
         });
       }
@@ -84,7 +97,9 @@ const OpenInMenu: React.FC<OpenInMenuProps> = ({ path, align = 'right' }) => {
                 ? 'iTerm2'
                 : app === 'warp'
                   ? 'Warp'
-                  : app;
+                  : app === 'wave'
+                    ? 'Wave'
+                    : app;
       toast({
         title: `Open in ${pretty} failed`,
         description: e?.message || String(e),
@@ -158,6 +173,10 @@ const OpenInMenu: React.FC<OpenInMenuProps> = ({ path, align = 'right' }) => {
               <img src={warpLogo} alt="Warp" className="h-4 w-4 rounded" />
               <span>Warp</span>
             </button>
+            <button className={menuItemBase} role="menuitem" onClick={() => callOpen('wave')}>
+              <Waves className="h-4 w-4 text-muted-foreground" />
+              <span>Wave</span>
+            </button>
             <button className={menuItemBase} role="menuitem" onClick={() => callOpen('iterm2')}>
               <img src={iterm2Logo} alt="iTerm2" className="h-4 w-4 rounded" />
               <span>iTerm2</span>
@@ -4,10 +4,13 @@ import { refreshGitStatus, subscribeGitStatus } from '../lib/gitStatusStore';
 
 export interface FileChange {
   path: string;
-  status: 'added' | 'modified' | 'deleted' | 'renamed';
+  status: 'added' | 'modified' | 'deleted' | 'renamed' | 'submodule';
   additions: number;
+  additionsApprox?: boolean;
   deletions: number;
   isStaged: boolean;
+  oldSha?: string;
+  newSha?: string;
   diff?: string;
 }
 
@@ -40,10 +43,13 @@ export function useFileChanges(taskPath: string) {
         const changes: FileChange[] = result.changes
           .map((change) => ({
             path: change.path,
-            status: change.status as 'added' | 'modified' | 'deleted' | 'renamed',
-            additions: change.additions || 0,
+            status: change.status as FileChange['status'],
+            additions: change.additions ?? 0,
+            additionsApprox: change.additionsApprox,
             deletions: change.deletions || 0,
             isStaged: change.isStaged || false,
+            oldSha: change.oldSha,
+            newSha: change.newSha,
             diff: change.diff,
           }))
           .filter((c) => !c.path.startsWith('.emdash/') && c.path !== 'PLANNING.md');
@@ -5,7 +5,8 @@ import { subscribeGitStatus } from '../lib/gitStatusStore';
 export interface TaskChange {
   path: string;
   status: string;
-  additions: number;
+  additions: number | null;
+  additionsApprox?: boolean;
   deletions: number;
   diff?: string;
 }
@@ -19,6 +19,8 @@ interface StartRunArgs {
   taskPath: string;
   runId?: string;
   mode?: RunnerMode;
+  reuse?: boolean;
+  dryRun?: boolean;
 }
 
 const listeners = new Set<Listener>();
@@ -216,6 +218,8 @@ export async function startContainerRun(args: StartRunArgs) {
   if (taskPath) payload.taskPath = taskPath;
   if (runId) payload.runId = runId;
   if (mode === 'container' || mode === 'host') payload.mode = mode;
+  if (typeof args.reuse === 'boolean') payload.reuse = args.reuse;
+  if (typeof args.dryRun === 'boolean') payload.dryRun = args.dryRun;
 
   if (!taskId || !taskPath) {
     throw new Error('taskId and taskPath are required to start a container run');
@@ -297,11 +301,14 @@ export async function refreshTaskRunState(taskId: string) {
         mode: 'container',
         type: 'ports',
         previewService: res.previewService ?? res.ports[0]?.service ?? 'app',
-        ports: res.ports.map((p: any) => ({
-          ...p,
-          protocol: 'tcp',
-          url: `http://localhost:${p.host}`,
-        })),
+        ports: res.ports.map((p: any) => {
+          const protocol = p.protocol === 'udp' ? 'udp' : 'tcp';
+          return {
+            ...p,
+            protocol,
+            url: protocol === 'tcp' ? `http://localhost:${p.host}` : undefined,
+          };
+        }),
       } as any;
       updateTaskState(portsEvent);
       const lifecycleEvent: RunnerEvent = {
@@ -1,9 +1,12 @@
 type GitStatusChange = {
   path: string;
   status: string;
-  additions: number;
+  additions: number | null;
+  additionsApprox?: boolean;
   deletions: number;
   isStaged: boolean;
+  oldSha?: string;
+  newSha?: string;
   diff?: string;
 };
 
@@ -37,7 +40,7 @@ const computeFingerprint = (status: GitStatusResult | null): string => {
       (c) =>
         `${c.path}|${c.status}|${Number(c.additions || 0)}|${Number(c.deletions || 0)}|${
           c.isStaged ? 1 : 0
-        }`
+        }|${c.newSha || ''}`
     )
     .join(';');
 };
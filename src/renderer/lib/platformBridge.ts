@@ -1,5 +1,10 @@
 type Settings = {
-  repository: { branchTemplate: string; pushOnCreate: boolean };
+  repository: {
+    branchTemplate: string;
+    pushOnCreate: boolean;
+    signCommits?: boolean;
+    signingFormat?: 'gpg' | 'ssh';
+  };
   projectPrep?: { autoInstallOnOpenInEditor: boolean };
   browserPreview?: { enabled: boolean; engine: 'chromium' };
   notifications?: { enabled: boolean; sound: boolean };
@@ -17,6 +22,13 @@ type Settings = {
   projects?: {
     defaultDirectory: string;
   };
+  serviceIcons?: Record<string, string>;
+  providerModels?: Record<string, string>;
+  prGenerationProviders?: string[];
+  terminal?: {
+    customTheme: Record<string, string> | null;
+    snapshotMaxBytes?: number;
+  };
 };
 
 
@@ -50,6 +62,13 @@ const DEFAULT_SETTINGS: Settings = {
   projects: {
     defaultDirectory: '~/emdash-projects',
   },
+  serviceIcons: {},
+  providerModels: {},
+  prGenerationProviders: ['claude', 'codex'],
+  terminal: {
+    customTheme: null,
+    snapshotMaxBytes: 2 * 1024 * 1024,
+  },
 };
 
 const warned = new Set<string>();
@@ -116,41 +135,64 @@ export function installPlatformBridge() {
     getDbInitError: async () => ({ success: true }),
     dbRetryInit: async () => ({ success: false, error: 'not implemented' }),
     dbBackupAndReset: async () => ({ success: false, error: 'not implemented' }),
+    dbBackup: async () => ({ success: false, error: 'not implemented' }),
+    dbVacuum: async () => ({ success: false, error: 'not implemented' }),
+    dbCheckIntegrity: async () => ({ success: false, error: 'not implemented' }),
     onDbInitError: () => noopCleanup,
+    onDbRuntimeError: () => noopCleanup,
     ptyStart: async () => ({ ok: false, error: 'not implemented' }),
     ptyInput: () => {},
+    ptyWriteFile: async () => ({ ok: false, error: 'not implemented' }),
     ptyResize: () => {},
     ptyKill: () => {},
     onPtyData: () => noopCleanup,
     ptyGetSnapshot: async () => ({ ok: false, error: 'not implemented' }),
     ptySaveSnapshot: async () => ({ ok: false, error: 'not implemented' }),
     ptyClearSnapshot: async () => ({ ok: false }),
+    ptyTrimSnapshot: async () => ({ ok: false }),
+    taskSetEnv: async () => ({ ok: false, error: 'not implemented' }),
+    ptySaveSession: async () => ({ ok: false, error: 'not implemented' }),
+    ptyRestoreSession: async () => ({ ok: false, error: 'not implemented' }),
     onPtyExit: () => noopCleanup,
     onPtyStarted: () => noopCleanup,
     terminalGetTheme: async () => ({ ok: false, error: 'not implemented' }),
+    terminalSetTheme: async () => ({ ok: false, error: 'not implemented' }),
     getProjects: async () => [],
     getTasks: async () => [],
     saveProject: async () => ({ success: false, error: 'not implemented' }),
     saveTask: async () => ({ success: false, error: 'not implemented' }),
     deleteProject: async () => ({ success: false, error: 'not implemented' }),
+    cleanupOrphans: async () => ({ success: false, error: 'not implemented' }),
+    getStats: async () => ({ success: false, error: 'not implemented' }),
+    globalSearch: async () => ({ success: false, error: 'not implemented' }),
     deleteTask: async () => ({ success: false, error: 'not implemented' }),
+    getTaskTags: async () => ({ success: false, error: 'not implemented' }),
+    setTaskTags: async () => ({ success: false, error: 'not implemented' }),
     saveConversation: async () => ({ success: false, error: 'not implemented' }),
     getConversations: async () => ({ success: false, error: 'not implemented' }),
     getOrCreateDefaultConversation: async () => ({ success: false, error: 'not implemented' }),
     saveMessage: async () => ({ success: false, error: 'not implemented' }),
     getMessages: async () => ({ success: false, error: 'not implemented' }),
     deleteConversation: async () => ({ success: false, error: 'not implemented' }),
+    renameConversation: async () => ({ success: false, error: 'not implemented' }),
     getProjectSettings: async () => ({ success: false, error: 'not implemented' }),
     updateProjectSettings: async () => ({ success: false, error: 'not implemented' }),
     fetchProjectBaseRef: async () => ({ success: false, error: 'not implemented' }),
+    taskSetBaseRef: async () => ({ success: false, error: 'not implemented' }),
     worktreeCreate: async () => ({ success: false, error: 'not implemented' }),
     worktreeList: async () => ({ success: false, error: 'not implemented' }),
     worktreeRemove: async () => ({ success: false, error: 'not implemented' }),
     worktreeStatus: async () => ({ success: false, error: 'not implemented' }),
     worktreeMerge: async () => ({ success: false, error: 'not implemented' }),
     worktreeGet: async () => ({ success: false, error: 'not implemented' }),
+    worktreeBranchDiff: async () => ({ success: false, error: 'not implemented' }),
+    worktreeRepair: async () => ({ success: false, error: 'not implemented' }),
     worktreeGetAll: async () => ({ success: false, error: 'not implemented' }),
     fsList: async () => ({ success: false, error: 'not implemented' }),
+    fsListTree: async () => ({ success: false, error: 'not implemented' }),
+    fsWatchStart: async () => ({ success: false, error: 'not implemented' }),
+    fsWatchStop: async () => ({ success: false, error: 'not implemented' }),
+    onFsChanged: () => noopCleanup,
     fsRead: async () => ({ success: false, error: 'not implemented' }),
     fsWriteFile: async () => ({ success: false, error: 'not implemented' }),
     fsRemove: async () => ({ success: false, error: 'not implemented' }),
@@ -159,14 +201,18 @@ export function installPlatformBridge() {
     startContainerRun: async () => ({ ok: false, error: 'not implemented' }),
     stopContainerRun: async () => ({ ok: false, error: 'not implemented' }),
     inspectContainerRun: async () => ({ ok: false, error: 'not implemented' }),
+    pruneContainers: async () => ({ ok: false, error: 'not implemented' }),
     resolveServiceIcon: async () => ({ ok: false, error: 'not implemented' }),
+    resolveServiceIcons: async () => ({ ok: false, error: 'not implemented' }),
     onRunEvent: () => noopCleanup,
     removeRunEventListeners: () => {},
     netProbePorts: async () => ({ reachable: [] }),
+    netWaitForPort: async () => ({ ready: false, waitedMs: 0 }),
     planLock: async () => ({ success: false, error: 'not implemented' }),
     planUnlock: async () => ({ success: false, error: 'not implemented' }),
     planApplyLock: async () => ({ success: false, error: 'not implemented' }),
     planReleaseLock: async () => ({ success: false, error: 'not implemented' }),
+    planLockStatus: async () => ({ success: false, error: 'not implemented' }),
     debugAppendLog: async () => ({ success: false, error: 'not implemented' }),
     githubCheckCLIInstalled: async () => false,
     githubInstallCLI: async () => ({ success: false, error: 'not implemented' }),
@@ -175,11 +221,17 @@ export function installPlatformBridge() {
     githubGetStatus: async () => ({ installed: false, authenticated: false, user: null }),
     githubIsAuthenticated: async () => false,
     githubGetUser: async () => null,
-    githubGetRepositories: async () => [],
+    githubGetRepositories: async () => ({
+      success: false,
+      repositories: [],
+      error: 'not implemented',
+    }),
     githubCloneRepository: async () => ({ success: false, error: 'not implemented' }),
     githubListPullRequests: async () => ({ success: false, error: 'not implemented' }),
     githubCreatePullRequestWorktree: async () => ({ success: false, error: 'not implemented' }),
     githubLogout: async () => ({ success: false, error: 'not implemented' }),
+    githubStoreToken: async () => ({ success: false, error: 'not implemented' }),
+    githubGetToken: async () => ({ success: false, error: 'not implemented' }),
     githubGetOwners: async () => ({ success: false, owners: [] }),
     githubValidateRepoName: async () => ({
       success: false,
@@ -191,16 +243,22 @@ export function installPlatformBridge() {
     githubIssuesList: async () => ({ success: false, error: 'not implemented' }),
     githubIssuesSearch: async () => ({ success: false, error: 'not implemented' }),
     githubIssueGet: async () => ({ success: false, error: 'not implemented' }),
+    githubIssueComments: async () => ({ success: false, error: 'not implemented' }),
     linearCheckConnection: async () => ({ connected: false }),
     linearSaveToken: async () => ({ success: false, error: 'not implemented' }),
     linearClearToken: async () => ({ success: false, error: 'not implemented' }),
     linearInitialFetch: async () => ({ success: false, error: 'not implemented' }),
     linearSearchIssues: async () => ({ success: false, error: 'not implemented' }),
+    linearListWorkflowStates: async () => ({ success: false, error: 'not implemented' }),
+    linearUpdateIssueState: async () => ({ success: false, error: 'not implemented' }),
     jiraSaveCredentials: async () => ({ success: false, error: 'not implemented' }),
     jiraClearCredentials: async () => ({ success: false, error: 'not implemented' }),
     jiraCheckConnection: async () => ({ connected: false }),
     jiraInitialFetch: async () => ({ success: false, error: 'not implemented' }),
     jiraSearchIssues: async () => ({ success: false, error: 'not implemented' }),
+    jiraSearchJql: async () => ({ success: false, error: 'not implemented' }),
+    jiraGetAttachments: async () => ({ success: false, error: 'not implemented' }),
+    jiraDownloadAttachment: async () => ({ success: false, error: 'not implemented' }),
     connectToGitHub: async () => ({ success: false, error: 'not implemented' }),
     onGithubAuthDeviceCode: () => noopCleanup,
     onGithubAuthPolling: () => noopCleanup,
@@ -214,6 +272,10 @@ export function installPlatformBridge() {
     hostPreviewStop: async () => ({ ok: true }),
     hostPreviewStopAll: async () => ({ success: true }),
     onHostPreviewEvent: () => noopCleanup,
+    hostPreviewLogs: async () => ({ ok: false, error: 'not implemented' }),
+    onHostPreviewLog: () => noopCleanup,
+    onHostPreviewExited: () => noopCleanup,
+    onHostPreviewRestarting: () => noopCleanup,
     browserShow: async () => ({ ok: false, error: 'not implemented' }),
     browserHide: async () => ({ ok: true }),
     browserSetBounds: async () => ({ ok: true }),
@@ -221,27 +283,52 @@ export function installPlatformBridge() {
     browserGoBack: async () => ({ ok: false, error: 'not implemented' }),
     browserGoForward: async () => ({ ok: false, error: 'not implemented' }),
     browserReload: async () => ({ ok: false, error: 'not implemented' }),
+    browserCurrentUrl: async () => ({ ok: false, url: null }),
     browserOpenDevTools: async () => ({ ok: false, error: 'not implemented' }),
     browserClear: async () => ({ ok: true }),
+    browserCapture: async () => ({ ok: false, error: 'not implemented' }),
+    browserClearStorage: async () => ({ ok: false, error: 'not implemented' }),
     getProviderStatuses: async () => ({ success: false, error: 'not implemented' }),
+    preflightCheck: async () => ({ success: false, error: 'not implemented' }),
     onProviderStatusUpdated: () => noopCleanup,
     getGitInfo: async (projectPath: string) => ({
       isGitRepo: false,
       path: projectPath,
     }),
     getGitStatus: async () => ({ success: false, error: 'not implemented' }),
+    getGitChangeSummary: async () => ({ success: false, error: 'not implemented' }),
     getFileDiff: async () => ({ success: false, error: 'not implemented' }),
+    gitBlame: async () => ({ success: false, error: 'not implemented' }),
+    getGitLog: async () => ({ success: false, error: 'not implemented' }),
+    getCommitDiff: async () => ({ success: false, error: 'not implemented' }),
+    getDiffs: async () => ({ success: false, error: 'not implemented' }),
     stageFile: async () => ({ success: false, error: 'not implemented' }),
+    stageAll: async () => ({ success: false, error: 'not implemented' }),
+    unstageAll: async () => ({ success: false, error: 'not implemented' }),
+    exportPatch: async () => ({ success: false, error: 'not implemented' }),
+    applyPatch: async () => ({ success: false, error: 'not implemented' }),
+    gitBenchmark: async () => ({ success: false, error: 'not implemented' }),
     revertFile: async () => ({ success: false, error: 'not implemented' }),
+    discardAllChanges: async () => ({ success: false, error: 'not implemented' }),
     gitCommitAndPush: async () => ({ success: false, error: 'not implemented' }),
+    gitPull: async () => ({ success: false, error: 'not implemented' }),
     generatePrContent: async () => ({ success: false, error: 'not implemented' }),
+    generateCommitMessage: async () => ({ success: false, error: 'not implemented' }),
+    providerRun: async () => ({ success: false, error: 'not implemented' }),
+    providerCapabilities: async () => ({ success: false, error: 'not implemented' }),
+    providerCancel: async () => ({ success: false, error: 'not implemented' }),
     createPullRequest: async () => ({ success: false, error: 'not implemented' }),
     getPrStatus: async () => ({ success: false, error: 'not implemented' }),
+    githubRerunChecks: async () => ({ success: false, error: 'not implemented' }),
+    githubPrMarkReady: async () => ({ success: false, error: 'not implemented' }),
+    githubPrMarkDraft: async () => ({ success: false, error: 'not implemented' }),
     getPrComments: async () => ({ success: false, error: 'not implemented' }),
     getPrChanges: async () => ({ success: false, error: 'not implemented' }),
     mergePullRequest: async () => ({ success: false, error: 'not implemented' }),
     getBranchStatus: async () => ({ success: false, error: 'not implemented' }),
     listRemoteBranches: async () => ({ success: false, error: 'not implemented' }),
+    deleteRemoteBranch: async () => ({ success: false, error: 'not implemented' }),
+    gitCancel: async () => ({ success: false, error: 'not implemented' }),
   };
 
   const api = new Proxy(base, {
@@ -297,6 +384,11 @@ export function installPlatformBridge() {
         (window as any).desktopAPI.ptyInput = (args: { id: string; data: string }) => {
           invoke('pty_input', args).catch(() => {});
         };
+        (window as any).desktopAPI.ptyWriteFile = (args: {
+          id: string;
+          filePath: string;
+          terminator?: string;
+        }) => invoke('pty_write_file', args);
         (window as any).desktopAPI.ptyResize = (args: { id: string; cols: number; rows: number }) => {
           invoke('pty_resize', args).catch(() => {});
         };
@@ -324,6 +416,19 @@ export function installPlatformBridge() {
         }) => invoke('pty_snapshot_save', args);
         (window as any).desktopAPI.ptyClearSnapshot = (args: { id: string }) =>
           invoke('pty_snapshot_clear', args);
+        (window as any).desktopAPI.ptyTrimSnapshot = (args: { id: string; maxBytes?: number }) =>
+          invoke('pty_snapshot_trim', args);
+        (window as any).desktopAPI.taskSetEnv = (args: {
+          taskId: string;
+          env: Record<string, string>;
+        }) => invoke('task_set_env', args);
+        (window as any).desktopAPI.ptySaveSession = (args: {
+          id: string;
+          cols?: number;
+          rows?: number;
+        }) => invoke('pty_save_session', args);
+        (window as any).desktopAPI.ptyRestoreSession = (args: { id: string; env?: Record<string, string> }) =>
+          invoke('pty_restore_session', args);
         (window as any).desktopAPI.onPtyExit = (
           id: string,
           listener: (info: { exitCode: number; signal?: number }) => void
@@ -354,6 +459,9 @@ export function installPlatformBridge() {
         (window as any).desktopAPI.getDbInitError = () => invoke('db_get_init_error');
         (window as any).desktopAPI.dbRetryInit = () => invoke('db_retry_init');
         (window as any).desktopAPI.dbBackupAndReset = () => invoke('db_backup_and_reset');
+        (window as any).desktopAPI.dbBackup = () => invoke('db_backup');
+        (window as any).desktopAPI.dbVacuum = () => invoke('db_vacuum');
+        (window as any).desktopAPI.dbCheckIntegrity = () => invoke('db_check_integrity');
         (window as any).desktopAPI.onDbInitError = (listener: (data: any) => void) => {
           const promise = listen('db:init-error', (event) => {
             listener(event.payload as any);
@@ -363,11 +471,21 @@ export function installPlatformBridge() {
             promise.then((unlisten) => unlisten()).catch(() => {});
           };
         };
+        (window as any).desktopAPI.onDbRuntimeError = (listener: (data: any) => void) => {
+          const promise = listen('db:runtime-error', (event) => {
+            listener(event.payload as any);
+          });
+          promise.catch(() => {});
+          return () => {
+            promise.then((unlisten) => unlisten()).catch(() => {});
+          };
+        };
         (window as any).desktopAPI.getProviderStatuses = (opts?: {
           refresh?: boolean;
           providers?: string[];
           providerId?: string;
         }) => invoke('providers_get_statuses', { opts });
+        (window as any).desktopAPI.preflightCheck = () => invoke('preflight_check');
         (window as any).desktopAPI.onProviderStatusUpdated = (
           listener: (data: { providerId: string; status: any }) => void
         ) => {
@@ -380,6 +498,8 @@ export function installPlatformBridge() {
           };
         };
         (window as any).desktopAPI.terminalGetTheme = () => invoke('terminal_get_theme');
+        (window as any).desktopAPI.terminalSetTheme = (theme: Record<string, string> | null) =>
+          invoke('terminal_set_theme', { theme });
         (window as any).desktopAPI.githubCheckCLIInstalled = () =>
           invoke('github_check_cli_installed');
         (window as any).desktopAPI.githubInstallCLI = () => invoke('github_install_cli');
@@ -393,11 +513,15 @@ export function installPlatformBridge() {
           invoke('github_connect', { projectPath });
         (window as any).desktopAPI.githubCloneRepository = (
           repoUrl: string,
-          localPath: string
-        ) => invoke('github_clone_repository', { repoUrl, localPath });
+          localPath: string,
+          useSsh?: boolean
+        ) => invoke('github_clone_repository', { repoUrl, localPath, useSsh });
         (window as any).desktopAPI.githubListPullRequests = (projectPath: string) =>
           invoke('github_list_pull_requests', { projectPath });
         (window as any).desktopAPI.githubLogout = () => invoke('github_logout');
+        (window as any).desktopAPI.githubStoreToken = (token: string) =>
+          invoke('github_store_token', { token });
+        (window as any).desktopAPI.githubGetToken = () => invoke('github_get_token');
         (window as any).desktopAPI.githubGetOwners = () => invoke('github_get_owners');
         (window as any).desktopAPI.githubValidateRepoName = (name: string, owner: string) =>
           invoke('github_validate_repo_name', { name, owner });
@@ -430,35 +554,151 @@ export function installPlatformBridge() {
           });
         (window as any).desktopAPI.githubIssueGet = (projectPath: string, number: number) =>
           invoke('github_issue_get', { projectPath, number });
+        (window as any).desktopAPI.githubIssueComments = (projectPath: string, number: number) =>
+          invoke('github_get_issue_comments', { projectPath, number });
         (window as any).desktopAPI.getGitInfo = (projectPath: string) =>
           invoke('git_get_info', { projectPath });
         (window as any).desktopAPI.getGitStatus = (taskPath: string) =>
           invoke('git_get_status', { taskPath });
-        (window as any).desktopAPI.getFileDiff = (args: { taskPath: string; filePath: string }) =>
-          invoke('git_get_file_diff', { taskPath: args.taskPath, filePath: args.filePath });
+        (window as any).desktopAPI.getGitChangeSummary = (taskPath: string) =>
+          invoke('git_get_change_summary', { taskPath });
+        (window as any).desktopAPI.getFileDiff = (args: {
+          taskPath: string;
+          filePath: string;
+          wordDiff?: boolean;
+          mode?: 'head' | 'staged' | 'working';
+        }) =>
+          invoke('git_get_file_diff', {
+            taskPath: args.taskPath,
+            filePath: args.filePath,
+            wordDiff: args.wordDiff,
+            mode: args.mode,
+          });
+        (window as any).desktopAPI.gitBlame = (args: {
+          taskPath: string;
+          filePath: string;
+          startLine?: number;
+          endLine?: number;
+        }) =>
+          invoke('git_blame', {
+            taskPath: args.taskPath,
+            filePath: args.filePath,
+            startLine: args.startLine,
+            endLine: args.endLine,
+          });
+        (window as any).desktopAPI.getGitLog = (args: {
+          taskPath: string;
+          limit?: number;
+          base?: string;
+        }) =>
+          invoke('git_get_log', {
+            taskPath: args.taskPath,
+            limit: args.limit,
+            base: args.base,
+          });
+        (window as any).desktopAPI.getCommitDiff = (args: {
+          taskPath: string;
+          commitSha: string;
+          filePath?: string;
+        }) =>
+          invoke('git_get_commit_diff', {
+            taskPath: args.taskPath,
+            commitSha: args.commitSha,
+            filePath: args.filePath,
+          });
+        (window as any).desktopAPI.getDiffs = (args: { taskPath: string; filePaths: string[] }) =>
+          invoke('git_get_diffs', { taskPath: args.taskPath, filePaths: args.filePaths });
         (window as any).desktopAPI.stageFile = (args: { taskPath: string; filePath: string }) =>
           invoke('git_stage_file', { taskPath: args.taskPath, filePath: args.filePath });
+        (window as any).desktopAPI.stageAll = (taskPath: string) =>
+          invoke('git_stage_all', { taskPath });
+        (window as any).desktopAPI.unstageAll = (taskPath: string) =>
+          invoke('git_unstage_all', { taskPath });
+        (window as any).desktopAPI.exportPatch = (args: {
+          taskPath: string;
+          base?: string;
+          outputPath?: string;
+        }) =>
+          invoke('git_export_patch', {
+            taskPath: args.taskPath,
+            base: args.base,
+            outputPath: args.outputPath,
+          });
+        (window as any).desktopAPI.applyPatch = (args: {
+          taskPath: string;
+          patchPath: string;
+          check?: boolean;
+        }) =>
+          invoke('git_apply_patch', {
+            taskPath: args.taskPath,
+            patchPath: args.patchPath,
+            check: args.check,
+          });
+        (window as any).desktopAPI.gitBenchmark = (taskPath: string) =>
+          invoke('git_benchmark', { taskPath });
         (window as any).desktopAPI.revertFile = (args: { taskPath: string; filePath: string }) =>
           invoke('git_revert_file', { taskPath: args.taskPath, filePath: args.filePath });
+        (window as any).desktopAPI.discardAllChanges = (args: {
+          taskPath: string;
+          confirmToken: string;
+        }) =>
+          invoke('git_discard_all', {
+            taskPath: args.taskPath,
+            confirmToken: args.confirmToken,
+          });
         (window as any).desktopAPI.gitCommitAndPush = (args: {
           taskPath: string;
           commitMessage?: string;
           createBranchIfOnDefault?: boolean;
           branchPrefix?: string;
+          generateMessage?: boolean;
         }) =>
           invoke('git_commit_and_push', {
             taskPath: args.taskPath,
             commitMessage: args.commitMessage,
             createBranchIfOnDefault: args.createBranchIfOnDefault,
             branchPrefix: args.branchPrefix,
+            generateMessage: args.generateMessage,
+          });
+        (window as any).desktopAPI.gitPull = (args: { taskPath: string; rebase?: boolean }) =>
+          invoke('git_pull', { taskPath: args.taskPath, rebase: args.rebase });
+        (window as any).desktopAPI.generateCommitMessage = (args: {
+          taskPath: string;
+          requestId?: string;
+        }) =>
+          invoke('git_generate_commit_message', {
+            taskPath: args.taskPath,
+            requestId: args.requestId,
+          });
+        (window as any).desktopAPI.providerRun = (args: {
+          providerId: string;
+          taskPath: string;
+          prompt: string;
+          model?: string;
+          timeoutMs?: number;
+          requestId?: string;
+        }) =>
+          invoke('provider_run', {
+            providerId: args.providerId,
+            taskPath: args.taskPath,
+            prompt: args.prompt,
+            model: args.model,
+            timeoutMs: args.timeoutMs,
+            requestId: args.requestId,
           });
+        (window as any).desktopAPI.providerCapabilities = (providerId: string) =>
+          invoke('provider_capabilities', { providerId });
+        (window as any).desktopAPI.providerCancel = (requestId: string) =>
+          invoke('provider_cancel', { requestId });
         (window as any).desktopAPI.generatePrContent = (args: {
           taskPath: string;
           base?: string;
+          requestId?: string;
         }) =>
           invoke('git_generate_pr_content', {
             taskPath: args.taskPath,
             base: args.base,
+            requestId: args.requestId,
           });
         (window as any).desktopAPI.createPullRequest = (args: {
           taskPath: string;
@@ -469,19 +709,24 @@ export function installPlatformBridge() {
           draft?: boolean;
           web?: boolean;
           fill?: boolean;
+          assignSelf?: boolean;
+          project?: string;
         }) =>
-          invoke('git_create_pr', {
-            taskPath: args.taskPath,
-            title: args.title,
-            body: args.body,
-            base: args.base,
-            head: args.head,
-            draft: args.draft,
-            web: args.web,
-            fill: args.fill,
-          });
+          invoke('git_create_pr', { args });
         (window as any).desktopAPI.getPrStatus = (args: { taskPath: string }) =>
           invoke('git_get_pr_status', { taskPath: args.taskPath });
+        (window as any).desktopAPI.githubRerunChecks = (args: {
+          taskPath: string;
+          onlyFailed?: boolean;
+        }) =>
+          invoke('github_rerun_checks', {
+            taskPath: args.taskPath,
+            onlyFailed: args.onlyFailed,
+          });
+        (window as any).desktopAPI.githubPrMarkReady = (args: { taskPath: string }) =>
+          invoke('github_pr_mark_ready', { taskPath: args.taskPath });
+        (window as any).desktopAPI.githubPrMarkDraft = (args: { taskPath: string }) =>
+          invoke('github_pr_mark_draft', { taskPath: args.taskPath });
         (window as any).desktopAPI.getPrComments = (args: { taskPath: string }) =>
           invoke('git_get_pr_comments', { taskPath: args.taskPath });
         (window as any).desktopAPI.getPrChanges = (args: { taskPath: string }) =>
@@ -503,11 +748,25 @@ export function installPlatformBridge() {
         (window as any).desktopAPI.listRemoteBranches = (args: {
           projectPath: string;
           remote?: string;
+          requestId?: string;
         }) =>
           invoke('git_list_remote_branches', {
             projectPath: args.projectPath,
             remote: args.remote,
+            requestId: args.requestId,
           });
+        (window as any).desktopAPI.deleteRemoteBranch = (args: {
+          projectPath: string;
+          branch: string;
+          remote?: string;
+        }) =>
+          invoke('git_delete_remote_branch', {
+            projectPath: args.projectPath,
+            branch: args.branch,
+            remote: args.remote,
+          });
+        (window as any).desktopAPI.gitCancel = (requestId: string) =>
+          invoke('git_cancel', { requestId });
         (window as any).desktopAPI.hostPreviewSetup = (args: { taskId: string; taskPath: string }) =>
           invoke('host_preview_setup', {
             taskId: args.taskId,
@@ -518,17 +777,21 @@ export function installPlatformBridge() {
           taskPath: string;
           script?: string;
           parentProjectPath?: string;
+          restartOnChange?: boolean;
         }) =>
           invoke('host_preview_start', {
             taskId: args.taskId,
             taskPath: args.taskPath,
             script: args.script,
             parentProjectPath: args.parentProjectPath,
+            restartOnChange: args.restartOnChange,
           });
         (window as any).desktopAPI.hostPreviewStop = (taskId: string) =>
           invoke('host_preview_stop', { taskId });
         (window as any).desktopAPI.hostPreviewStopAll = (exceptId?: string) =>
           invoke('host_preview_stop_all', { exceptId });
+        (window as any).desktopAPI.hostPreviewLogs = (taskId: string, tail?: number) =>
+          invoke('host_preview_logs', { taskId, tail });
         (window as any).desktopAPI.onHostPreviewEvent = (listener: (data: any) => void) => {
           const promise = listen('preview:host:event', (event) => {
             listener(event.payload as any);
@@ -538,6 +801,33 @@ export function installPlatformBridge() {
             promise.then((unlisten) => unlisten()).catch(() => {});
           };
         };
+        (window as any).desktopAPI.onHostPreviewLog = (listener: (data: any) => void) => {
+          const promise = listen('host_preview:log', (event) => {
+            listener(event.payload as any);
+          });
+          promise.catch(() => {});
+          return () => {
+            promise.then((unlisten) => unlisten()).catch(() => {});
+          };
+        };
+        (window as any).desktopAPI.onHostPreviewExited = (listener: (data: any) => void) => {
+          const promise = listen('host_preview:exited', (event) => {
+            listener(event.payload as any);
+          });
+          promise.catch(() => {});
+          return () => {
+            promise.then((unlisten) => unlisten()).catch(() => {});
+          };
+        };
+        (window as any).desktopAPI.onHostPreviewRestarting = (listener: (data: any) => void) => {
+          const promise = listen('host_preview:restarting', (event) => {
+            listener(event.payload as any);
+          });
+          promise.catch(() => {});
+          return () => {
+            promise.then((unlisten) => unlisten()).catch(() => {});
+          };
+        };
 
         (window as any).desktopAPI.onPlanEvent = (listener: (data: any) => void) => {
           const promise = listen('plan:event', (event) => {
@@ -551,13 +841,21 @@ export function installPlatformBridge() {
         (window as any).desktopAPI.getProjects = () => invoke('db_get_projects');
         (window as any).desktopAPI.saveProject = (project: any) =>
           invoke('db_save_project', { project });
-        (window as any).desktopAPI.getTasks = (projectId?: string) =>
-          invoke('db_get_tasks', { projectId });
+        (window as any).desktopAPI.getTasks = (projectId?: string, tags?: string[]) =>
+          invoke('db_get_tasks', { projectId, tags });
         (window as any).desktopAPI.saveTask = (task: any) => invoke('db_save_task', { task });
         (window as any).desktopAPI.deleteProject = (projectId: string) =>
           invoke('db_delete_project', { projectId });
+        (window as any).desktopAPI.cleanupOrphans = () => invoke('db_cleanup_orphans');
+        (window as any).desktopAPI.getStats = () => invoke('db_get_stats');
+        (window as any).desktopAPI.globalSearch = (query: string, limit?: number) =>
+          invoke('db_global_search', { query, limit });
         (window as any).desktopAPI.deleteTask = (taskId: string) =>
           invoke('db_delete_task', { taskId });
+        (window as any).desktopAPI.getTaskTags = (taskId: string) =>
+          invoke('db_get_task_tags', { taskId });
+        (window as any).desktopAPI.setTaskTags = (taskId: string, tags: string[]) =>
+          invoke('db_set_task_tags', { taskId, tags });
         (window as any).desktopAPI.saveConversation = (conversation: any) =>
           invoke('db_save_conversation', { conversation });
         (window as any).desktopAPI.getConversations = (taskId: string) =>
@@ -570,24 +868,37 @@ export function installPlatformBridge() {
           invoke('db_get_messages', { conversationId });
         (window as any).desktopAPI.deleteConversation = (conversationId: string) =>
           invoke('db_delete_conversation', { conversationId });
+        (window as any).desktopAPI.renameConversation = (conversationId: string, title: string) =>
+          invoke('db_rename_conversation', { conversationId, title });
         (window as any).desktopAPI.getProjectSettings = (projectId: string) =>
           invoke('project_settings_get', { projectId });
         (window as any).desktopAPI.updateProjectSettings = (args: {
           projectId: string;
-          baseRef: string;
+          baseRef?: string;
+          overrides?: Record<string, any>;
         }) =>
           invoke('project_settings_update', {
-            projectId: args.projectId,
-            baseRef: args.baseRef,
+            args: {
+              projectId: args.projectId,
+              baseRef: args.baseRef,
+              overrides: args.overrides,
+            },
           });
         (window as any).desktopAPI.fetchProjectBaseRef = (args: {
           projectId: string;
           projectPath: string;
+          taskId?: string;
         }) =>
           invoke('project_settings_fetch_base_ref', {
             projectId: args.projectId,
             projectPath: args.projectPath,
+            taskId: args.taskId,
           });
+        (window as any).desktopAPI.taskSetBaseRef = (args: {
+          taskId: string;
+          baseRef?: string | null;
+        }) =>
+          invoke('task_set_base_ref', { args: { taskId: args.taskId, baseRef: args.baseRef } });
         const invokeWithArgs = async <T>(command: string, payload: Record<string, any>) => {
           try {
             return await invoke<T>(command, payload);
@@ -605,12 +916,20 @@ export function installPlatformBridge() {
           taskName: string;
           projectId: string;
           autoApprove?: boolean;
+          requestId?: string;
+          taskId?: string;
+          baseRef?: string;
+          push?: boolean;
         }) =>
           invokeWithArgs('worktree_create', {
             projectPath: args.projectPath,
             taskName: args.taskName,
             projectId: args.projectId,
             autoApprove: args.autoApprove,
+            requestId: args.requestId,
+            taskId: args.taskId,
+            baseRef: args.baseRef,
+            push: args.push,
           });
         (window as any).desktopAPI.worktreeList = (args: { projectPath: string }) =>
           invokeWithArgs('worktree_list', { projectPath: args.projectPath });
@@ -626,8 +945,14 @@ export function installPlatformBridge() {
             worktreePath: args.worktreePath,
             branch: args.branch,
           });
-        (window as any).desktopAPI.worktreeStatus = (args: { worktreePath: string }) =>
-          invokeWithArgs('worktree_status', { worktreePath: args.worktreePath });
+        (window as any).desktopAPI.worktreeStatus = (args: {
+          worktreePath: string;
+          baseRef?: string;
+        }) =>
+          invokeWithArgs('worktree_status', {
+            worktreePath: args.worktreePath,
+            baseRef: args.baseRef,
+          });
         (window as any).desktopAPI.worktreeMerge = (args: {
           projectPath: string;
           worktreeId: string;
@@ -638,6 +963,12 @@ export function installPlatformBridge() {
           });
         (window as any).desktopAPI.worktreeGet = (args: { worktreeId: string }) =>
           invokeWithArgs('worktree_get', { worktreeId: args.worktreeId });
+        (window as any).desktopAPI.worktreeBranchDiff = (args: { worktreeId: string }) =>
+          invokeWithArgs('worktree_branch_diff', { worktreeId: args.worktreeId });
+        (window as any).desktopAPI.worktreeRepair = (args: {
+          worktreeId: string;
+          branchName?: string;
+        }) => invokeWithArgs('worktree_repair', args);
         (window as any).desktopAPI.worktreeGetAll = () => invoke('worktree_get_all');
         (window as any).desktopAPI.fsList = (
           root: string,
@@ -648,6 +979,28 @@ export function installPlatformBridge() {
             includeDirs: opts?.includeDirs,
             maxEntries: opts?.maxEntries,
           });
+        (window as any).desktopAPI.fsListTree = (
+          path: string,
+          opts?: { maxDepth?: number; includeGlobs?: string[]; excludeGlobs?: string[] }
+        ) =>
+          invoke('fs_list_tree', {
+            path,
+            maxDepth: opts?.maxDepth,
+            includeGlobs: opts?.includeGlobs,
+            excludeGlobs: opts?.excludeGlobs,
+          });
+        (window as any).desktopAPI.fsWatchStart = (path: string, id: string) =>
+          invoke('fs_watch_start', { path, id });
+        (window as any).desktopAPI.fsWatchStop = (id: string) => invoke('fs_watch_stop', { id });
+        (window as any).desktopAPI.onFsChanged = (listener: (data: any) => void) => {
+          const promise = listen('fs:changed', (event) => {
+            listener(event.payload as any);
+          });
+          promise.catch(() => {});
+          return () => {
+            promise.then((unlisten) => unlisten()).catch(() => {});
+          };
+        };
         (window as any).desktopAPI.fsRead = (root: string, relPath: string, maxBytes?: number) =>
           invoke('fs_read', { root, relPath, maxBytes });
         (window as any).desktopAPI.fsWriteFile = (
@@ -675,17 +1028,23 @@ export function installPlatformBridge() {
           taskPath: string;
           runId?: string;
           mode?: 'container' | 'host';
+          reuse?: boolean;
+          dryRun?: boolean;
         }) =>
           invoke('container_start_run', {
             taskId: args.taskId,
             taskPath: args.taskPath,
             runId: args.runId,
             mode: args.mode,
+            reuse: args.reuse,
+            dryRun: args.dryRun,
           });
-        (window as any).desktopAPI.stopContainerRun = (taskId: string) =>
-          invoke('container_stop_run', { taskId });
+        (window as any).desktopAPI.stopContainerRun = (taskId: string, taskPath?: string) =>
+          invoke('container_stop_run', { args: { taskId, taskPath } });
         (window as any).desktopAPI.inspectContainerRun = (taskId: string) =>
           invoke('container_inspect_run', { taskId });
+        (window as any).desktopAPI.pruneContainers = (args?: { all?: boolean }) =>
+          invoke('container_prune', { args });
         (window as any).desktopAPI.resolveServiceIcon = (args: {
           service: string;
           allowNetwork?: boolean;
@@ -696,6 +1055,18 @@ export function installPlatformBridge() {
             allowNetwork: args.allowNetwork,
             taskPath: args.taskPath,
           });
+        (window as any).desktopAPI.resolveServiceIcons = (args: {
+          services: string[];
+          allowNetwork?: boolean;
+          taskPath?: string;
+        }) =>
+          invoke('icons_resolve_services', {
+            args: {
+              services: args.services,
+              allowNetwork: args.allowNetwork,
+              taskPath: args.taskPath,
+            },
+          });
         (window as any).desktopAPI.onRunEvent = (listener: (event: any) => void) => {
           const promise = listen('run:event', (event) => {
             listener(event.payload as any);
@@ -727,14 +1098,28 @@ export function installPlatformBridge() {
             ports,
             timeoutMs,
           });
+        (window as any).desktopAPI.netWaitForPort = (
+          host: string,
+          port: number,
+          timeoutMs?: number,
+          intervalMs?: number
+        ) =>
+          invoke('net_wait_for_port', {
+            host,
+            port,
+            timeoutMs,
+            intervalMs,
+          });
         (window as any).desktopAPI.planLock = (taskPath: string) =>
-          invoke('plan_lock', { taskPath });
+          invoke('plan_lock', { args: { taskPath } });
         (window as any).desktopAPI.planUnlock = (taskPath: string) =>
-          invoke('plan_unlock', { taskPath });
+          invoke('plan_unlock', { args: { taskPath } });
         (window as any).desktopAPI.planApplyLock = (taskPath: string) =>
-          invoke('plan_lock', { taskPath });
+          invoke('plan_lock', { args: { taskPath } });
         (window as any).desktopAPI.planReleaseLock = (taskPath: string) =>
-          invoke('plan_unlock', { taskPath });
+          invoke('plan_unlock', { args: { taskPath } });
+        (window as any).desktopAPI.planLockStatus = (taskPath: string, ttlMs?: number) =>
+          invoke('plan_lock_status', { args: { taskPath, ttlMs } });
         (window as any).desktopAPI.debugAppendLog = (
           filePath: string,
           content: string,
@@ -857,9 +1242,17 @@ export function installPlatformBridge() {
         (window as any).desktopAPI.browserGoBack = () => invoke('browser_view_go_back');
         (window as any).desktopAPI.browserGoForward = () => invoke('browser_view_go_forward');
         (window as any).desktopAPI.browserReload = () => invoke('browser_view_reload');
+        (window as any).desktopAPI.browserCurrentUrl = () => invoke('browser_view_current_url');
         (window as any).desktopAPI.browserOpenDevTools = () =>
           invoke('browser_view_open_devtools');
         (window as any).desktopAPI.browserClear = () => invoke('browser_view_clear');
+        (window as any).desktopAPI.browserCapture = (args?: {
+          format?: 'png' | 'jpeg';
+          quality?: number;
+        }) =>
+          invoke('browser_view_capture', { format: args?.format, quality: args?.quality });
+        (window as any).desktopAPI.browserClearStorage = (scope?: 'cookies' | 'cache' | 'all') =>
+          invoke('browser_view_clear_storage', { scope });
         (window as any).desktopAPI.linearSaveToken = (token: string) =>
           invoke('linear_save_token', { token });
         (window as any).desktopAPI.linearCheckConnection = () =>
@@ -869,6 +1262,10 @@ export function installPlatformBridge() {
           invoke('linear_initial_fetch', { limit });
         (window as any).desktopAPI.linearSearchIssues = (searchTerm: string, limit?: number) =>
           invoke('linear_search_issues', { searchTerm, limit });
+        (window as any).desktopAPI.linearListWorkflowStates = (teamId: string) =>
+          invoke('linear_list_workflow_states', { teamId });
+        (window as any).desktopAPI.linearUpdateIssueState = (issueId: string, stateId: string) =>
+          invoke('linear_update_issue_state', { issueId, stateId });
         (window as any).desktopAPI.jiraSaveCredentials = (args: {
           siteUrl: string;
           email: string;
@@ -885,6 +1282,15 @@ export function installPlatformBridge() {
           invoke('jira_initial_fetch', { limit });
         (window as any).desktopAPI.jiraSearchIssues = (searchTerm: string, limit?: number) =>
           invoke('jira_search_issues', { searchTerm, limit });
+        (window as any).desktopAPI.jiraSearchJql = (jql: string, limit?: number) =>
+          invoke('jira_search_jql', { jql, limit });
+        (window as any).desktopAPI.jiraGetAttachments = (issueKey: string) =>
+          invoke('jira_get_attachments', { issueKey });
+        (window as any).desktopAPI.jiraDownloadAttachment = (
+          issueKey: string,
+          attachmentId: string,
+          destPath: string
+        ) => invoke('jira_download_attachment', { issueKey, attachmentId, destPath });
         (window as any).desktopAPI.githubCreatePullRequestWorktree = (args: {
           projectPath: string;
           projectId: string;
@@ -892,15 +1298,9 @@ export function installPlatformBridge() {
           prTitle?: string;
           taskName?: string;
           branchName?: string;
+          force?: boolean;
         }) =>
-          invoke('github_create_pull_request_worktree', {
-            projectPath: args.projectPath,
-            projectId: args.projectId,
-            prNumber: args.prNumber,
-            prTitle: args.prTitle,
-            taskName: args.taskName,
-            branchName: args.branchName,
-          });
+          invoke('github_create_pull_request_worktree', { args });
       })
       .catch(() => {});
   }
@@ -38,10 +38,17 @@ export type PrFile = {
   changeType?: string | null;
 };
 
+export type PrReviewer = {
+  login: string;
+  state: string;
+  submittedAt?: string | null;
+};
+
 export type PrStatus = PrInfo & {
   mergeStateStatus?: string;
   commentsCount?: number;
   reviewCount?: number;
+  reviewers?: PrReviewer[];
   checksSummary?: PrChecksSummary;
   headRefName?: string;
   baseRefName?: string;
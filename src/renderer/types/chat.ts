@@ -15,6 +15,10 @@ export interface TaskMetadata {
   jiraIssue?: JiraIssueSummary | null;
   initialPrompt?: string | null;
   autoApprove?: boolean | null;
+  /** Per-task base ref override; managed by the backend once the task is saved */
+  baseRef?: string | null;
+  /** Per-task terminal env overrides; managed by the backend via task_set_env */
+  env?: Record<string, string> | null;
   /** Set to true after the initial injection (prompt/issue) has been sent to the agent */
   initialInjectionSent?: boolean | null;
   // When present, this task is a multi-agent task orchestrating multiple worktrees
@@ -1,6 +1,11 @@
 // Updated for Codex integration
 import type { ResolvedContainerConfig, RunnerEvent, RunnerMode } from '../../shared/container';
 
+type ProjectSettingsOverrides = {
+  repository?: { branchTemplate?: string; pushOnCreate?: boolean };
+  projectPrep?: { autoInstallOnOpenInEditor?: boolean };
+};
+
 type ProjectSettingsPayload = {
   projectId: string;
   name: string;
@@ -8,6 +13,7 @@ type ProjectSettingsPayload = {
   gitRemote?: string;
   gitBranch?: string;
   baseRef?: string;
+  overrides?: ProjectSettingsOverrides;
 };
 
 export {};
@@ -24,7 +30,12 @@ declare global {
       getSettings: () => Promise<{
         success: boolean;
         settings?: {
-          repository: { branchTemplate: string; pushOnCreate: boolean };
+          repository: {
+            branchTemplate: string;
+            pushOnCreate: boolean;
+            signCommits?: boolean;
+            signingFormat?: 'gpg' | 'ssh';
+          };
           projectPrep?: { autoInstallOnOpenInEditor: boolean };
           browserPreview?: { enabled: boolean; engine: 'chromium' };
           notifications?: { enabled: boolean; sound: boolean };
@@ -42,12 +53,24 @@ declare global {
           projects?: {
             defaultDirectory: string;
           };
+          serviceIcons?: Record<string, string>;
+          /** Provider id -> model passed via the CLI's `--model` flag, where supported. */
+          providerModels?: Record<string, string>;
+          /** Providers tried in order for PR title/description generation. */
+          prGenerationProviders?: string[];
+          terminal?: { customTheme: Record<string, string> | null; snapshotMaxBytes?: number };
         };
+        warnings?: string[];
         error?: string;
       }>;
       updateSettings: (
         settings: Partial<{
-          repository: { branchTemplate?: string; pushOnCreate?: boolean };
+          repository: {
+            branchTemplate?: string;
+            pushOnCreate?: boolean;
+            signCommits?: boolean;
+            signingFormat?: 'gpg' | 'ssh';
+          };
           projectPrep: { autoInstallOnOpenInEditor?: boolean };
           browserPreview: { enabled?: boolean; engine?: 'chromium' };
           notifications: { enabled?: boolean; sound?: boolean };
@@ -65,11 +88,22 @@ declare global {
           projects?: {
             defaultDirectory?: string;
           };
+          /** Service name -> icon domain; set a key to null to remove it. */
+          serviceIcons?: Record<string, string | null>;
+          providerModels?: Record<string, string>;
+          prGenerationProviders?: string[];
+          /** Hex colors keyed by xterm theme name; null restores the default theme. */
+          terminal?: { customTheme?: Record<string, string> | null; snapshotMaxBytes?: number };
         }>
       ) => Promise<{
         success: boolean;
         settings?: {
-          repository: { branchTemplate: string; pushOnCreate: boolean };
+          repository: {
+            branchTemplate: string;
+            pushOnCreate: boolean;
+            signCommits?: boolean;
+            signingFormat?: 'gpg' | 'ssh';
+          };
           projectPrep?: { autoInstallOnOpenInEditor: boolean };
           browserPreview?: { enabled: boolean; engine: 'chromium' };
           notifications?: { enabled: boolean; sound: boolean };
@@ -87,6 +121,12 @@ declare global {
           projects?: {
             defaultDirectory: string;
           };
+          serviceIcons?: Record<string, string>;
+          /** Provider id -> model passed via the CLI's `--model` flag, where supported. */
+          providerModels?: Record<string, string>;
+          /** Providers tried in order for PR title/description generation. */
+          prGenerationProviders?: string[];
+          terminal?: { customTheme: Record<string, string> | null; snapshotMaxBytes?: number };
         };
         error?: string;
       }>;
@@ -106,7 +146,18 @@ declare global {
         backupPath?: string;
         movedPath?: string;
       }>;
+      dbBackup: () => Promise<{ success: boolean; backupPath?: string; error?: string }>;
+      dbVacuum: () => Promise<{ success: boolean; error?: string }>;
+      dbCheckIntegrity: () => Promise<{
+        success: boolean;
+        ok?: boolean;
+        problems?: string[];
+        error?: string;
+      }>;
       onDbInitError: (listener: (data: { message?: string; dbPath?: string }) => void) => () => void;
+      onDbRuntimeError: (
+        listener: (data: { kind?: string; message?: string; dbPath?: string }) => void
+      ) => () => void;
 
       // PTY
       ptyStart: (opts: {
@@ -135,11 +186,42 @@ declare global {
         error?: string;
       }>;
       ptyClearSnapshot: (args: { id: string }) => Promise<{ ok: boolean }>;
+      ptyTrimSnapshot: (args: {
+        id: string;
+        maxBytes?: number;
+      }) => Promise<{ ok: boolean; bytes?: number; error?: string }>;
+      taskSetEnv: (args: {
+        taskId: string;
+        env: Record<string, string>;
+      }) => Promise<{ ok: boolean; env?: Record<string, string>; error?: string }>;
+      ptyWriteFile: (args: {
+        id: string;
+        filePath: string;
+        terminator?: string;
+      }) => Promise<{ ok: boolean; bytes?: number; error?: string }>;
+      ptySaveSession: (args: {
+        id: string;
+        cols?: number;
+        rows?: number;
+      }) => Promise<{ ok: boolean; error?: string }>;
+      ptyRestoreSession: (args: {
+        id: string;
+        env?: Record<string, string>;
+      }) => Promise<{
+        ok: boolean;
+        scrollback?: string;
+        savedAt?: string;
+        envKeys?: string[];
+        error?: string;
+      }>;
       onPtyExit: (
         id: string,
         listener: (info: { exitCode: number; signal?: number }) => void
       ) => () => void;
       onPtyStarted: (listener: (data: { id: string }) => void) => () => void;
+      terminalSetTheme: (
+        theme: Record<string, string> | null
+      ) => Promise<{ ok: boolean; theme?: Record<string, string> | null; error?: string }>;
       terminalGetTheme: () => Promise<{
         ok: boolean;
         config?: {
@@ -179,7 +261,18 @@ declare global {
         taskName: string;
         projectId: string;
         autoApprove?: boolean;
-      }) => Promise<{ success: boolean; worktree?: any; error?: string }>;
+        requestId?: string;
+        taskId?: string;
+        baseRef?: string;
+        push?: boolean;
+      }) => Promise<{
+        success: boolean;
+        worktree?: any;
+        pushed?: boolean;
+        cancelled?: boolean;
+        code?: string;
+        error?: string;
+      }>;
       worktreeList: (args: {
         projectPath: string;
       }) => Promise<{ success: boolean; worktrees?: any[]; error?: string }>;
@@ -191,6 +284,7 @@ declare global {
       }) => Promise<{ success: boolean; error?: string }>;
       worktreeStatus: (args: {
         worktreePath: string;
+        baseRef?: string;
       }) => Promise<{ success: boolean; status?: any; error?: string }>;
       worktreeMerge: (args: {
         projectPath: string;
@@ -199,6 +293,30 @@ declare global {
       worktreeGet: (args: {
         worktreeId: string;
       }) => Promise<{ success: boolean; worktree?: any; error?: string }>;
+      worktreeBranchDiff: (args: { worktreeId: string }) => Promise<{
+        success: boolean;
+        baseRef?: string;
+        summary?: { files: number; additions: number; deletions: number };
+        shortstat?: string;
+        files?: Array<{
+          path: string;
+          status: 'added' | 'deleted' | 'modified' | 'typechange';
+          additions: number;
+          deletions: number;
+          binary: boolean;
+          tooLarge: boolean;
+          diff: { lines: any[]; crlf: boolean } | null;
+        }>;
+        error?: string;
+      }>;
+      worktreeRepair: (args: { worktreeId: string; branchName?: string }) => Promise<{
+        success: boolean;
+        repaired?: boolean;
+        branch?: string;
+        head?: string;
+        code?: 'BRANCH_DIVERGED';
+        error?: string;
+      }>;
       worktreeGetAll: () => Promise<{
         success: boolean;
         worktrees?: any[];
@@ -216,15 +334,22 @@ declare global {
         settings?: ProjectSettingsPayload;
         error?: string;
       }>;
-      updateProjectSettings: (args: { projectId: string; baseRef: string }) => Promise<{
+      updateProjectSettings: (args: {
+        projectId: string;
+        baseRef?: string;
+        overrides?: ProjectSettingsOverrides;
+      }) => Promise<{
         success: boolean;
         settings?: ProjectSettingsPayload;
         error?: string;
       }>;
       getGitInfo: (projectPath: string) => Promise<{
+        success?: boolean;
+        code?: string;
         isGitRepo: boolean;
         remote?: string;
         branch?: string;
+        headShort?: string;
         baseRef?: string;
         upstream?: string;
         aheadCount?: number;
@@ -238,48 +363,205 @@ declare global {
         changes?: Array<{
           path: string;
           status: string;
-          additions: number;
+          additions: number | null;
+          additionsApprox?: boolean;
           deletions: number;
           isStaged: boolean;
+          oldSha?: string;
+          newSha?: string;
           diff?: string;
         }>;
         error?: string;
       }>;
-      getFileDiff: (args: { taskPath: string; filePath: string }) => Promise<{
+      getFileDiff: (args: {
+        taskPath: string;
+        filePath: string;
+        wordDiff?: boolean;
+        mode?: 'head' | 'staged' | 'working';
+      }) => Promise<{
         success: boolean;
         diff?: {
           lines: Array<{
             left?: string;
             right?: string;
             type: 'context' | 'add' | 'del';
+            changes?: Array<[number, number]>;
           }>;
+          crlf?: boolean;
         };
         error?: string;
       }>;
+      gitBlame: (args: {
+        taskPath: string;
+        filePath: string;
+        startLine?: number;
+        endLine?: number;
+      }) => Promise<{
+        success: boolean;
+        lines?: Array<{
+          lineNo: number;
+          commit: string;
+          author: string;
+          authorTime: number | null;
+          summary: string;
+          uncommitted: boolean;
+        }>;
+        error?: string;
+      }>;
+      getGitLog: (args: { taskPath: string; limit?: number; base?: string }) => Promise<{
+        success: boolean;
+        commits?: Array<{ hash: string; author: string; timestamp: number; subject: string }>;
+        error?: string;
+      }>;
+      getCommitDiff: (args: { taskPath: string; commitSha: string; filePath?: string }) => Promise<{
+        success: boolean;
+        sha?: string;
+        files?: Array<{
+          path: string;
+          oldPath?: string | null;
+          binary: boolean;
+          lines: Array<{ left?: string; right?: string; type: 'context' | 'add' | 'del' }>;
+        }>;
+        summary?: { files: number; additions: number; deletions: number };
+        error?: string;
+      }>;
+      getDiffs: (args: { taskPath: string; filePaths: string[] }) => Promise<{
+        success: boolean;
+        diffs?: Record<
+          string,
+          {
+            lines: Array<{ left?: string; right?: string; type: 'context' | 'add' | 'del' }>;
+            crlf?: boolean;
+          }
+        >;
+        error?: string;
+      }>;
       stageFile: (args: { taskPath: string; filePath: string }) => Promise<{
         success: boolean;
         error?: string;
       }>;
+      stageAll: (taskPath: string) => Promise<{
+        success: boolean;
+        changes?: Array<{
+          path: string;
+          status: string;
+          additions: number | null;
+          additionsApprox?: boolean;
+          deletions: number;
+          isStaged: boolean;
+          oldSha?: string;
+          newSha?: string;
+        }>;
+        error?: string;
+      }>;
+      unstageAll: (taskPath: string) => Promise<{
+        success: boolean;
+        changes?: Array<{
+          path: string;
+          status: string;
+          additions: number | null;
+          additionsApprox?: boolean;
+          deletions: number;
+          isStaged: boolean;
+          oldSha?: string;
+          newSha?: string;
+        }>;
+        error?: string;
+      }>;
+      exportPatch: (args: { taskPath: string; base?: string; outputPath?: string }) => Promise<{
+        success: boolean;
+        path?: string;
+        size?: number;
+        base?: string;
+        empty?: boolean;
+        error?: string;
+      }>;
+      applyPatch: (args: { taskPath: string; patchPath: string; check?: boolean }) => Promise<{
+        success: boolean;
+        checked?: boolean;
+        code?: 'CHECK_FAILED';
+        error?: string;
+      }>;
+      gitBenchmark: (taskPath: string) => Promise<{
+        success: boolean;
+        gitPath?: string;
+        taskPath?: string;
+        timings?: Array<{ operation: string; ms: number; ok: boolean; error?: string }>;
+        totalMs?: number;
+        error?: string;
+      }>;
       revertFile: (args: { taskPath: string; filePath: string }) => Promise<{
         success: boolean;
         action?: 'unstaged' | 'reverted';
         error?: string;
       }>;
+      discardAllChanges: (args: { taskPath: string; confirmToken: string }) => Promise<{
+        success: boolean;
+        discarded?: string[];
+        head?: string;
+        code?: string;
+        error?: string;
+      }>;
       gitCommitAndPush: (args: {
         taskPath: string;
         commitMessage?: string;
         createBranchIfOnDefault?: boolean;
         branchPrefix?: string;
+        generateMessage?: boolean;
       }) => Promise<{
         success: boolean;
         branch?: string;
         output?: string;
         error?: string;
       }>;
-      generatePrContent: (args: { taskPath: string; base?: string }) => Promise<{
+      gitPull: (args: { taskPath: string; rebase?: boolean }) => Promise<{
+        success: boolean;
+        branch?: string;
+        updated?: boolean;
+        output?: string;
+        code?: string;
+        conflicted?: boolean;
+        rebase?: boolean;
+        files?: string[];
+        error?: string;
+      }>;
+      generateCommitMessage: (args: { taskPath: string; requestId?: string }) => Promise<{
+        success: boolean;
+        message?: string;
+        error?: string;
+      }>;
+      providerRun: (args: {
+        providerId: string;
+        taskPath: string;
+        prompt: string;
+        model?: string;
+        timeoutMs?: number;
+        requestId?: string;
+      }) => Promise<{ success: boolean; stdout?: string; error?: string }>;
+      providerCapabilities: (providerId: string) => Promise<{
+        success: boolean;
+        providerId?: string;
+        cli?: string;
+        version?: string;
+        flags?: Record<
+          'autoApprove' | 'initialPrompt' | 'model',
+          { flag: string | null; supported: boolean | null }
+        >;
+        code?: string;
+        error?: string;
+      }>;
+      providerCancel: (
+        requestId: string
+      ) => Promise<{ success: boolean; cancelled?: boolean; error?: string }>;
+      generatePrContent: (args: {
+        taskPath: string;
+        base?: string;
+        requestId?: string;
+      }) => Promise<{
         success: boolean;
         title?: string;
         description?: string;
+        cancelled?: boolean;
         error?: string;
       }>;
       createPullRequest: (args: {
@@ -291,12 +573,44 @@ declare global {
         draft?: boolean;
         web?: boolean;
         fill?: boolean;
+        assignSelf?: boolean;
+        project?: string;
       }) => Promise<{
         success: boolean;
         url?: string;
         output?: string;
+        code?: string;
         error?: string;
       }>;
+      getGitChangeSummary: (taskPath: string) => Promise<{
+        success: boolean;
+        files?: number;
+        additions?: number;
+        deletions?: number;
+        additionsApprox?: boolean;
+        error?: string;
+      }>;
+      githubRerunChecks: (args: { taskPath: string; onlyFailed?: boolean }) => Promise<{
+        success: boolean;
+        headSha?: string;
+        runs?: Array<{
+          runId: number;
+          workflowName: string | null;
+          url: string | null;
+          rerun: boolean;
+          error: string | null;
+        }>;
+        externalChecks?: string[];
+        externalFailedChecks?: string[];
+        code?: string;
+        error?: string;
+      }>;
+      githubPrMarkReady: (args: {
+        taskPath: string;
+      }) => Promise<{ success: boolean; pr?: any | null; error?: string }>;
+      githubPrMarkDraft: (args: {
+        taskPath: string;
+      }) => Promise<{ success: boolean; pr?: any | null; error?: string }>;
       getPrStatus: (args: { taskPath: string }) => Promise<{
         success: boolean;
         pr?: {
@@ -307,6 +621,7 @@ declare global {
           mergeStateStatus?: string;
           commentsCount?: number;
           reviewCount?: number;
+          reviewers?: Array<{ login: string; state: string; submittedAt?: string | null }>;
           checksSummary?: {
             total: number;
             passed: number;
@@ -369,6 +684,7 @@ declare global {
           mergeStateStatus?: string;
           commentsCount?: number;
           reviewCount?: number;
+          reviewers?: Array<{ login: string; state: string; submittedAt?: string | null }>;
           checksSummary?: {
             total: number;
             passed: number;
@@ -394,11 +710,29 @@ declare global {
         behind?: number;
         error?: string;
       }>;
-      listRemoteBranches: (args: { projectPath: string; remote?: string }) => Promise<{
+      deleteRemoteBranch: (args: {
+        projectPath: string;
+        branch: string;
+        remote?: string;
+      }) => Promise<{
+        success: boolean;
+        deleted?: boolean;
+        remote?: string;
+        branch?: string;
+        output?: string;
+        error?: string;
+      }>;
+      listRemoteBranches: (args: {
+        projectPath: string;
+        remote?: string;
+        requestId?: string;
+      }) => Promise<{
         success: boolean;
         branches?: Array<{ ref: string; remote: string; branch: string; label: string }>;
+        cancelled?: boolean;
         error?: string;
       }>;
+      gitCancel: (requestId: string) => Promise<{ success: boolean; cancelled?: boolean }>;
       loadContainerConfig: (taskPath: string) => Promise<
         | {
             ok: true;
@@ -426,11 +760,26 @@ declare global {
         taskPath: string;
         runId?: string;
         mode?: RunnerMode;
+        reuse?: boolean;
+        dryRun?: boolean;
       }) => Promise<
         | {
             ok: true;
             runId: string;
             sourcePath: string | null;
+            reused?: boolean;
+            dryRun?: boolean;
+            plan?: {
+              image: string | null;
+              composeFiles: string[];
+              ports: Array<{
+                service: string;
+                protocol: 'tcp' | 'udp';
+                container: number;
+                host: number;
+              }>;
+              previewService: string;
+            };
           }
         | {
             ok: false;
@@ -448,10 +797,33 @@ declare global {
             };
           }
       >;
-      stopContainerRun: (taskId: string) => Promise<{ ok: boolean; error?: string }>;
+      stopContainerRun: (
+        taskId: string,
+        taskPath?: string
+      ) => Promise<{ ok: boolean; removedArtifacts?: string[]; error?: string }>;
+      pruneContainers?: (args?: { all?: boolean }) => Promise<{
+        ok: boolean;
+        removed?: Array<{ project: string; containers: string[]; overrideRemoved: boolean }>;
+        kept?: string[];
+        error?: string;
+      }>;
+      resolveServiceIcons?: (args: {
+        services: string[];
+        allowNetwork?: boolean;
+        taskPath?: string;
+      }) => Promise<{ ok: boolean; icons?: Record<string, string | null> }>;
       openExternal: (url: string) => Promise<{ success: boolean; error?: string }>;
       openIn: (args: {
-        app: 'finder' | 'cursor' | 'vscode' | 'terminal' | 'ghostty' | 'zed' | 'iterm2' | 'warp';
+        app:
+          | 'finder'
+          | 'cursor'
+          | 'vscode'
+          | 'terminal'
+          | 'ghostty'
+          | 'zed'
+          | 'iterm2'
+          | 'warp'
+          | 'wave';
         path: string;
       }) => Promise<{ success: boolean; error?: string }>;
       connectToGitHub: (projectPath: string) => Promise<{
@@ -470,6 +842,18 @@ declare global {
         items?: Array<{ path: string; type: 'file' | 'dir' }>;
         error?: string;
       }>;
+      fsListTree: (
+        path: string,
+        opts?: { maxDepth?: number; includeGlobs?: string[]; excludeGlobs?: string[] }
+      ) => Promise<{
+        success: boolean;
+        items?: Array<{ path: string; isDir: boolean; size: number }>;
+        truncated?: boolean;
+        error?: string;
+      }>;
+      fsWatchStart: (path: string, id: string) => Promise<{ success: boolean; error?: string }>;
+      fsWatchStop: (id: string) => Promise<{ success: boolean; stopped?: boolean; error?: string }>;
+      onFsChanged: (listener: (data: { id: string; paths: string[] }) => void) => () => void;
       fsRead: (
         root: string,
         relPath: string,
@@ -538,11 +922,18 @@ declare global {
         user?: any;
       }>;
       githubGetUser: () => Promise<any>;
-      githubGetRepositories: () => Promise<any[]>;
+      githubGetRepositories: () => Promise<{
+        success: boolean;
+        repositories: any[];
+        code?: string;
+        retryAfterMs?: number;
+        error?: string;
+      }>;
       githubCloneRepository: (
         repoUrl: string,
-        localPath: string
-      ) => Promise<{ success: boolean; error?: string }>;
+        localPath: string,
+        useSsh?: boolean
+      ) => Promise<{ success: boolean; path?: string; error?: string }>;
       githubGetOwners: () => Promise<{
         success: boolean;
         owners?: Array<{ login: string; type: 'User' | 'Organization' }>;
@@ -584,14 +975,19 @@ declare global {
         prTitle?: string;
         taskName?: string;
         branchName?: string;
+        force?: boolean;
       }) => Promise<{
         success: boolean;
         worktree?: any;
         branchName?: string;
         taskName?: string;
+        code?: 'LOCAL_DIVERGED' | 'COMPARE_FAILED' | 'RATE_LIMITED';
+        ahead?: number;
         error?: string;
       }>;
       githubLogout: () => Promise<void>;
+      githubStoreToken: (token: string) => Promise<{ success: boolean; error?: string }>;
+      githubGetToken: () => Promise<{ success: boolean; token?: string | null; error?: string }>;
       // Linear integration
       linearCheckConnection?: () => Promise<{
         connected: boolean;
@@ -620,6 +1016,23 @@ declare global {
         issues?: any[];
         error?: string;
       }>;
+      linearListWorkflowStates?: (teamId: string) => Promise<{
+        success: boolean;
+        states?: Array<{ id: string; name: string; type: string }>;
+        error?: string;
+      }>;
+      linearUpdateIssueState?: (
+        issueId: string,
+        stateId: string
+      ) => Promise<{
+        success: boolean;
+        issue?: {
+          id: string;
+          identifier: string;
+          state: { id: string; name: string; type: string };
+        };
+        error?: string;
+      }>;
       // Jira integration
       jiraSaveCredentials?: (args: {
         siteUrl: string;
@@ -642,6 +1055,26 @@ declare global {
         searchTerm: string,
         limit?: number
       ) => Promise<{ success: boolean; issues?: any[]; error?: string }>;
+      jiraSearchJql?: (
+        jql: string,
+        limit?: number
+      ) => Promise<{ success: boolean; issues?: any[]; error?: string }>;
+      jiraGetAttachments?: (issueKey: string) => Promise<{
+        success: boolean;
+        attachments?: Array<{
+          id: string;
+          filename: string;
+          mimeType: string;
+          size: number;
+          contentUrl: string;
+        }>;
+        error?: string;
+      }>;
+      jiraDownloadAttachment?: (
+        issueKey: string,
+        attachmentId: string,
+        destPath: string
+      ) => Promise<{ success: boolean; path?: string; bytes?: number; error?: string }>;
       getProviderStatuses?: (opts?: {
         refresh?: boolean;
         providers?: string[];
@@ -657,14 +1090,63 @@ declare global {
       onProviderStatusUpdated?: (
         listener: (data: { providerId: string; status: any }) => void
       ) => () => void;
+      preflightCheck: () => Promise<{
+        success: boolean;
+        tools?: Array<{
+          tool: string;
+          present: boolean;
+          version: string | null;
+          path: string | null;
+        }>;
+        error?: string;
+      }>;
 
       // Database operations
       getProjects: () => Promise<any[]>;
       saveProject: (project: any) => Promise<{ success: boolean; error?: string }>;
-      getTasks: (projectId?: string) => Promise<any[]>;
+      getTasks: (projectId?: string, tags?: string[]) => Promise<any[]>;
       saveTask: (task: any) => Promise<{ success: boolean; error?: string }>;
       deleteProject: (projectId: string) => Promise<{ success: boolean; error?: string }>;
+      cleanupOrphans: () => Promise<{
+        success: boolean;
+        removed?: Partial<Record<'tasks' | 'conversations' | 'messages' | 'taskTags', number>>;
+        error?: string;
+      }>;
+      getStats: () => Promise<{
+        success: boolean;
+        stats?: {
+          projects: number;
+          tasks: { total: number; byStatus: Record<string, number> };
+          conversations: number;
+          messages: number;
+          latestTaskByProject: Record<
+            string,
+            { id: string; name: string; status: string; updatedAt: string }
+          >;
+        };
+        error?: string;
+      }>;
+      globalSearch: (
+        query: string,
+        limit?: number
+      ) => Promise<{
+        success: boolean;
+        results?: Array<{
+          kind: 'project' | 'task' | 'conversation';
+          id: string;
+          label: string;
+          projectId: string;
+        }>;
+        error?: string;
+      }>;
       deleteTask: (taskId: string) => Promise<{ success: boolean; error?: string }>;
+      getTaskTags: (
+        taskId: string
+      ) => Promise<{ success: boolean; tags?: string[]; error?: string }>;
+      setTaskTags: (
+        taskId: string,
+        tags: string[]
+      ) => Promise<{ success: boolean; tags?: string[]; error?: string }>;
 
       // Message operations
       saveMessage: (message: any) => Promise<{ success: boolean; error?: string }>;
@@ -674,6 +1156,10 @@ declare global {
       getOrCreateDefaultConversation: (
         taskId: string
       ) => Promise<{ success: boolean; conversation?: any; error?: string }>;
+      renameConversation: (
+        conversationId: string,
+        title: string
+      ) => Promise<{ success: boolean; conversation?: any; error?: string }>;
 
       // Debug helpers
       debugAppendLog: (
@@ -689,7 +1175,34 @@ declare global {
       planReleaseLock: (
         taskPath: string
       ) => Promise<{ success: boolean; restored?: number; error?: string }>;
+      planLockStatus: (
+        taskPath: string,
+        ttlMs?: number
+      ) => Promise<{
+        success: boolean;
+        locked?: boolean;
+        heldBy?: string | null;
+        acquiredAt?: number;
+        ageMs?: number;
+        expired?: boolean;
+        error?: string;
+      }>;
       onPlanEvent: (listener: (evt: { type: string; relPath?: string; root?: string }) => void) => () => void;
+
+      // Browser preview
+      browserCapture: (args?: { format?: 'png' | 'jpeg'; quality?: number }) => Promise<{
+        ok: boolean;
+        mime?: string;
+        dataUrl?: string;
+        path?: string | null;
+        error?: string;
+      }>;
+      browserClearStorage: (scope?: 'cookies' | 'cache' | 'all') => Promise<{
+        ok: boolean;
+        scope?: 'cookies' | 'cache' | 'all';
+        supported?: boolean;
+        error?: string;
+      }>;
     };
   }
 }
@@ -716,6 +1229,9 @@ export interface DesktopAPI {
     movedPath?: string;
   }>;
   onDbInitError: (listener: (data: { message?: string; dbPath?: string }) => void) => () => void;
+  onDbRuntimeError: (
+    listener: (data: { kind?: string; message?: string; dbPath?: string }) => void
+  ) => () => void;
 
   // PTY
   ptyStart: (opts: {
@@ -744,6 +1260,34 @@ export interface DesktopAPI {
     error?: string;
   }>;
   ptyClearSnapshot: (args: { id: string }) => Promise<{ ok: boolean }>;
+  ptyTrimSnapshot: (args: {
+    id: string;
+    maxBytes?: number;
+  }) => Promise<{ ok: boolean; bytes?: number; error?: string }>;
+  taskSetEnv: (args: {
+    taskId: string;
+    env: Record<string, string>;
+  }) => Promise<{ ok: boolean; env?: Record<string, string>; error?: string }>;
+  ptyWriteFile: (args: {
+    id: string;
+    filePath: string;
+    terminator?: string;
+  }) => Promise<{ ok: boolean; bytes?: number; error?: string }>;
+  ptySaveSession: (args: {
+    id: string;
+    cols?: number;
+    rows?: number;
+  }) => Promise<{ ok: boolean; error?: string }>;
+  ptyRestoreSession: (args: {
+    id: string;
+    env?: Record<string, string>;
+  }) => Promise<{
+    ok: boolean;
+    scrollback?: string;
+    savedAt?: string;
+    envKeys?: string[];
+    error?: string;
+  }>;
   onPtyExit: (
     id: string,
     listener: (info: { exitCode: number; signal?: number }) => void
@@ -763,7 +1307,18 @@ export interface DesktopAPI {
     taskName: string;
     projectId: string;
     autoApprove?: boolean;
-  }) => Promise<{ success: boolean; worktree?: any; error?: string }>;
+    requestId?: string;
+    taskId?: string;
+    baseRef?: string;
+    push?: boolean;
+  }) => Promise<{
+    success: boolean;
+    worktree?: any;
+    pushed?: boolean;
+    cancelled?: boolean;
+    code?: string;
+    error?: string;
+  }>;
   worktreeList: (args: {
     projectPath: string;
   }) => Promise<{ success: boolean; worktrees?: any[]; error?: string }>;
@@ -775,6 +1330,7 @@ export interface DesktopAPI {
   }) => Promise<{ success: boolean; error?: string }>;
   worktreeStatus: (args: {
     worktreePath: string;
+    baseRef?: string;
   }) => Promise<{ success: boolean; status?: any; error?: string }>;
   worktreeMerge: (args: {
     projectPath: string;
@@ -783,6 +1339,30 @@ export interface DesktopAPI {
   worktreeGet: (args: {
     worktreeId: string;
   }) => Promise<{ success: boolean; worktree?: any; error?: string }>;
+  worktreeBranchDiff: (args: { worktreeId: string }) => Promise<{
+    success: boolean;
+    baseRef?: string;
+    summary?: { files: number; additions: number; deletions: number };
+    shortstat?: string;
+    files?: Array<{
+      path: string;
+      status: 'added' | 'deleted' | 'modified' | 'typechange';
+      additions: number;
+      deletions: number;
+      binary: boolean;
+      tooLarge: boolean;
+      diff: { lines: any[]; crlf: boolean } | null;
+    }>;
+    error?: string;
+  }>;
+  worktreeRepair: (args: { worktreeId: string; branchName?: string }) => Promise<{
+    success: boolean;
+    repaired?: boolean;
+    branch?: string;
+    head?: string;
+    code?: 'BRANCH_DIVERGED';
+    error?: string;
+  }>;
   worktreeGetAll: () => Promise<{
     success: boolean;
     worktrees?: any[];
@@ -800,15 +1380,22 @@ export interface DesktopAPI {
     settings?: ProjectSettingsPayload;
     error?: string;
   }>;
-  updateProjectSettings: (args: { projectId: string; baseRef: string }) => Promise<{
+  updateProjectSettings: (args: {
+    projectId: string;
+    baseRef?: string;
+    overrides?: ProjectSettingsOverrides;
+  }) => Promise<{
     success: boolean;
     settings?: ProjectSettingsPayload;
     error?: string;
   }>;
   getGitInfo: (projectPath: string) => Promise<{
+    success?: boolean;
+    code?: string;
     isGitRepo: boolean;
     remote?: string;
     branch?: string;
+    headShort?: string;
     baseRef?: string;
     upstream?: string;
     aheadCount?: number;
@@ -816,11 +1403,17 @@ export interface DesktopAPI {
     path?: string;
     error?: string;
   }>;
-  listRemoteBranches: (args: { projectPath: string; remote?: string }) => Promise<{
+  listRemoteBranches: (args: {
+    projectPath: string;
+    remote?: string;
+    requestId?: string;
+  }) => Promise<{
     success: boolean;
     branches?: Array<{ ref: string; remote: string; branch: string; label: string }>;
+    cancelled?: boolean;
     error?: string;
   }>;
+  gitCancel: (requestId: string) => Promise<{ success: boolean; cancelled?: boolean }>;
   createPullRequest: (args: {
     taskPath: string;
     title?: string;
@@ -830,10 +1423,13 @@ export interface DesktopAPI {
     draft?: boolean;
     web?: boolean;
     fill?: boolean;
+    assignSelf?: boolean;
+    project?: string;
   }) => Promise<{
     success: boolean;
     url?: string;
     output?: string;
+    code?: string;
     error?: string;
   }>;
   getPrStatus: (args: { taskPath: string }) => Promise<{
@@ -846,6 +1442,7 @@ export interface DesktopAPI {
       mergeStateStatus?: string;
       commentsCount?: number;
       reviewCount?: number;
+      reviewers?: Array<{ login: string; state: string; submittedAt?: string | null }>;
       checksSummary?: {
         total: number;
         passed: number;
@@ -890,6 +1487,7 @@ export interface DesktopAPI {
       mergeStateStatus?: string;
       commentsCount?: number;
       reviewCount?: number;
+      reviewers?: Array<{ login: string; state: string; submittedAt?: string | null }>;
       checksSummary?: {
         total: number;
         passed: number;
@@ -928,6 +1526,11 @@ export interface DesktopAPI {
   onProviderStatusUpdated?: (
     listener: (data: { providerId: string; status: any }) => void
   ) => () => void;
+  preflightCheck: () => Promise<{
+    success: boolean;
+    tools?: Array<{ tool: string; present: boolean; version: string | null; path: string | null }>;
+    error?: string;
+  }>;
 
   // Filesystem
   fsList: (
@@ -984,11 +1587,18 @@ export interface DesktopAPI {
   onGithubAuthUserUpdated: (callback: (data: { user: any }) => void) => () => void;
   githubIsAuthenticated: () => Promise<boolean>;
   githubGetUser: () => Promise<any>;
-  githubGetRepositories: () => Promise<any[]>;
+  githubGetRepositories: () => Promise<{
+    success: boolean;
+    repositories: any[];
+    code?: string;
+    retryAfterMs?: number;
+    error?: string;
+  }>;
   githubCloneRepository: (
     repoUrl: string,
-    localPath: string
-  ) => Promise<{ success: boolean; error?: string }>;
+    localPath: string,
+    useSsh?: boolean
+  ) => Promise<{ success: boolean; path?: string; error?: string }>;
   githubGetStatus?: () => Promise<{
     installed: boolean;
     authenticated: boolean;
@@ -1006,14 +1616,19 @@ export interface DesktopAPI {
     prTitle?: string;
     taskName?: string;
     branchName?: string;
+    force?: boolean;
   }) => Promise<{
     success: boolean;
     worktree?: any;
     branchName?: string;
     taskName?: string;
+    code?: 'LOCAL_DIVERGED' | 'COMPARE_FAILED' | 'RATE_LIMITED';
+    ahead?: number;
     error?: string;
   }>;
   githubLogout: () => Promise<void>;
+  githubStoreToken: (token: string) => Promise<{ success: boolean; error?: string }>;
+  githubGetToken: () => Promise<{ success: boolean; token?: string | null; error?: string }>;
   // GitHub issues
   githubIssuesList?: (
     projectPath: string,
@@ -1028,6 +1643,20 @@ export interface DesktopAPI {
     projectPath: string,
     number: number
   ) => Promise<{ success: boolean; issue?: any; error?: string }>;
+  githubIssueComments?: (
+    projectPath: string,
+    number: number
+  ) => Promise<{
+    success: boolean;
+    comments?: Array<{
+      id: string | null;
+      author: { login?: string } | null;
+      body: string | null;
+      createdAt: string | null;
+      url: string | null;
+    }>;
+    error?: string;
+  }>;
 
   // Linear integration
   linearCheckConnection?: () => Promise<{
@@ -1057,13 +1686,62 @@ export interface DesktopAPI {
     issues?: any[];
     error?: string;
   }>;
+  linearListWorkflowStates?: (teamId: string) => Promise<{
+    success: boolean;
+    states?: Array<{ id: string; name: string; type: string }>;
+    error?: string;
+  }>;
+  linearUpdateIssueState?: (
+    issueId: string,
+    stateId: string
+  ) => Promise<{
+    success: boolean;
+    issue?: {
+      id: string;
+      identifier: string;
+      state: { id: string; name: string; type: string };
+    };
+    error?: string;
+  }>;
 
   // Database operations
   getProjects: () => Promise<any[]>;
   saveProject: (project: any) => Promise<{ success: boolean; error?: string }>;
-  getTasks: (projectId?: string) => Promise<any[]>;
+  getTasks: (projectId?: string, tags?: string[]) => Promise<any[]>;
   saveTask: (task: any) => Promise<{ success: boolean; error?: string }>;
   deleteProject: (projectId: string) => Promise<{ success: boolean; error?: string }>;
+  cleanupOrphans: () => Promise<{
+    success: boolean;
+    removed?: Partial<Record<'tasks' | 'conversations' | 'messages' | 'taskTags', number>>;
+    error?: string;
+  }>;
+  getStats: () => Promise<{
+    success: boolean;
+    stats?: {
+      projects: number;
+      tasks: { total: number; byStatus: Record<string, number> };
+      conversations: number;
+      messages: number;
+      latestTaskByProject: Record<
+        string,
+        { id: string; name: string; status: string; updatedAt: string }
+      >;
+    };
+    error?: string;
+  }>;
+  globalSearch: (
+    query: string,
+    limit?: number
+  ) => Promise<{
+    success: boolean;
+    results?: Array<{
+      kind: 'project' | 'task' | 'conversation';
+      id: string;
+      label: string;
+      projectId: string;
+    }>;
+    error?: string;
+  }>;
   deleteTask: (taskId: string) => Promise<{ success: boolean; error?: string }>;
 
   // Message operations
@@ -1,5 +1,10 @@
 import type { TerminalSnapshotPayload } from '#types/terminalSnapshot';
 
+type ProjectSettingsOverrides = {
+  repository?: { branchTemplate?: string; pushOnCreate?: boolean };
+  projectPrep?: { autoInstallOnOpenInEditor?: boolean };
+};
+
 type ProjectSettingsPayload = {
   projectId: string;
   name: string;
@@ -7,6 +12,7 @@ type ProjectSettingsPayload = {
   gitRemote?: string;
   gitBranch?: string;
   baseRef?: string;
+  overrides?: ProjectSettingsOverrides;
 };
 
 // Global type declarations for the desktop bridge API
@@ -42,11 +48,22 @@ declare global {
         error?: string;
       }>;
       ptyClearSnapshot: (args: { id: string }) => Promise<{ ok: boolean }>;
+      ptyTrimSnapshot: (args: {
+        id: string;
+        maxBytes?: number;
+      }) => Promise<{ ok: boolean; bytes?: number; error?: string }>;
+      taskSetEnv: (args: {
+        taskId: string;
+        env: Record<string, string>;
+      }) => Promise<{ ok: boolean; env?: Record<string, string>; error?: string }>;
       onPtyExit: (
         id: string,
         listener: (info: { exitCode: number; signal?: number }) => void
       ) => () => void;
       onPtyStarted: (listener: (data: { id: string }) => void) => () => void;
+      terminalSetTheme: (
+        theme: Record<string, string> | null
+      ) => Promise<{ ok: boolean; theme?: Record<string, string> | null; error?: string }>;
       terminalGetTheme: () => Promise<{
         ok: boolean;
         config?: {
@@ -85,7 +102,9 @@ declare global {
         taskName: string;
         projectId: string;
         autoApprove?: boolean;
-      }) => Promise<{ success: boolean; worktree?: any; error?: string }>;
+        baseRef?: string;
+        push?: boolean;
+      }) => Promise<{ success: boolean; worktree?: any; pushed?: boolean; error?: string }>;
       worktreeList: (args: {
         projectPath: string;
       }) => Promise<{ success: boolean; worktrees?: any[]; error?: string }>;
@@ -97,6 +116,7 @@ declare global {
       }) => Promise<{ success: boolean; error?: string }>;
       worktreeStatus: (args: {
         worktreePath: string;
+        baseRef?: string;
       }) => Promise<{ success: boolean; status?: any; error?: string }>;
       worktreeMerge: (args: {
         projectPath: string;
@@ -112,22 +132,38 @@ declare global {
         settings?: ProjectSettingsPayload;
         error?: string;
       }>;
-      updateProjectSettings: (args: { projectId: string; baseRef: string }) => Promise<{
+      updateProjectSettings: (args: {
+        projectId: string;
+        baseRef?: string;
+        overrides?: ProjectSettingsOverrides;
+      }) => Promise<{
         success: boolean;
         settings?: ProjectSettingsPayload;
         error?: string;
       }>;
-      fetchProjectBaseRef: (args: { projectId: string; projectPath: string }) => Promise<{
+      fetchProjectBaseRef: (args: {
+        projectId: string;
+        projectPath: string;
+        taskId?: string;
+      }) => Promise<{
         success: boolean;
         baseRef?: string;
         remote?: string;
         branch?: string;
+        code?: string;
         error?: string;
       }>;
+      taskSetBaseRef: (args: {
+        taskId: string;
+        baseRef?: string | null;
+      }) => Promise<{ success: boolean; baseRef?: string | null; code?: string; error?: string }>;
       getGitInfo: (projectPath: string) => Promise<{
+        success?: boolean;
+        code?: string;
         isGitRepo: boolean;
         remote?: string;
         branch?: string;
+        headShort?: string;
         baseRef?: string;
         upstream?: string;
         aheadCount?: number;
@@ -141,12 +177,21 @@ declare global {
         changes?: Array<{
           path: string;
           status: string;
-          additions: number;
+          additions: number | null;
+          additionsApprox?: boolean;
           deletions: number;
           diff?: string;
         }>;
         error?: string;
       }>;
+      getGitChangeSummary: (taskPath: string) => Promise<{
+        success: boolean;
+        files?: number;
+        additions?: number;
+        deletions?: number;
+        additionsApprox?: boolean;
+        error?: string;
+      }>;
       getPrStatus: (args: { taskPath: string }) => Promise<{
         success: boolean;
         pr?: {
@@ -157,6 +202,7 @@ declare global {
           mergeStateStatus?: string;
           commentsCount?: number;
           reviewCount?: number;
+          reviewers?: Array<{ login: string; state: string; submittedAt?: string | null }>;
           checksSummary?: {
             total: number;
             passed: number;
@@ -219,6 +265,7 @@ declare global {
           mergeStateStatus?: string;
           commentsCount?: number;
           reviewCount?: number;
+          reviewers?: Array<{ login: string; state: string; submittedAt?: string | null }>;
           checksSummary?: {
             total: number;
             passed: number;
@@ -275,11 +322,18 @@ declare global {
       githubAuth: () => Promise<{ success: boolean; token?: string; user?: any; error?: string }>;
       githubIsAuthenticated: () => Promise<boolean>;
       githubGetUser: () => Promise<any>;
-      githubGetRepositories: () => Promise<any[]>;
+      githubGetRepositories: () => Promise<{
+        success: boolean;
+        repositories: any[];
+        code?: string;
+        retryAfterMs?: number;
+        error?: string;
+      }>;
       githubCloneRepository: (
         repoUrl: string,
-        localPath: string
-      ) => Promise<{ success: boolean; error?: string }>;
+        localPath: string,
+        useSsh?: boolean
+      ) => Promise<{ success: boolean; path?: string; error?: string }>;
       githubGetOwners: () => Promise<{
         success: boolean;
         owners?: Array<{ login: string; type: 'User' | 'Organization' }>;
@@ -319,14 +373,19 @@ declare global {
         prTitle?: string;
         taskName?: string;
         branchName?: string;
+        force?: boolean;
       }) => Promise<{
         success: boolean;
         worktree?: any;
         branchName?: string;
         taskName?: string;
+        code?: 'LOCAL_DIVERGED' | 'COMPARE_FAILED' | 'RATE_LIMITED';
+        ahead?: number;
         error?: string;
       }>;
       githubLogout: () => Promise<void>;
+      githubStoreToken: (token: string) => Promise<{ success: boolean; error?: string }>;
+      githubGetToken: () => Promise<{ success: boolean; token?: string | null; error?: string }>;
       getSettings: () => Promise<any>;
       updateSettings: (settings: any) => Promise<void>;
       linearCheckConnection?: () => Promise<{
@@ -355,6 +414,23 @@ declare global {
         issues?: any[];
         error?: string;
       }>;
+      linearListWorkflowStates?: (teamId: string) => Promise<{
+        success: boolean;
+        states?: Array<{ id: string; name: string; type: string }>;
+        error?: string;
+      }>;
+      linearUpdateIssueState?: (
+        issueId: string,
+        stateId: string
+      ) => Promise<{
+        success: boolean;
+        issue?: {
+          id: string;
+          identifier: string;
+          state: { id: string; name: string; type: string };
+        };
+        error?: string;
+      }>;
       // Database recovery
       getDbInitError?: () => Promise<{
         success: boolean;
@@ -371,12 +447,47 @@ declare global {
         movedPath?: string;
       }>;
       onDbInitError?: (listener: (data: { message?: string; dbPath?: string }) => void) => () => void;
+      onDbRuntimeError?: (
+        listener: (data: { kind?: string; message?: string; dbPath?: string }) => void
+      ) => () => void;
       // Database methods
       getProjects: () => Promise<any[]>;
       saveProject: (project: any) => Promise<{ success: boolean; error?: string }>;
-      getTasks: (projectId?: string) => Promise<any[]>;
+      getTasks: (projectId?: string, tags?: string[]) => Promise<any[]>;
       saveTask: (task: any) => Promise<{ success: boolean; error?: string }>;
       deleteProject: (projectId: string) => Promise<{ success: boolean; error?: string }>;
+      cleanupOrphans: () => Promise<{
+        success: boolean;
+        removed?: Partial<Record<'tasks' | 'conversations' | 'messages' | 'taskTags', number>>;
+        error?: string;
+      }>;
+      getStats: () => Promise<{
+        success: boolean;
+        stats?: {
+          projects: number;
+          tasks: { total: number; byStatus: Record<string, number> };
+          conversations: number;
+          messages: number;
+          latestTaskByProject: Record<
+            string,
+            { id: string; name: string; status: string; updatedAt: string }
+          >;
+        };
+        error?: string;
+      }>;
+      globalSearch: (
+        query: string,
+        limit?: number
+      ) => Promise<{
+        success: boolean;
+        results?: Array<{
+          kind: 'project' | 'task' | 'conversation';
+          id: string;
+          label: string;
+          projectId: string;
+        }>;
+        error?: string;
+      }>;
       deleteTask: (taskId: string) => Promise<{ success: boolean; error?: string }>;
     };
   }
@@ -19,6 +19,7 @@ describe('resolveContainerConfig', () => {
           preview: true,
         },
       ],
+      allowShellStart: false,
     });
   });
 
@@ -41,6 +42,20 @@ describe('resolveContainerConfig', () => {
     ]);
   });
 
+  it('accepts udp ports but never picks them as the preview', () => {
+    const config = resolveContainerConfig({
+      ports: [
+        { service: 'dns', container: 53, protocol: 'udp', preview: true },
+        { service: 'web', container: 3000 },
+      ],
+    });
+
+    expect(config.ports).toEqual([
+      { service: 'dns', container: 53, protocol: 'udp', preview: false },
+      { service: 'web', container: 3000, protocol: 'tcp', preview: true },
+    ]);
+  });
+
   it('throws when duplicate services are provided', () => {
     expect(() =>
       resolveContainerConfig({
@@ -65,6 +80,57 @@ describe('resolveContainerConfig', () => {
     expect(config.packageManager).toBe('bun');
     expect(config.start).toBe('bun run dev');
   });
+
+  it('rejects chained start commands unless allowShellStart is set', () => {
+    const result = validateContainerConfig({ start: 'npm run build && npm start' });
+    expect(result.ok).toBe(false);
+    if (!result.ok) {
+      expect(result.error.path).toBe('start');
+    }
+
+    const config = resolveContainerConfig({
+      start: 'npm run build && npm start',
+      allowShellStart: true,
+    });
+    expect(config.start).toBe('npm run build && npm start');
+  });
+
+  it('trims compose files and profiles', () => {
+    const config = resolveContainerConfig({
+      composeFiles: [' docker-compose.yml', 'docker-compose.override.yml '],
+      composeProfiles: ['dev'],
+    });
+    expect(config.composeFiles).toEqual(['docker-compose.yml', 'docker-compose.override.yml']);
+    expect(config.composeProfiles).toEqual(['dev']);
+  });
+
+  it('rejects memory limits without a unit', () => {
+    const result = validateContainerConfig({ resources: { memory: '2048' } });
+    expect(result.ok).toBe(false);
+    if (!result.ok) {
+      expect(result.error.path).toBe('resources.memory');
+    }
+  });
+
+  it('accepts a custom image and rejects an empty one', () => {
+    expect(resolveContainerConfig({ image: ' python:3.12 ' }).image).toBe('python:3.12');
+    expect(() => resolveContainerConfig({ image: '  ' })).toThrow(ContainerConfigError);
+  });
+
+  it('accepts a dockerfile path and rejects an empty one', () => {
+    expect(resolveContainerConfig({ dockerfile: ' docker/Dockerfile.dev ' }).dockerfile).toBe(
+      'docker/Dockerfile.dev'
+    );
+    expect(() => resolveContainerConfig({ dockerfile: '' })).toThrow(ContainerConfigError);
+  });
+
+  it('rejects empty compose file entries', () => {
+    const result = validateContainerConfig({ composeFiles: ['docker-compose.yml', ''] });
+    expect(result.ok).toBe(false);
+    if (!result.ok) {
+      expect(result.error.path).toBe('composeFiles[1]');
+    }
+  });
 });
 
 describe('validateContainerConfig', () => {
@@ -7,11 +7,18 @@ export const DEFAULT_PREVIEW_SERVICE = 'app';
 
 export type PackageManager = 'npm' | 'pnpm' | 'yarn' | 'bun';
 
+export type ContainerPortProtocol = 'tcp' | 'udp';
+
 export interface ContainerPortConfig {
   service: string;
   container: number;
   preview?: boolean;
-  protocol?: 'tcp';
+  protocol?: ContainerPortProtocol;
+}
+
+export interface ContainerResourcesConfig {
+  memory?: string;
+  cpus?: string | number;
 }
 
 export interface ContainerConfigFile {
@@ -21,12 +28,26 @@ export interface ContainerConfigFile {
   envFile?: string;
   workdir?: string;
   ports?: ContainerPortConfig[];
+  composeFiles?: string[];
+  composeProfiles?: string[];
+  resources?: ContainerResourcesConfig;
+  allowShellStart?: boolean;
+  /**
+   * Overrides the base image picked from the package manager. The image must provide
+   * `bash`, since the install and start script runs via `bash -lc`.
+   */
+  image?: string;
+  /**
+   * Dockerfile (relative to the task root) to build the workspace image from. Takes
+   * precedence over `image`; the build is cached until the Dockerfile or lockfiles change.
+   */
+  dockerfile?: string;
 }
 
 export interface ResolvedContainerPortConfig {
   service: string;
   container: number;
-  protocol: 'tcp';
+  protocol: ContainerPortProtocol;
   preview: boolean;
 }
 
@@ -37,6 +58,12 @@ export interface ResolvedContainerConfig {
   envFile?: string;
   workdir: string;
   ports: ResolvedContainerPortConfig[];
+  composeFiles?: string[];
+  composeProfiles?: string[];
+  resources?: { memory?: string; cpus?: string };
+  allowShellStart: boolean;
+  image?: string;
+  dockerfile?: string;
 }
 
 export interface ResolveContainerConfigOptions {
@@ -80,10 +107,16 @@ export function resolveContainerConfig(
     source.packageManager,
     options.inferredPackageManager
   );
-  const start = resolveStartCommand(source.start, packageManager);
+  const allowShellStart = resolveAllowShellStart(source.allowShellStart);
+  const start = resolveStartCommand(source.start, packageManager, allowShellStart);
   const envFile = resolveEnvFile(source.envFile);
+  const image = resolveImage(source.image);
+  const dockerfile = resolveDockerfile(source.dockerfile);
   const workdir = resolveWorkdir(source.workdir);
   const ports = resolvePorts(source.ports);
+  const composeFiles = resolveStringList(source.composeFiles, 'composeFiles');
+  const composeProfiles = resolveStringList(source.composeProfiles, 'composeProfiles');
+  const resources = resolveResources(source.resources);
 
   return {
     version,
@@ -92,6 +125,12 @@ export function resolveContainerConfig(
     envFile,
     workdir,
     ports,
+    composeFiles,
+    composeProfiles,
+    resources,
+    allowShellStart,
+    image,
+    dockerfile,
   };
 }
 
@@ -124,7 +163,21 @@ function resolvePackageManager(raw: unknown, inferred?: PackageManager): Package
   return normalized as PackageManager;
 }
 
-function resolveStartCommand(raw: unknown, packageManager: PackageManager): string {
+const SHELL_START_OPERATORS = [';', '&&', '`', '$(', '|', '\n'];
+
+function resolveAllowShellStart(raw: unknown): boolean {
+  if (raw == null) return false;
+  if (typeof raw !== 'boolean') {
+    throw new ContainerConfigError('`allowShellStart` must be a boolean', 'allowShellStart');
+  }
+  return raw;
+}
+
+function resolveStartCommand(
+  raw: unknown,
+  packageManager: PackageManager,
+  allowShell: boolean
+): string {
   if (raw == null) {
     return packageManager === 'bun' ? DEFAULT_BUN_START_COMMAND : DEFAULT_START_COMMAND;
   }
@@ -135,6 +188,12 @@ function resolveStartCommand(raw: unknown, packageManager: PackageManager): stri
   if (normalized.length === 0) {
     throw new ContainerConfigError('`start` cannot be empty', 'start');
   }
+  if (!allowShell && SHELL_START_OPERATORS.some((op) => normalized.includes(op))) {
+    throw new ContainerConfigError(
+      '`start` contains shell operators (;, &&, |, backticks, or $( ). The start command runs through a shell inside the container, so chained or substituted commands are rejected by default to avoid running unintended code from a checked-in config. Set "allowShellStart": true in .emdash/config.json if you trust this command.',
+      'start'
+    );
+  }
   return normalized;
 }
 
@@ -150,6 +209,30 @@ function resolveEnvFile(raw: unknown): string | undefined {
   return normalized;
 }
 
+function resolveImage(raw: unknown): string | undefined {
+  if (raw == null) return undefined;
+  if (typeof raw !== 'string') {
+    throw new ContainerConfigError('`image` must be a string', 'image');
+  }
+  const normalized = raw.trim();
+  if (normalized.length === 0) {
+    throw new ContainerConfigError('`image` cannot be empty', 'image');
+  }
+  return normalized;
+}
+
+function resolveDockerfile(raw: unknown): string | undefined {
+  if (raw == null) return undefined;
+  if (typeof raw !== 'string') {
+    throw new ContainerConfigError('`dockerfile` must be a string path', 'dockerfile');
+  }
+  const normalized = raw.trim();
+  if (normalized.length === 0) {
+    throw new ContainerConfigError('`dockerfile` cannot be empty', 'dockerfile');
+  }
+  return normalized;
+}
+
 function resolveWorkdir(raw: unknown): string {
   if (raw == null) return DEFAULT_WORKDIR;
   if (typeof raw !== 'string') {
@@ -162,6 +245,57 @@ function resolveWorkdir(raw: unknown): string {
   return normalized;
 }
 
+function resolveStringList(raw: unknown, key: string): string[] | undefined {
+  if (raw == null) return undefined;
+  if (!Array.isArray(raw)) {
+    throw new ContainerConfigError(`\`${key}\` must be an array of strings`, key);
+  }
+  return raw.map((entry, index) => {
+    if (typeof entry !== 'string' || entry.trim().length === 0) {
+      throw new ContainerConfigError(
+        `\`${key}\` entries must be non-empty strings`,
+        `${key}[${index}]`
+      );
+    }
+    return entry.trim();
+  });
+}
+
+function resolveResources(raw: unknown): { memory?: string; cpus?: string } | undefined {
+  if (raw == null) return undefined;
+  if (typeof raw !== 'object' || Array.isArray(raw)) {
+    throw new ContainerConfigError('`resources` must be an object', 'resources');
+  }
+  const { memory, cpus } = raw as Record<string, unknown>;
+  let resolvedMemory: string | undefined;
+  if (memory != null) {
+    const normalized = typeof memory === 'string' ? memory.trim().toLowerCase() : '';
+    const match = /^(\d+)[bkmg]$/.exec(normalized);
+    if (!match || Number(match[1]) === 0) {
+      throw new ContainerConfigError(
+        '`resources.memory` must be a number with a unit (b, k, m, or g), e.g. "2g"',
+        'resources.memory'
+      );
+    }
+    resolvedMemory = normalized;
+  }
+  let resolvedCpus: string | undefined;
+  if (cpus != null) {
+    const text =
+      typeof cpus === 'number' ? String(cpus) : typeof cpus === 'string' ? cpus.trim() : '';
+    const value = Number(text);
+    if (text.length === 0 || !Number.isFinite(value) || value <= 0) {
+      throw new ContainerConfigError(
+        '`resources.cpus` must be a positive number, e.g. "1.5"',
+        'resources.cpus'
+      );
+    }
+    resolvedCpus = text;
+  }
+  if (resolvedMemory === undefined && resolvedCpus === undefined) return undefined;
+  return { memory: resolvedMemory, cpus: resolvedCpus };
+}
+
 function resolvePorts(raw: unknown): ResolvedContainerPortConfig[] {
   if (raw == null) return [cloneDefaultPort()];
   if (!Array.isArray(raw)) {
@@ -190,8 +324,8 @@ function resolvePorts(raw: unknown): ResolvedContainerPortConfig[] {
         `${path}.container`
       );
     }
-    if (protocol != null && protocol !== 'tcp') {
-      throw new ContainerConfigError('Only TCP protocol is supported in M1', `${path}.protocol`);
+    if (protocol != null && protocol !== 'tcp' && protocol !== 'udp') {
+      throw new ContainerConfigError('`protocol` must be "tcp" or "udp"', `${path}.protocol`);
     }
     if (preview != null && typeof preview !== 'boolean') {
       throw new ContainerConfigError(
@@ -202,7 +336,7 @@ function resolvePorts(raw: unknown): ResolvedContainerPortConfig[] {
     result.push({
       service: service.trim(),
       container,
-      protocol: 'tcp',
+      protocol: protocol === 'udp' ? 'udp' : 'tcp',
       preview: preview === true,
     });
   });
@@ -214,7 +348,8 @@ function resolvePorts(raw: unknown): ResolvedContainerPortConfig[] {
 }
 
 function ensurePreviewPort(ports: ResolvedContainerPortConfig[]): void {
-  const idx = ports.findIndex((port) => port.preview);
+  // Previews are HTTP, so only TCP ports can be the preview target.
+  const idx = ports.findIndex((port) => port.preview && port.protocol === 'tcp');
   if (idx >= 0) {
     // Normalize to boolean true for first preview, false for the rest.
     ports.forEach((port, index) => {
@@ -222,7 +357,10 @@ function ensurePreviewPort(ports: ResolvedContainerPortConfig[]): void {
     });
     return;
   }
-  ports[0] = { ...ports[0], preview: true };
+  const firstTcp = ports.findIndex((port) => port.protocol === 'tcp');
+  ports.forEach((port, index) => {
+    port.preview = index === firstTcp;
+  });
 }
 
 function ensureUniqueServices(ports: ResolvedContainerPortConfig[]): void {
@@ -246,6 +384,7 @@ export function createDefaultContainerConfig(): ResolvedContainerConfig {
     start: DEFAULT_START_COMMAND,
     workdir: DEFAULT_WORKDIR,
     ports: [cloneDefaultPort()],
+    allowShellStart: false,
   };
 }
 
@@ -261,6 +400,8 @@ export type ContainerConfigSchema = {
     };
     readonly start: { readonly type: 'string'; readonly minLength: 1 };
     readonly envFile: { readonly type: 'string'; readonly minLength: 1 };
+    readonly image: { readonly type: 'string'; readonly minLength: 1 };
+    readonly dockerfile: { readonly type: 'string'; readonly minLength: 1 };
     readonly workdir: { readonly type: 'string'; readonly minLength: 1 };
     readonly ports: {
       readonly type: 'array';
@@ -277,10 +418,30 @@ export type ContainerConfigSchema = {
             readonly maximum: 65535;
           };
           readonly preview: { readonly type: 'boolean' };
-          readonly protocol: { readonly type: 'string'; readonly enum: readonly ['tcp'] };
+          readonly protocol: {
+            readonly type: 'string';
+            readonly enum: readonly ['tcp', 'udp'];
+          };
         };
       };
     };
+    readonly composeFiles: {
+      readonly type: 'array';
+      readonly items: { readonly type: 'string'; readonly minLength: 1 };
+    };
+    readonly composeProfiles: {
+      readonly type: 'array';
+      readonly items: { readonly type: 'string'; readonly minLength: 1 };
+    };
+    readonly allowShellStart: { readonly type: 'boolean' };
+    readonly resources: {
+      readonly type: 'object';
+      readonly additionalProperties: false;
+      readonly properties: {
+        readonly memory: { readonly type: 'string'; readonly pattern: string };
+        readonly cpus: { readonly type: readonly ['string', 'number'] };
+      };
+    };
   };
 };
 
@@ -293,6 +454,8 @@ export const CONTAINER_CONFIG_JSON_SCHEMA: ContainerConfigSchema = {
     packageManager: { type: 'string', enum: ['npm', 'pnpm', 'yarn', 'bun'] as const },
     start: { type: 'string', minLength: 1 },
     envFile: { type: 'string', minLength: 1 },
+    image: { type: 'string', minLength: 1 },
+    dockerfile: { type: 'string', minLength: 1 },
     workdir: { type: 'string', minLength: 1 },
     ports: {
       type: 'array',
@@ -305,10 +468,21 @@ export const CONTAINER_CONFIG_JSON_SCHEMA: ContainerConfigSchema = {
           service: { type: 'string', minLength: 1 },
           container: { type: 'integer', minimum: 1, maximum: 65535 },
           preview: { type: 'boolean' },
-          protocol: { type: 'string', enum: ['tcp'] as const },
+          protocol: { type: 'string', enum: ['tcp', 'udp'] as const },
         },
       },
     },
+    composeFiles: { type: 'array', items: { type: 'string', minLength: 1 } },
+    composeProfiles: { type: 'array', items: { type: 'string', minLength: 1 } },
+    allowShellStart: { type: 'boolean' },
+    resources: {
+      type: 'object',
+      additionalProperties: false,
+      properties: {
+        memory: { type: 'string', pattern: '^[0-9]+[bkmgBKMG]$' },
+        cpus: { type: ['string', 'number'] as const },
+      },
+    },
   },
 };
 
@@ -45,7 +45,7 @@ export interface RunnerLifecycleEvent extends RunnerEventEnvelope<'lifecycle'> {
 
 export interface RunnerPortMapping {
   service: string;
-  protocol: 'tcp';
+  protocol: 'tcp' | 'udp';
   container: number;
   host: number;
   url?: string;
@@ -12,6 +12,7 @@ const baseConfig: ResolvedContainerConfig = {
     { service: 'app', container: 3000, protocol: 'tcp', preview: true },
     { service: 'inspect', container: 9229, protocol: 'tcp', preview: false },
   ],
+  allowShellStart: false,
 };
 
 describe('generateMockStartEvents', () => {
@@ -1,5 +1,12 @@
 import { relations, sql } from 'drizzle-orm';
-import { index, integer, sqliteTable, text, uniqueIndex } from 'drizzle-orm/sqlite-core';
+import {
+  index,
+  integer,
+  primaryKey,
+  sqliteTable,
+  text,
+  uniqueIndex,
+} from 'drizzle-orm/sqlite-core';
 
 export const projects = sqliteTable(
   'projects',
@@ -10,6 +17,7 @@ export const projects = sqliteTable(
     gitRemote: text('git_remote'),
     gitBranch: text('git_branch'),
     baseRef: text('base_ref'),
+    settings: text('settings'),
     githubRepository: text('github_repository'),
     githubConnected: integer('github_connected').notNull().default(0),
     createdAt: text('created_at')
@@ -49,6 +57,20 @@ export const tasks = sqliteTable(
   })
 );
 
+export const taskTags = sqliteTable(
+  'task_tags',
+  {
+    taskId: text('task_id')
+      .notNull()
+      .references(() => tasks.id, { onDelete: 'cascade' }),
+    tag: text('tag').notNull(),
+  },
+  (table) => ({
+    pk: primaryKey({ columns: [table.taskId, table.tag] }),
+    tagIdx: index('idx_task_tags_tag').on(table.tag),
+  })
+);
+
 export const conversations = sqliteTable(
   'conversations',
   {
@@ -82,10 +104,13 @@ export const messages = sqliteTable(
       .notNull()
       .default(sql`CURRENT_TIMESTAMP`),
     metadata: text('metadata'),
+    providerId: text('provider_id'),
+    model: text('model'),
   },
   (table) => ({
     conversationIdIdx: index('idx_messages_conversation_id').on(table.conversationId),
     timestampIdx: index('idx_messages_timestamp').on(table.timestamp),
+    providerModelIdx: index('idx_messages_provider_model').on(table.providerId, table.model),
   })
 );
 
@@ -99,6 +124,14 @@ export const tasksRelations = relations(tasks, ({ one, many }) => ({
     references: [projects.id],
   }),
   conversations: many(conversations),
+  tags: many(taskTags),
+}));
+
+export const taskTagsRelations = relations(taskTags, ({ one }) => ({
+  task: one(tasks, {
+    fields: [taskTags.taskId],
+    references: [tasks.id],
+  }),
 }));
 
 export const conversationsRelations = relations(conversations, ({ one, many }) => ({
@@ -118,5 +151,6 @@ export const messagesRelations = relations(messages, ({ one }) => ({
 
 export type ProjectRow = typeof projects.$inferSelect;
 export type TaskRow = typeof tasks.$inferSelect;
+export type TaskTagRow = typeof taskTags.$inferSelect;
 export type ConversationRow = typeof conversations.$inferSelect;
 export type MessageRow = typeof messages.$inferSelect;